@@ -0,0 +1,515 @@
+//! Printing the lowered HIR of a file, for debugging.
+//!
+//! Each HIR node is printed on its own line, indented under its parent. Nodes with no pointer back
+//! to the syntax tree are marked with `(no ptr)`, so gaps in lowering are visible. Missing nodes,
+//! e.g. from parse errors, are printed as `<none>`.
+
+use std::fmt::{self, Write as _};
+
+pub(crate) fn get(file: &mlb_statics::SourceFile) -> String {
+  let mut p = Printer {
+    file,
+    buf: String::new(),
+    depth: 0,
+  };
+  p.str_dec(file.lowered.root);
+  p.buf
+}
+
+struct Printer<'a> {
+  file: &'a mlb_statics::SourceFile,
+  buf: String,
+  depth: usize,
+}
+
+impl Printer<'_> {
+  fn line(&mut self, label: fmt::Arguments<'_>) {
+    for _ in 0..self.depth {
+      self.buf.push_str("  ");
+    }
+    writeln!(self.buf, "{label}").unwrap();
+  }
+
+  fn node(&mut self, idx: sml_hir::Idx, label: fmt::Arguments<'_>) {
+    if self.file.lowered.ptrs.hir_to_ast(idx).is_some() {
+      self.line(label);
+    } else {
+      self.line(format_args!("{label} (no ptr)"));
+    }
+  }
+
+  fn none(&mut self) {
+    self.line(format_args!("<none>"));
+  }
+
+  /// runs `f` with the depth increased by one.
+  fn nest<F>(&mut self, f: F)
+  where
+    F: FnOnce(&mut Self),
+  {
+    self.depth += 1;
+    f(self);
+    self.depth -= 1;
+  }
+
+  fn str_dec(&mut self, str_dec: sml_hir::StrDecIdx) {
+    let str_dec = match str_dec {
+      Some(x) => x,
+      None => return self.none(),
+    };
+    let idx = str_dec.into();
+    match &self.file.lowered.arenas.str_dec[str_dec] {
+      sml_hir::StrDec::Dec(dec) => {
+        self.node(idx, format_args!("StrDec::Dec"));
+        self.nest(|p| p.dec(*dec));
+      }
+      sml_hir::StrDec::Structure(str_binds) => {
+        self.node(idx, format_args!("StrDec::Structure"));
+        self.nest(|p| {
+          for str_bind in str_binds {
+            p.line(format_args!("StrBind {}", str_bind.name));
+            p.nest(|p| p.str_exp(str_bind.str_exp));
+          }
+        });
+      }
+      sml_hir::StrDec::Local(local_dec, in_dec) => {
+        self.node(idx, format_args!("StrDec::Local"));
+        self.nest(|p| {
+          p.str_dec(*local_dec);
+          p.str_dec(*in_dec);
+        });
+      }
+      sml_hir::StrDec::Seq(str_decs) => {
+        self.node(idx, format_args!("StrDec::Seq"));
+        self.nest(|p| str_decs.iter().for_each(|&x| p.str_dec(x)));
+      }
+      sml_hir::StrDec::Signature(sig_binds) => {
+        self.node(idx, format_args!("StrDec::Signature"));
+        self.nest(|p| {
+          for sig_bind in sig_binds {
+            p.line(format_args!("SigBind {}", sig_bind.name));
+            p.nest(|p| p.sig_exp(sig_bind.sig_exp));
+          }
+        });
+      }
+      sml_hir::StrDec::Functor(functor_binds) => {
+        self.node(idx, format_args!("StrDec::Functor"));
+        self.nest(|p| {
+          for fun_bind in functor_binds {
+            p.line(format_args!(
+              "FunctorBind {} ({})",
+              fun_bind.functor_name, fun_bind.param_name
+            ));
+            p.nest(|p| {
+              p.sig_exp(fun_bind.param_sig);
+              p.str_exp(fun_bind.body);
+            });
+          }
+        });
+      }
+    }
+  }
+
+  fn str_exp(&mut self, str_exp: sml_hir::StrExpIdx) {
+    let str_exp = match str_exp {
+      Some(x) => x,
+      None => return self.none(),
+    };
+    let idx = str_exp.into();
+    match &self.file.lowered.arenas.str_exp[str_exp] {
+      sml_hir::StrExp::Struct(str_dec) => {
+        self.node(idx, format_args!("StrExp::Struct"));
+        self.nest(|p| p.str_dec(*str_dec));
+      }
+      sml_hir::StrExp::Path(path) => self.node(idx, format_args!("StrExp::Path {path}")),
+      sml_hir::StrExp::Ascription(inner, asc, sig_exp) => {
+        let asc = match asc {
+          sml_hir::Ascription::Transparent => ":",
+          sml_hir::Ascription::Opaque => ":>",
+        };
+        self.node(idx, format_args!("StrExp::Ascription {asc}"));
+        self.nest(|p| {
+          p.str_exp(*inner);
+          p.sig_exp(*sig_exp);
+        });
+      }
+      sml_hir::StrExp::App(name, arg) => {
+        self.node(idx, format_args!("StrExp::App {name}"));
+        self.nest(|p| p.str_exp(*arg));
+      }
+      sml_hir::StrExp::Let(str_dec, inner) => {
+        self.node(idx, format_args!("StrExp::Let"));
+        self.nest(|p| {
+          p.str_dec(*str_dec);
+          p.str_exp(*inner);
+        });
+      }
+    }
+  }
+
+  fn sig_exp(&mut self, sig_exp: sml_hir::SigExpIdx) {
+    let sig_exp = match sig_exp {
+      Some(x) => x,
+      None => return self.none(),
+    };
+    let idx = sig_exp.into();
+    match &self.file.lowered.arenas.sig_exp[sig_exp] {
+      sml_hir::SigExp::Spec(spec) => {
+        self.node(idx, format_args!("SigExp::Spec"));
+        self.nest(|p| p.spec(*spec));
+      }
+      sml_hir::SigExp::Name(name) => self.node(idx, format_args!("SigExp::Name {name}")),
+      sml_hir::SigExp::WhereType(inner, ty_vars, path, ty) => {
+        let ty_vars = ty_vars_str(ty_vars);
+        self.node(idx, format_args!("SigExp::WhereType{ty_vars} {path}"));
+        self.nest(|p| {
+          p.sig_exp(*inner);
+          p.ty(*ty);
+        });
+      }
+      sml_hir::SigExp::Where(inner, lhs, rhs) => {
+        self.node(idx, format_args!("SigExp::Where {lhs} = {rhs}"));
+        self.nest(|p| p.sig_exp(*inner));
+      }
+    }
+  }
+
+  fn spec(&mut self, spec: sml_hir::SpecIdx) {
+    let spec = match spec {
+      Some(x) => x,
+      None => return self.none(),
+    };
+    let idx = spec.into();
+    match &self.file.lowered.arenas.spec[spec] {
+      sml_hir::Spec::Val(ty_vars, val_descs) => {
+        let ty_vars = ty_vars_str(ty_vars);
+        self.node(idx, format_args!("Spec::Val{ty_vars}"));
+        self.nest(|p| {
+          for val_desc in val_descs {
+            p.line(format_args!("ValDesc {}", val_desc.name));
+            p.nest(|p| p.ty(val_desc.ty));
+          }
+        });
+      }
+      sml_hir::Spec::Ty(ty_desc) => {
+        let ty_vars = ty_vars_str(&ty_desc.ty_vars);
+        self.node(idx, format_args!("Spec::Ty{ty_vars} {}", ty_desc.name));
+      }
+      sml_hir::Spec::EqTy(ty_desc) => {
+        let ty_vars = ty_vars_str(&ty_desc.ty_vars);
+        self.node(idx, format_args!("Spec::EqTy{ty_vars} {}", ty_desc.name));
+      }
+      sml_hir::Spec::Datatype(dat_desc) => {
+        self.node(idx, format_args!("Spec::Datatype"));
+        self.nest(|p| p.dat_bind(dat_desc));
+      }
+      sml_hir::Spec::DatatypeCopy(name, path) => {
+        self.node(idx, format_args!("Spec::DatatypeCopy {name} = {path}"));
+      }
+      sml_hir::Spec::Exception(ex_desc) => {
+        self.node(idx, format_args!("Spec::Exception {}", ex_desc.name));
+        if let Some(ty) = ex_desc.ty {
+          self.nest(|p| p.ty(ty));
+        }
+      }
+      sml_hir::Spec::Str(str_desc) => {
+        self.node(idx, format_args!("Spec::Str {}", str_desc.name));
+        self.nest(|p| p.sig_exp(str_desc.sig_exp));
+      }
+      sml_hir::Spec::Include(sig_exp) => {
+        self.node(idx, format_args!("Spec::Include"));
+        self.nest(|p| p.sig_exp(*sig_exp));
+      }
+      sml_hir::Spec::Sharing(inner, kind, paths) => {
+        let kind = match kind {
+          sml_hir::SharingKind::Regular => "sharing type",
+          sml_hir::SharingKind::Derived => "sharing",
+        };
+        let paths: Vec<_> = paths.iter().map(ToString::to_string).collect();
+        self.node(
+          idx,
+          format_args!("Spec::Sharing {kind} {}", paths.join(" = ")),
+        );
+        self.nest(|p| p.spec(*inner));
+      }
+      sml_hir::Spec::Seq(specs) => {
+        self.node(idx, format_args!("Spec::Seq"));
+        self.nest(|p| specs.iter().for_each(|&x| p.spec(x)));
+      }
+    }
+  }
+
+  fn exp(&mut self, exp: sml_hir::ExpIdx) {
+    let exp = match exp {
+      Some(x) => x,
+      None => return self.none(),
+    };
+    let idx = exp.into();
+    match &self.file.lowered.arenas.exp[exp] {
+      sml_hir::Exp::Hole => self.node(idx, format_args!("Exp::Hole")),
+      sml_hir::Exp::SCon(scon) => self.node(idx, format_args!("Exp::SCon {}", SConDisplay(scon))),
+      sml_hir::Exp::Path(path) => self.node(idx, format_args!("Exp::Path {path}")),
+      sml_hir::Exp::Record(rows) => {
+        self.node(idx, format_args!("Exp::Record"));
+        self.nest(|p| {
+          for (lab, exp) in rows {
+            p.line(format_args!("{lab} ="));
+            p.nest(|p| p.exp(*exp));
+          }
+        });
+      }
+      sml_hir::Exp::Vector(exps) => {
+        self.node(idx, format_args!("Exp::Vector"));
+        self.nest(|p| exps.iter().for_each(|&x| p.exp(x)));
+      }
+      sml_hir::Exp::Let(dec, exp) => {
+        self.node(idx, format_args!("Exp::Let"));
+        self.nest(|p| {
+          p.dec(*dec);
+          p.exp(*exp);
+        });
+      }
+      sml_hir::Exp::App(func, arg) => {
+        self.node(idx, format_args!("Exp::App"));
+        self.nest(|p| {
+          p.exp(*func);
+          p.exp(*arg);
+        });
+      }
+      sml_hir::Exp::Handle(exp, matcher) => {
+        self.node(idx, format_args!("Exp::Handle"));
+        self.nest(|p| {
+          p.exp(*exp);
+          p.matcher(matcher);
+        });
+      }
+      sml_hir::Exp::Raise(exp) => {
+        self.node(idx, format_args!("Exp::Raise"));
+        self.nest(|p| p.exp(*exp));
+      }
+      sml_hir::Exp::Fn(matcher) => {
+        self.node(idx, format_args!("Exp::Fn"));
+        self.nest(|p| p.matcher(matcher));
+      }
+      sml_hir::Exp::Typed(exp, ty) => {
+        self.node(idx, format_args!("Exp::Typed"));
+        self.nest(|p| {
+          p.exp(*exp);
+          p.ty(*ty);
+        });
+      }
+    }
+  }
+
+  fn matcher(&mut self, matcher: &[(sml_hir::PatIdx, sml_hir::ExpIdx)]) {
+    for &(pat, exp) in matcher {
+      self.line(format_args!("MatchRule"));
+      self.nest(|p| {
+        p.pat(pat);
+        p.exp(exp);
+      });
+    }
+  }
+
+  fn dec(&mut self, dec: sml_hir::DecIdx) {
+    let dec = match dec {
+      Some(x) => x,
+      None => return self.none(),
+    };
+    let idx = dec.into();
+    match &self.file.lowered.arenas.dec[dec] {
+      sml_hir::Dec::Hole => self.node(idx, format_args!("Dec::Hole")),
+      sml_hir::Dec::Val(ty_vars, val_binds) => {
+        let ty_vars = ty_vars_str(ty_vars);
+        self.node(idx, format_args!("Dec::Val{ty_vars}"));
+        self.nest(|p| {
+          for val_bind in val_binds {
+            let rec = if val_bind.rec { " rec" } else { "" };
+            p.line(format_args!("ValBind{rec}"));
+            p.nest(|p| {
+              p.pat(val_bind.pat);
+              p.exp(val_bind.exp);
+            });
+          }
+        });
+      }
+      sml_hir::Dec::Ty(ty_binds) => {
+        self.node(idx, format_args!("Dec::Ty"));
+        self.nest(|p| p.ty_binds(ty_binds));
+      }
+      sml_hir::Dec::Datatype(dat_binds, ty_binds) => {
+        self.node(idx, format_args!("Dec::Datatype"));
+        self.nest(|p| {
+          dat_binds.iter().for_each(|x| p.dat_bind(x));
+          p.ty_binds(ty_binds);
+        });
+      }
+      sml_hir::Dec::DatatypeCopy(name, path) => {
+        self.node(idx, format_args!("Dec::DatatypeCopy {name} = {path}"));
+      }
+      sml_hir::Dec::Abstype(dat_binds, ty_binds, dec) => {
+        self.node(idx, format_args!("Dec::Abstype"));
+        self.nest(|p| {
+          dat_binds.iter().for_each(|x| p.dat_bind(x));
+          p.ty_binds(ty_binds);
+          p.dec(*dec);
+        });
+      }
+      sml_hir::Dec::Exception(ex_binds) => {
+        self.node(idx, format_args!("Dec::Exception"));
+        self.nest(|p| {
+          for ex_bind in ex_binds {
+            match ex_bind {
+              sml_hir::ExBind::New(name, ty) => {
+                p.line(format_args!("ExBind::New {name}"));
+                if let Some(ty) = ty {
+                  p.nest(|p| p.ty(*ty));
+                }
+              }
+              sml_hir::ExBind::Copy(name, path) => {
+                p.line(format_args!("ExBind::Copy {name} = {path}"));
+              }
+            }
+          }
+        });
+      }
+      sml_hir::Dec::Local(local_dec, in_dec) => {
+        self.node(idx, format_args!("Dec::Local"));
+        self.nest(|p| {
+          p.dec(*local_dec);
+          p.dec(*in_dec);
+        });
+      }
+      sml_hir::Dec::Open(paths) => {
+        let paths: Vec<_> = paths.iter().map(ToString::to_string).collect();
+        self.node(idx, format_args!("Dec::Open {}", paths.join(" ")));
+      }
+      sml_hir::Dec::Seq(decs) => {
+        self.node(idx, format_args!("Dec::Seq"));
+        self.nest(|p| decs.iter().for_each(|&x| p.dec(x)));
+      }
+    }
+  }
+
+  fn ty_binds(&mut self, ty_binds: &[sml_hir::TyBind]) {
+    for ty_bind in ty_binds {
+      let ty_vars = ty_vars_str(&ty_bind.ty_vars);
+      self.line(format_args!("TyBind{ty_vars} {}", ty_bind.name));
+      self.nest(|p| p.ty(ty_bind.ty));
+    }
+  }
+
+  fn dat_bind(&mut self, dat_bind: &sml_hir::DatBind) {
+    let ty_vars = ty_vars_str(&dat_bind.ty_vars);
+    self.line(format_args!("DatBind{ty_vars} {}", dat_bind.name));
+    self.nest(|p| {
+      for con_bind in dat_bind.cons.iter() {
+        p.line(format_args!("ConBind {}", con_bind.name));
+        if let Some(ty) = con_bind.ty {
+          p.nest(|p| p.ty(ty));
+        }
+      }
+    });
+  }
+
+  fn pat(&mut self, pat: sml_hir::PatIdx) {
+    let pat = match pat {
+      Some(x) => x,
+      None => return self.none(),
+    };
+    let idx = pat.into();
+    match &self.file.lowered.arenas.pat[pat] {
+      sml_hir::Pat::Wild => self.node(idx, format_args!("Pat::Wild")),
+      sml_hir::Pat::SCon(scon) => self.node(idx, format_args!("Pat::SCon {}", SConDisplay(scon))),
+      sml_hir::Pat::Con(path, arg) => {
+        self.node(idx, format_args!("Pat::Con {path}"));
+        if let Some(arg) = arg {
+          self.nest(|p| p.pat(*arg));
+        }
+      }
+      sml_hir::Pat::Record { rows, allows_other } => {
+        let rest = if *allows_other { " ..." } else { "" };
+        self.node(idx, format_args!("Pat::Record{rest}"));
+        self.nest(|p| {
+          for (lab, pat) in rows {
+            p.line(format_args!("{lab} ="));
+            p.nest(|p| p.pat(*pat));
+          }
+        });
+      }
+      sml_hir::Pat::Vector(pats) => {
+        self.node(idx, format_args!("Pat::Vector"));
+        self.nest(|p| pats.iter().for_each(|&x| p.pat(x)));
+      }
+      sml_hir::Pat::Typed(pat, ty) => {
+        self.node(idx, format_args!("Pat::Typed"));
+        self.nest(|p| {
+          p.pat(*pat);
+          p.ty(*ty);
+        });
+      }
+      sml_hir::Pat::As(name, pat) => {
+        self.node(idx, format_args!("Pat::As {name}"));
+        self.nest(|p| p.pat(*pat));
+      }
+      sml_hir::Pat::Or(or_pat) => {
+        self.node(idx, format_args!("Pat::Or"));
+        self.nest(|p| {
+          p.pat(or_pat.first);
+          or_pat.rest.iter().for_each(|&x| p.pat(x));
+        });
+      }
+    }
+  }
+
+  fn ty(&mut self, ty: sml_hir::TyIdx) {
+    let ty = match ty {
+      Some(x) => x,
+      None => return self.none(),
+    };
+    let idx = ty.into();
+    match &self.file.lowered.arenas.ty[ty] {
+      sml_hir::Ty::Hole => self.node(idx, format_args!("Ty::Hole")),
+      sml_hir::Ty::Var(ty_var) => self.node(idx, format_args!("Ty::Var {ty_var}")),
+      sml_hir::Ty::Record(rows) => {
+        self.node(idx, format_args!("Ty::Record"));
+        self.nest(|p| {
+          for (lab, ty) in rows {
+            p.line(format_args!("{lab} :"));
+            p.nest(|p| p.ty(*ty));
+          }
+        });
+      }
+      sml_hir::Ty::Con(args, path) => {
+        self.node(idx, format_args!("Ty::Con {path}"));
+        self.nest(|p| args.iter().for_each(|&x| p.ty(x)));
+      }
+      sml_hir::Ty::Fn(param, res) => {
+        self.node(idx, format_args!("Ty::Fn"));
+        self.nest(|p| {
+          p.ty(*param);
+          p.ty(*res);
+        });
+      }
+    }
+  }
+}
+
+/// returns e.g. ` 'a 'b` for `'a`, `'b`.
+fn ty_vars_str(ty_vars: &[sml_hir::TyVar]) -> String {
+  ty_vars.iter().map(|x| format!(" {x}")).collect()
+}
+
+struct SConDisplay<'a>(&'a sml_hir::SCon);
+
+impl fmt::Display for SConDisplay<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.0 {
+      sml_hir::SCon::Int(x) => write!(f, "{x}"),
+      sml_hir::SCon::Real(x) => write!(f, "{x}"),
+      sml_hir::SCon::Word(x) => write!(f, "0w{x}"),
+      sml_hir::SCon::Char(x) => write!(f, "#{:?}", x.to_string()),
+      sml_hir::SCon::String(x) => write!(f, "{x:?}"),
+    }
+  }
+}
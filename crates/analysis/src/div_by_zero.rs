@@ -0,0 +1,44 @@
+//! Detecting integer division or modulo by a literal zero.
+
+/// Returns the indices of applications of the std basis `div` or `mod` to a pair whose right
+/// operand is a literal zero.
+pub(crate) fn get(file: &mlb_statics::SourceFile) -> Vec<sml_hir::Idx> {
+  let arenas = &file.lowered.arenas;
+  arenas
+    .exp
+    .iter()
+    .filter_map(|(idx, exp)| {
+      let (func, arg) = match exp {
+        sml_hir::Exp::App(func, arg) => ((*func)?, (*arg)?),
+        _ => return None,
+      };
+      let path = match &arenas.exp[func] {
+        sml_hir::Exp::Path(path) => path,
+        _ => return None,
+      };
+      if !matches!(path.last().as_str(), "div" | "mod") || !is_std_basis(file, func.into()) {
+        return None;
+      }
+      let rows = match &arenas.exp[arg] {
+        sml_hir::Exp::Record(rows) => rows,
+        _ => return None,
+      };
+      let rhs = rows
+        .iter()
+        .find_map(|(lab, exp)| (*lab == sml_hir::Lab::tuple(1)).then_some(*exp))??;
+      let is_zero = matches!(
+        &arenas.exp[rhs],
+        sml_hir::Exp::SCon(sml_hir::SCon::Int(sml_hir::Int::Finite(0)) | sml_hir::SCon::Word(0))
+      );
+      (rows.len() == 2 && is_zero).then_some(idx.into())
+    })
+    .collect()
+}
+
+/// Returns whether the path at `idx` resolved to a value not defined by the user.
+fn is_std_basis(file: &mlb_statics::SourceFile, idx: sml_hir::Idx) -> bool {
+  file.info.get_symbol_kind(idx).is_some()
+    && file.info.get_def(idx).map_or(true, |def| {
+      matches!(def.path, sml_statics::DefPath::StdBasis(_))
+    })
+}
@@ -0,0 +1,51 @@
+//! Getting the declarations that enclose a position, like for breadcrumbs or expanding a
+//! selection.
+
+use crate::folding_range::non_trivia_range;
+use sml_syntax::{SyntaxKind as SK, SyntaxToken};
+use text_size_util::TextRange;
+
+/// Returns the ranges of the declarations, structure-level declarations, and specifications
+/// containing `tok`, innermost first.
+pub(crate) fn get(tok: &SyntaxToken) -> Vec<TextRange> {
+  let node = match tok.parent() {
+    Some(x) => x,
+    None => return Vec::new(),
+  };
+  node
+    .ancestors()
+    .filter(|node| is_decl(node.kind()))
+    .filter_map(|node| non_trivia_range(&node))
+    .collect()
+}
+
+fn is_decl(kind: SK) -> bool {
+  matches!(
+    kind,
+    SK::ValDec
+      | SK::FunDec
+      | SK::TyDec
+      | SK::DatDec
+      | SK::DatCopyDec
+      | SK::AbstypeDec
+      | SK::ExDec
+      | SK::LocalDec
+      | SK::OpenDec
+      | SK::InfixDec
+      | SK::InfixrDec
+      | SK::NonfixDec
+      | SK::DoDec
+      | SK::StructureStrDec
+      | SK::LocalStrDec
+      | SK::SigDec
+      | SK::FunctorDec
+      | SK::ValSpec
+      | SK::TySpec
+      | SK::EqTySpec
+      | SK::DatSpec
+      | SK::DatCopySpec
+      | SK::ExSpec
+      | SK::StrSpec
+      | SK::IncludeSpec
+  )
+}
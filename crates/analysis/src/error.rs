@@ -1,3 +1,4 @@
+use config::Severity;
 use text_pos::Range;
 
 /// An error.
@@ -9,4 +10,19 @@ pub struct Error {
   pub message: String,
   /// The error code.
   pub code: u16,
+  /// The severity of the error.
+  pub severity: Severity,
+  /// Extra information about the error, for clients that render some errors differently.
+  pub tags: Vec<DiagnosticTag>,
+  /// Other ranges in the same file relevant to the error, with a message for each.
+  pub related: Vec<(Range, String)>,
+}
+
+/// A tag on an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticTag {
+  /// The error is about unused or unnecessary code.
+  Unnecessary,
+  /// The error is about a use of a deprecated item.
+  Deprecated,
 }
@@ -0,0 +1,44 @@
+//! The catalog of error codes.
+//!
+//! Each part of analysis exposes the codes assigned by the `to_code` method of its errors. The
+//! opt-in checks and other errors reported directly by analysis have their codes here. See
+//! `docs/errors.md` for the full explanations.
+
+pub(crate) const UNDEFINED_DEPRECATED: u16 = 1013;
+pub(crate) const DEPRECATED: u16 = 6001;
+pub(crate) const DIV_BY_ZERO: u16 = 6002;
+pub(crate) const NUM_LABEL_GAP: u16 = 6003;
+pub(crate) const ERRORS_HIDDEN: u16 = 6004;
+pub(crate) const SHADOW_INFIX: u16 = 6005;
+pub(crate) const NARROW_HANDLE: u16 = 6006;
+
+/// The codes reported directly by analysis, or by the CLI.
+const CODES: &[(u16, &str)] = &[
+  (UNDEFINED_DEPRECATED, "undefined deprecated value in config"),
+  (1997, "invalid or missing CLI argument"),
+  (DEPRECATED, "deprecated value"),
+  (DIV_BY_ZERO, "division by zero"),
+  (NUM_LABEL_GAP, "missing numeric label"),
+  (ERRORS_HIDDEN, "errors hidden"),
+  (
+    SHADOW_INFIX,
+    "value binding shadows standard infix operator",
+  ),
+  (NARROW_HANDLE, "handle matches only one exception"),
+];
+
+/// Returns every error code, with a short description, in increasing order of code.
+pub fn error_codes() -> Vec<(u16, &'static str)> {
+  let mut ret: Vec<_> = std::iter::empty()
+    .chain(crate::input::ERROR_CODES)
+    .chain(mlb_statics::ERROR_CODES)
+    .chain(sml_lex::ERROR_CODES)
+    .chain(sml_parse::parser::ERROR_CODES)
+    .chain(sml_lower::ERROR_CODES)
+    .chain(sml_statics::ERROR_CODES)
+    .chain(CODES)
+    .copied()
+    .collect();
+  ret.sort_by_key(|&(code, _)| code);
+  ret
+}
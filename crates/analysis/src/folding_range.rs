@@ -0,0 +1,45 @@
+//! Getting the regions of a file that may be folded, like `struct ... end`.
+
+use sml_syntax::rowan::NodeOrToken;
+use sml_syntax::token::Triviable as _;
+use sml_syntax::{SyntaxKind as SK, SyntaxNode};
+use text_size_util::TextRange;
+
+/// Returns the ranges of the foldable regions in `root`, ordered by start position. A region
+/// contains every region nested inside it.
+pub(crate) fn get(root: &SyntaxNode) -> Vec<TextRange> {
+  root
+    .descendants_with_tokens()
+    .filter_map(|elem| match elem {
+      NodeOrToken::Node(node) => {
+        let foldable = matches!(
+          node.kind(),
+          SK::StructStrExp
+            | SK::SigSigExp
+            | SK::LetExp
+            | SK::LetStrExp
+            | SK::LocalDec
+            | SK::LocalStrDec
+        );
+        foldable.then(|| non_trivia_range(&node)).flatten()
+      }
+      NodeOrToken::Token(tok) => (tok.kind() == SK::BlockComment).then(|| tok.text_range()),
+    })
+    .collect()
+}
+
+/// returns the range from the start of the first non-trivia token to the end of the last one, so
+/// that e.g. comments before a `let` are not part of its range.
+pub(crate) fn non_trivia_range(node: &SyntaxNode) -> Option<TextRange> {
+  let mut toks = node
+    .descendants_with_tokens()
+    .filter_map(NodeOrToken::into_token)
+    .filter(|tok| !tok.kind().is_trivia());
+  let fst = toks.next()?;
+  let end = toks
+    .last()
+    .unwrap_or_else(|| fst.clone())
+    .text_range()
+    .end();
+  Some(TextRange::new(fst.text_range().start(), end))
+}
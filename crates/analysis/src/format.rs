@@ -0,0 +1,189 @@
+//! Formatting source files.
+//!
+//! This is conservative. It never moves tokens between lines. It only:
+//!
+//! - indents the items in `struct`, `sig`, `let`, and `local` bodies one level more than the line
+//!   starting the body, and the `in` and `end` at the same level as that line
+//! - shifts other lines by the same amount as the line they continue
+//! - collapses runs of spaces between tokens to one space
+//! - puts one space around infix operators
+//! - removes trailing whitespace
+//!
+//! Formatting already formatted text does nothing.
+
+use fast_hash::FxHashMap;
+use sml_syntax::token::Triviable as _;
+use sml_syntax::{SyntaxKind as SK, SyntaxNode, SyntaxToken};
+
+const INDENT: usize = 2;
+
+/// Returns the formatted text of `root`.
+pub(crate) fn get(root: &SyntaxNode) -> String {
+  let text = root.to_string();
+  let line_starts: Vec<usize> = std::iter::once(0)
+    .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+    .collect();
+  let mut cx = Cx {
+    text: &text,
+    line_starts,
+    indents: FxHashMap::default(),
+  };
+  let tokens: Vec<_> = root
+    .descendants_with_tokens()
+    .filter_map(|x| x.into_token())
+    .collect();
+  let mut ret = String::with_capacity(text.len());
+  let mut at_line_start = true;
+  let mut space = false;
+  let mut prev = None::<&SyntaxToken>;
+  for (idx, tok) in tokens.iter().enumerate() {
+    if tok.kind() == SK::Whitespace {
+      if tok.text().contains('\n') {
+        let newline = if tok.text().contains("\r\n") {
+          "\r\n"
+        } else {
+          "\n"
+        };
+        for _ in tok.text().matches('\n') {
+          trim_end_spaces(&mut ret);
+          ret.push_str(newline);
+        }
+        at_line_start = true;
+      } else {
+        space = true;
+      }
+      continue;
+    }
+    if at_line_start {
+      let line = cx.line(tok);
+      // comments are indented like the token after them, except that comments before an `in` or
+      // `end` are indented like the body before them.
+      let indent = tokens[idx..]
+        .iter()
+        .find(|x| !x.kind().is_trivia())
+        .map_or(0, |x| cx.indent(x, line, x == tok));
+      cx.indents.insert(line, indent);
+      ret.push_str(&" ".repeat(indent));
+      at_line_start = false;
+    } else if space || prev.map_or(false, is_infix_op) || is_infix_op(tok) {
+      ret.push(' ');
+    }
+    ret.push_str(tok.text());
+    space = false;
+    prev = Some(tok);
+  }
+  trim_end_spaces(&mut ret);
+  ret
+}
+
+struct Cx<'a> {
+  text: &'a str,
+  /// the byte offsets of the starts of the lines in the original text.
+  line_starts: Vec<usize>,
+  /// the new indentation of the lines formatted so far, by line number in the original text.
+  indents: FxHashMap<usize, usize>,
+}
+
+impl Cx<'_> {
+  fn line(&self, tok: &SyntaxToken) -> usize {
+    let offset = usize::from(tok.text_range().start());
+    match self.line_starts.binary_search(&offset) {
+      Ok(x) => x,
+      Err(x) => x - 1,
+    }
+  }
+
+  fn orig_indent(&self, line: usize) -> usize {
+    self.text[self.line_starts[line]..]
+      .chars()
+      .take_while(|&c| c == ' ' || c == '\t')
+      .count()
+  }
+
+  fn new_indent(&self, line: usize) -> usize {
+    self
+      .indents
+      .get(&line)
+      .copied()
+      .unwrap_or_else(|| self.orig_indent(line))
+  }
+
+  /// returns the new indentation of `line`, whose first non-trivia token is `tok`. `starts_line` is
+  /// whether `tok` is also the first token on `line`, i.e. not after a comment.
+  fn indent(&self, tok: &SyntaxToken, line: usize, starts_line: bool) -> usize {
+    for node in tok.parent().into_iter().flat_map(|x| x.ancestors()) {
+      let first = match first_non_trivia(&node) {
+        Some(x) => x,
+        None => continue,
+      };
+      if first == *tok {
+        continue;
+      }
+      let node_line = self.line(&first);
+      let node_indent = self.new_indent(node_line);
+      if is_block(node.kind()) {
+        let is_delimiter = starts_line
+          && tok.parent().as_ref() == Some(&node)
+          && matches!(tok.kind(), SK::InKw | SK::EndKw);
+        return if is_delimiter {
+          node_indent
+        } else {
+          node_indent + INDENT
+        };
+      }
+      if is_seq(node.kind()) {
+        continue;
+      }
+      // shift by as much as the line this continues was shifted.
+      return (self.orig_indent(line) + node_indent).saturating_sub(self.orig_indent(node_line));
+    }
+    0
+  }
+}
+
+fn first_non_trivia(node: &SyntaxNode) -> Option<SyntaxToken> {
+  node
+    .descendants_with_tokens()
+    .filter_map(|x| x.into_token())
+    .find(|x| !x.kind().is_trivia())
+}
+
+/// nodes whose items are indented one level more than the line starting the node.
+fn is_block(kind: SK) -> bool {
+  matches!(
+    kind,
+    SK::StructStrExp | SK::SigSigExp | SK::LetExp | SK::LetStrExp | SK::LocalDec | SK::LocalStrDec
+  )
+}
+
+/// nodes that are sequences of items, or wrap single items, and so don't affect indentation.
+fn is_seq(kind: SK) -> bool {
+  matches!(
+    kind,
+    SK::Root
+      | SK::StrDec
+      | SK::StrDecInSeq
+      | SK::DecStrDec
+      | SK::Dec
+      | SK::DecInSeq
+      | SK::Spec
+      | SK::SpecWithTailInSeq
+      | SK::SpecWithTail
+      | SK::SpecInSeq
+  )
+}
+
+fn is_infix_op(tok: &SyntaxToken) -> bool {
+  matches!(tok.kind(), SK::Name | SK::Star | SK::Eq)
+    && tok.parent().map_or(false, |x| {
+      matches!(
+        x.kind(),
+        SK::InfixExp | SK::InfixPat | SK::InfixFunBindCaseHead
+      )
+    })
+}
+
+fn trim_end_spaces(s: &mut String) {
+  let len = s.trim_end_matches(|c| c == ' ' || c == '\t').len();
+  s.truncate(len);
+}
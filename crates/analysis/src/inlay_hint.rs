@@ -0,0 +1,82 @@
+//! Getting inferred type hints for bindings without type annotations.
+
+use sml_syntax::ast::{self, AstNode as _, SyntaxNodePtr};
+use text_pos::Position;
+use text_size_util::{TextRange, TextSize};
+
+pub(crate) fn get(
+  file: &mlb_statics::SourceFile,
+  syms: &sml_statics::Syms,
+) -> Vec<(Position, String)> {
+  let mut ret = Vec::<(Position, String)>::new();
+  for node in file.parsed.root.syntax().descendants() {
+    if let Some(val_bind) = ast::ValBind::cast(node.clone()) {
+      let pat = match val_bind.pat() {
+        Some(x) => x,
+        None => continue,
+      };
+      if has_annotation(&pat) || matches!(val_bind.exp(), Some(ast::Exp::TypedExp(_))) {
+        continue;
+      }
+      let end = pat.syntax().text_range().end();
+      push(&mut ret, file, syms, SyntaxNodePtr::new(pat.syntax()), end);
+    } else if let Some(fun_bind) = ast::FunBind::cast(node) {
+      if fun_bind
+        .fun_bind_cases()
+        .any(|case| case.ty_annotation().is_some())
+      {
+        continue;
+      }
+      let name = fun_bind
+        .fun_bind_cases()
+        .next()
+        .and_then(|case| case.fun_bind_case_head())
+        .and_then(|head| match head {
+          ast::FunBindCaseHead::PrefixFunBindCaseHead(head) => head.name_star_eq(),
+          ast::FunBindCaseHead::InfixFunBindCaseHead(_) => None,
+        });
+      let name = match name {
+        Some(x) => x.token,
+        None => continue,
+      };
+      // the fun bind's ptr maps to the pat for the name of the function.
+      let end = name.text_range().end();
+      push(
+        &mut ret,
+        file,
+        syms,
+        SyntaxNodePtr::new(fun_bind.syntax()),
+        end,
+      );
+    }
+  }
+  ret
+}
+
+fn push(
+  ac: &mut Vec<(Position, String)>,
+  file: &mlb_statics::SourceFile,
+  syms: &sml_statics::Syms,
+  ptr: SyntaxNodePtr,
+  end: TextSize,
+) {
+  let ty = file
+    .lowered
+    .ptrs
+    .ast_to_hir(ptr)
+    .and_then(|idx| file.info.get_ty_display(syms, idx));
+  let pos = file.pos_db.range(TextRange::empty(end)).map(|x| x.start);
+  if let (Some(ty), Some(pos)) = (ty, pos) {
+    ac.push((pos, format!(": {ty}")));
+  }
+}
+
+/// also returns true for wildcards, since those bind nothing.
+fn has_annotation(pat: &ast::Pat) -> bool {
+  match pat {
+    ast::Pat::TypedPat(_) | ast::Pat::TypedNamePat(_) | ast::Pat::WildcardPat(_) => true,
+    ast::Pat::AsPat(pat) => pat.ty_annotation().is_some(),
+    ast::Pat::ParenPat(pat) => pat.pat().map_or(false, |pat| has_annotation(&pat)),
+    _ => false,
+  }
+}
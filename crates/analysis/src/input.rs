@@ -1,6 +1,6 @@
 //! Input to analysis.
 
-use fast_hash::FxHashSet;
+use fast_hash::{FxHashMap, FxHashSet};
 use paths::{PathId, PathMap, WithPath};
 use std::collections::BTreeSet;
 use std::fmt;
@@ -16,6 +16,20 @@ pub struct Input {
   pub(crate) groups: PathMap<Group>,
   /// The root group id.
   pub(crate) root_group_id: PathId,
+  /// The config file, if there is one.
+  pub(crate) config_path: Option<PathId>,
+  /// Std basis values to report as deprecated, as written in the config file.
+  pub(crate) deprecated: Vec<Deprecated>,
+  /// Opt-in checks on source files.
+  pub(crate) lints: Lints,
+  /// Options for lowering source files.
+  pub(crate) lower_config: sml_lower::Config,
+  /// The max number of errors to report per path, if any.
+  pub(crate) max_errors_per_path: Option<usize>,
+  /// How to report errors, by code.
+  pub(crate) severities: Severities,
+  /// The source paths, relative to the root.
+  pub(crate) rel_paths: PathMap<PathBuf>,
 }
 
 impl Input {
@@ -23,8 +37,86 @@ impl Input {
   pub fn iter_sources(&self) -> impl Iterator<Item = WithPath<&str>> + '_ {
     self.sources.iter().map(|(&path, s)| path.wrap(s.as_str()))
   }
+
+  /// Returns the source paths reachable from the root group, in the order they are analyzed. A
+  /// source path comes after every other source path it may depend on.
+  pub fn ordered_sources(&self) -> Vec<PathId> {
+    let mut ac = OrderedSources {
+      groups: &self.groups,
+      visited: FxHashSet::default(),
+      ret: Vec::new(),
+    };
+    ac.group(self.root_group_id);
+    ac.ret
+  }
+}
+
+struct OrderedSources<'a> {
+  groups: &'a PathMap<Group>,
+  visited: FxHashSet<PathId>,
+  ret: Vec<PathId>,
 }
 
+impl OrderedSources<'_> {
+  fn group(&mut self, path: PathId) {
+    if !self.visited.insert(path) {
+      return;
+    }
+    if let Some(group) = self.groups.get(&path) {
+      self.bas_dec(&group.bas_dec);
+    }
+  }
+
+  fn bas_dec(&mut self, dec: &mlb_hir::BasDec) {
+    match dec {
+      mlb_hir::BasDec::Open(_) | mlb_hir::BasDec::Export(_, _, _) => {}
+      mlb_hir::BasDec::Path(p, mlb_hir::PathKind::Sml) => {
+        if self.visited.insert(*p) {
+          self.ret.push(*p);
+        }
+      }
+      mlb_hir::BasDec::Path(p, mlb_hir::PathKind::Mlb) => self.group(*p),
+      mlb_hir::BasDec::Basis(_, exp) => self.bas_exp(exp),
+      mlb_hir::BasDec::Local(local_dec, in_dec) => {
+        self.bas_dec(local_dec);
+        self.bas_dec(in_dec);
+      }
+      mlb_hir::BasDec::Seq(decs) => {
+        for dec in decs {
+          self.bas_dec(dec);
+        }
+      }
+    }
+  }
+
+  fn bas_exp(&mut self, exp: &mlb_hir::BasExp) {
+    match exp {
+      mlb_hir::BasExp::Bas(dec) => self.bas_dec(dec),
+      mlb_hir::BasExp::Name(_) => {}
+      mlb_hir::BasExp::Let(dec, exp) => {
+        self.bas_dec(dec);
+        self.bas_exp(exp);
+      }
+    }
+  }
+}
+
+/// Opt-in checks and language extensions for source files.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Lints {
+  /// Whether to report integer division or modulo by a literal zero.
+  pub(crate) div_by_zero: bool,
+  /// Whether to report records whose numeric labels are one label away from making them a tuple.
+  pub(crate) num_label_gap: bool,
+  /// Whether to report bindings that shadow std basis infix operators without declaring a fixity.
+  pub(crate) shadow_infix: bool,
+  /// Whether to report `handle`s that match only one exception constructor.
+  pub(crate) narrow_handle: bool,
+}
+
+/// The configured severities of errors by code. `None` means to not report errors with that code.
+pub(crate) type Severities = FxHashMap<u16, Option<config::Severity>>;
+
 /// An error when getting input.
 ///
 /// TODO make this un-pub and turn into [`crate::error::Error`]? the problem is that _sometimes_, a
@@ -62,11 +154,29 @@ impl GetInputError {
       GetInputErrorKind::Mlb(_) => 1009,
       GetInputErrorKind::Cycle => 1010,
       GetInputErrorKind::Duplicate(_) => 1011,
+      GetInputErrorKind::InvalidErrorCode(_) => 1012,
       GetInputErrorKind::UnsupportedExport => 1999,
     }
   }
 }
 
+/// The codes returned by [`GetInputError::to_code`], with short descriptions.
+pub(crate) const ERROR_CODES: &[(u16, &str)] = &[
+  (1001, "file or directory I/O failed"),
+  (1002, "path not contained in the root directory"),
+  (1003, "multiple root group files"),
+  (1004, "no root group file"),
+  (1005, "root group file is not `.mlb` or `.cm`"),
+  (1006, "couldn't parse config"),
+  (1007, "invalid config version"),
+  (1008, "couldn't parse SML/NJ CM file"),
+  (1009, "couldn't parse ML Basis file"),
+  (1010, "cycle between files"),
+  (1011, "duplicate name in ML Basis file"),
+  (1012, "invalid error code in config"),
+  (1999, "unsupported export kind"),
+];
+
 impl fmt::Display for GetInputError {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     if self.source.path.is_some() {
@@ -89,6 +199,7 @@ enum GetInputErrorKind {
   Mlb(mlb_syntax::Error),
   Cycle,
   Duplicate(sml_hir::Name),
+  InvalidErrorCode(str_util::SmolStr),
   /// must be last
   UnsupportedExport,
 }
@@ -114,6 +225,7 @@ impl fmt::Display for GetInputErrorKind {
       GetInputErrorKind::Mlb(e) => write!(f, "couldn't process ML Basis file: {e}"),
       GetInputErrorKind::Cycle => f.write_str("there is a cycle involving this path"),
       GetInputErrorKind::Duplicate(name) => write!(f, "duplicate name: {name}"),
+      GetInputErrorKind::InvalidErrorCode(code) => write!(f, "invalid error code: {code}"),
       GetInputErrorKind::UnsupportedExport => f.write_str("unsupported export kind"),
     }
   }
@@ -167,16 +279,38 @@ impl GroupPath {
   }
 }
 
+/// An entry in the config's list of deprecated std basis values.
+#[derive(Debug)]
+pub(crate) struct Deprecated {
+  /// The entry as written.
+  pub(crate) name: str_util::SmolStr,
+  /// The path the entry denotes, if it is a valid path.
+  pub(crate) path: Option<sml_hir::Path>,
+  /// The range of the entry in the config file.
+  pub(crate) range: Range,
+}
+
 #[derive(Debug)]
 pub(crate) struct Group {
   pub(crate) bas_dec: mlb_hir::BasDec,
   pub(crate) pos_db: text_pos::PositionDb,
+  /// errors that did not stop processing the group file.
+  pub(crate) errors: Vec<GetInputError>,
 }
 
 struct RootGroup {
-  path: PathId,
-  kind: GroupPathKind,
+  /// usually there is only one.
+  paths: Vec<(PathId, GroupPathKind)>,
+  /// the config file, if it set many root groups.
+  config_path: Option<PathId>,
+  /// the config file, if any.
+  config_file: Option<PathId>,
   path_vars: paths::slash_var_path::Env,
+  deprecated: Vec<Deprecated>,
+  lints: Lints,
+  lower_config: sml_lower::Config,
+  max_errors_per_path: Option<usize>,
+  severities: Severities,
 }
 
 fn get_root_group<F>(fs: &F, root: &mut Root) -> Result<RootGroup>
@@ -186,6 +320,13 @@ where
   let mut root_group_source = Source::default();
   let config_path = root.paths.as_path().join(config::FILE_NAME);
   let mut path_vars = paths::slash_var_path::Env::default();
+  let mut config_file = None::<PathId>;
+  let mut deprecated = Vec::<Deprecated>::new();
+  let mut lints = Lints::default();
+  let mut lower_config = sml_lower::Config::default();
+  let mut max_errors_per_path = Some(crate::DEFAULT_MAX_ERRORS_PER_PATH);
+  let mut severities = Severities::default();
+  let mut config_group_paths = Vec::<GroupPath>::new();
   if let Ok(contents) = fs.read_to_string(&config_path) {
     let config: config::Root = match toml::from_str(&contents) {
       Ok(x) => x,
@@ -204,7 +345,53 @@ where
         kind: GetInputErrorKind::InvalidConfigVersion(config.version),
       });
     }
+    config_file = Some(get_path_id(
+      fs,
+      &mut root.paths,
+      Source::default(),
+      config_path.as_path(),
+    )?);
+    if let Some(lang) = config.language {
+      if let Some(deps) = lang.deprecated {
+        let pos_db = text_pos::PositionDb::new(&contents);
+        deprecated.extend(deps.into_iter().filter_map(|name| {
+          let range = pos_db.range(entry_range(&contents, name.as_str()))?;
+          let path = get_hir_path(name.as_str());
+          Some(Deprecated { name, path, range })
+        }));
+      }
+      lints.div_by_zero = lang.div_by_zero.unwrap_or(false);
+      lints.num_label_gap = lang.num_label_gap.unwrap_or(false);
+      lints.shadow_infix = lang.shadow_infix.unwrap_or(false);
+      lints.narrow_handle = lang.narrow_handle.unwrap_or(false);
+      if let Some(ext) = lang.extensions {
+        lower_config.vectors = ext.vectors.unwrap_or(false);
+      }
+    }
     if let Some(ws) = config.workspace {
+      if let Some(n) = ws.max_errors_per_file {
+        max_errors_per_path = (n != 0).then_some(n);
+      }
+      if let Some(diagnostics) = ws.diagnostics {
+        for (code, severity) in diagnostics {
+          let code = match code.parse::<u16>() {
+            Ok(x) => x,
+            Err(_) => {
+              return Err(GetInputError {
+                source: Source::default(),
+                path: config_path,
+                kind: GetInputErrorKind::InvalidErrorCode(code),
+              })
+            }
+          };
+          let severity = match severity {
+            config::DiagnosticSeverity::Error => Some(config::Severity::Error),
+            config::DiagnosticSeverity::Warning => Some(config::Severity::Warning),
+            config::DiagnosticSeverity::Ignore => None,
+          };
+          severities.insert(code, severity);
+        }
+      }
       if let Some(ws_path_vars) = ws.path_vars {
         for (key, val) in ws_path_vars {
           match val {
@@ -224,29 +411,36 @@ where
         }
       }
       // try to get from the config.
-      if let (None, Some(path)) = (&root.group_path, ws.root) {
-        let path = root.paths.as_path().join(path.as_str());
-        match GroupPath::new(fs, path.clone()) {
-          Some(path) => {
-            root_group_source.path = Some(config_path);
-            root.group_path = Some(path);
-          }
-          None => {
-            return Err(GetInputError {
-              source: Source {
-                path: Some(config_path),
-                range: None,
-              },
-              path,
-              kind: GetInputErrorKind::NotGroup,
-            })
+      if let (None, Some(roots)) = (&root.group_path, ws.root) {
+        let roots = match roots {
+          config::RootGroups::One(path) => vec![path],
+          config::RootGroups::Many(paths) => paths,
+        };
+        for path in roots {
+          let path = root.paths.as_path().join(path.as_str());
+          match GroupPath::new(fs, path.clone()) {
+            Some(path) => config_group_paths.push(path),
+            None => {
+              return Err(GetInputError {
+                source: Source {
+                  path: Some(config_path),
+                  range: None,
+                },
+                path,
+                kind: GetInputErrorKind::NotGroup,
+              })
+            }
           }
         }
+        root_group_source.path = Some(config_path.clone());
       }
     }
   }
+  if config_group_paths.len() == 1 {
+    root.group_path = config_group_paths.pop();
+  }
   // if not, try to get one from the root dir.
-  if root.group_path.is_none() {
+  if root.group_path.is_none() && config_group_paths.is_empty() {
     let dir_entries = fs
       .read_dir(root.paths.as_path())
       .map_err(|e| GetInputError {
@@ -272,23 +466,71 @@ where
       }
     }
   }
-  let root_group_path = root.group_path.as_ref().ok_or_else(|| GetInputError {
-    source: Source::default(),
-    path: root.paths.as_path().to_owned(),
-    kind: GetInputErrorKind::NoRoot,
-  })?;
-  Ok(RootGroup {
-    path: get_path_id(
+  let group_paths = match &root.group_path {
+    Some(group_path) => std::slice::from_ref(group_path),
+    None => config_group_paths.as_slice(),
+  };
+  if group_paths.is_empty() {
+    return Err(GetInputError {
+      source: Source::default(),
+      path: root.paths.as_path().to_owned(),
+      kind: GetInputErrorKind::NoRoot,
+    });
+  }
+  let mut paths = Vec::with_capacity(group_paths.len());
+  for group_path in group_paths {
+    let path = get_path_id(
+      fs,
+      &mut root.paths,
+      root_group_source.clone(),
+      group_path.as_path(),
+    )?;
+    paths.push((path, group_path.kind));
+  }
+  let config_path = if paths.len() > 1 {
+    Some(get_path_id(
       fs,
       &mut root.paths,
-      root_group_source,
-      root_group_path.path.as_path(),
-    )?,
-    kind: root_group_path.kind,
+      Source::default(),
+      config_path.as_path(),
+    )?)
+  } else {
+    None
+  };
+  Ok(RootGroup {
+    paths,
+    config_path,
+    config_file,
     path_vars,
+    deprecated,
+    lints,
+    lower_config,
+    max_errors_per_path,
+    severities,
   })
 }
 
+/// returns the range of the string literal for `name` in the config `contents`, or an empty range
+/// at the start if there is no such literal.
+fn entry_range(contents: &str, name: &str) -> text_size_util::TextRange {
+  let found = ['"', '\''].into_iter().find_map(|quote| {
+    let start = contents.find(&format!("{quote}{name}{quote}"))?;
+    let start = text_size_util::TextSize::try_from(start).ok()?;
+    let len = text_size_util::TextSize::try_from(name.len() + 2).ok()?;
+    Some(text_size_util::TextRange::at(start, len))
+  });
+  found.unwrap_or_default()
+}
+
+/// splits a fully-qualified name like `Foo.bar` into a path. returns `None` if any part is empty.
+fn get_hir_path(s: &str) -> Option<sml_hir::Path> {
+  let names: Option<Vec<_>> = s
+    .split('.')
+    .map(|x| (!x.is_empty()).then(|| sml_hir::Name::new(x)))
+    .collect();
+  sml_hir::Path::try_new(names?)
+}
+
 #[derive(Debug, Clone, Copy)]
 struct GroupToProcess {
   /// the path that led us to `group_path`.
@@ -374,78 +616,106 @@ where
   F: paths::FileSystem,
 {
   let root_group = get_root_group(fs, root)?;
-  let init = GroupToProcess {
-    containing_path: root_group.path,
-    containing_range: None,
-    group_path: root_group.path,
-  };
   let mut sources = PathMap::<String>::default();
-  let groups = match root_group.kind {
-    GroupPathKind::Cm => {
-      let mut cm_files = PathMap::<CmFile>::default();
-      get_cm_file(
+  let mut groups = PathMap::<Group>::default();
+  let mut cm_files = PathMap::<CmFile>::default();
+  for &(path, kind) in &root_group.paths {
+    let init = GroupToProcess {
+      containing_path: path,
+      containing_range: None,
+      group_path: path,
+    };
+    match kind {
+      GroupPathKind::Cm => get_cm_file(
         &mut root.paths,
         fs,
         &root_group.path_vars,
         &mut sources,
         &mut cm_files,
         init,
-      )?;
-      cm_files
-        .into_iter()
-        .map(|(path, cm_file)| {
-          let exports: Vec<_> = cm_file
-            .exports
-            .into_iter()
-            .map(|ex| mlb_hir::BasDec::Export(ex.namespace, ex.name.clone(), ex.name))
-            .collect();
-          let bas_dec = mlb_hir::BasDec::Local(
-            mlb_hir::BasDec::seq(cm_file.paths).into(),
-            mlb_hir::BasDec::seq(exports).into(),
-          );
-          let group = Group {
-            bas_dec,
-            pos_db: cm_file.pos_db.expect("no pos db"),
-          };
-          (path, group)
-        })
-        .collect()
-    }
-    GroupPathKind::Mlb => {
-      let mut groups = PathMap::<Group>::default();
-      let mut stack = vec![init];
-      while let Some(cur) = stack.pop() {
-        if groups.contains_key(&cur.group_path) {
-          continue;
-        }
-        let (group_path, contents, pos_db) = start_group_file(&mut root.paths, cur, fs)?;
-        let group_path = group_path.as_path();
-        let group_parent = group_path
-          .parent()
-          .expect("path from get_path has no parent");
-        let syntax_dec =
-          mlb_syntax::get(&contents, &root_group.path_vars).map_err(|e| GetInputError {
+      )?,
+      GroupPathKind::Mlb => {
+        let mut stack = vec![init];
+        while let Some(cur) = stack.pop() {
+          if groups.contains_key(&cur.group_path) {
+            continue;
+          }
+          let (group_path, contents, pos_db) = start_group_file(&mut root.paths, cur, fs)?;
+          let group_path = group_path.as_path();
+          let group_parent = group_path
+            .parent()
+            .expect("path from get_path has no parent");
+          let mlb_error = |e: mlb_syntax::Error| GetInputError {
             source: Source {
               path: None,
               range: pos_db.range(e.text_range()),
             },
             path: group_path.to_owned(),
             kind: GetInputErrorKind::Mlb(e),
-          })?;
-        let mut cx = MlbCx {
-          path: group_path,
-          parent: group_parent,
-          pos_db: &pos_db,
-          fs,
-          root: &mut root.paths,
-          sources: &mut sources,
-          stack: &mut stack,
-          path_id: cur.group_path,
-        };
-        let bas_dec = get_bas_dec(&mut cx, syntax_dec)?;
-        groups.insert(cur.group_path, Group { bas_dec, pos_db });
+          };
+          let (syntax_dec, errors) =
+            mlb_syntax::get(&contents, &root_group.path_vars).map_err(mlb_error)?;
+          let errors: Vec<_> = errors.into_iter().map(mlb_error).collect();
+          let mut cx = MlbCx {
+            path: group_path,
+            parent: group_parent,
+            pos_db: &pos_db,
+            fs,
+            root: &mut root.paths,
+            sources: &mut sources,
+            stack: &mut stack,
+            path_id: cur.group_path,
+          };
+          let bas_dec = get_bas_dec(&mut cx, syntax_dec)?;
+          let group = Group {
+            bas_dec,
+            pos_db,
+            errors,
+          };
+          groups.insert(cur.group_path, group);
+        }
       }
-      groups
+    }
+  }
+  groups.extend(cm_files.into_iter().map(|(path, cm_file)| {
+    let exports: Vec<_> = cm_file
+      .exports
+      .into_iter()
+      .map(|ex| mlb_hir::BasDec::Export(ex.namespace, ex.name.clone(), ex.name))
+      .collect();
+    let bas_dec = mlb_hir::BasDec::Local(
+      mlb_hir::BasDec::seq(cm_file.paths).into(),
+      mlb_hir::BasDec::seq(exports).into(),
+    );
+    let group = Group {
+      bas_dec,
+      pos_db: cm_file.pos_db.expect("no pos db"),
+      errors: Vec::new(),
+    };
+    (path, group)
+  }));
+  // with many root groups, analyze them all from a group for the config file, keeping each root
+  // group's bindings local to it so they are independent of one another.
+  let root_group_id = match root_group.config_path {
+    None => root_group.paths[0].0,
+    Some(config_path) => {
+      let bas_dec = root_group
+        .paths
+        .iter()
+        .map(|&(path, _)| {
+          mlb_hir::BasDec::Local(
+            mlb_hir::BasDec::Path(path, mlb_hir::PathKind::Mlb).into(),
+            mlb_hir::BasDec::seq(Vec::new()).into(),
+          )
+        })
+        .collect();
+      let group = Group {
+        bas_dec: mlb_hir::BasDec::seq(bas_dec),
+        pos_db: text_pos::PositionDb::new(""),
+        errors: Vec::new(),
+      };
+      groups.insert(config_path, group);
+      config_path
     }
   };
   let graph: topo_sort::Graph<_> = groups
@@ -463,10 +733,21 @@ where
       kind: GetInputErrorKind::Cycle,
     });
   }
+  let rel_paths = sources
+    .keys()
+    .map(|&path| (path, root.paths.get_rel_path(path).to_owned()))
+    .collect();
   Ok(Input {
     sources,
     groups,
-    root_group_id: root_group.path,
+    root_group_id,
+    config_path: root_group.config_file,
+    deprecated: root_group.deprecated,
+    lints: root_group.lints,
+    lower_config: root_group.lower_config,
+    max_errors_per_path: root_group.max_errors_per_path,
+    severities: root_group.severities,
+    rel_paths,
   })
 }
 
@@ -661,14 +942,7 @@ where
         .into_iter()
         .map(|(name, exp)| {
           if !names.insert(name.val.clone()) {
-            return Err(GetInputError {
-              source: Source {
-                path: None,
-                range: cx.pos_db.range(name.range),
-              },
-              path: cx.path.to_owned(),
-              kind: GetInputErrorKind::Duplicate(name.val),
-            });
+            return Err(duplicate(cx, name));
           }
           let exp = get_bas_exp(cx, exp)?;
           Ok(mlb_hir::BasDec::Basis(name, exp.into()))
@@ -689,14 +963,7 @@ where
         .into_iter()
         .map(|(lhs, rhs)| {
           if !names.insert(lhs.val.clone()) {
-            return Err(GetInputError {
-              source: Source {
-                path: None,
-                range: cx.pos_db.range(lhs.range),
-              },
-              path: cx.path.to_owned(),
-              kind: GetInputErrorKind::Duplicate(lhs.val),
-            });
+            return Err(duplicate(cx, lhs));
           }
           let rhs = rhs.unwrap_or_else(|| lhs.clone());
           let ns = match ns {
@@ -734,16 +1001,42 @@ where
       mlb_hir::BasDec::Path(path_id, kind)
     }
     mlb_syntax::BasDec::Ann(_, dec) => get_bas_dec(cx, *dec)?,
-    mlb_syntax::BasDec::Seq(decs) => mlb_hir::BasDec::seq(
-      decs
+    mlb_syntax::BasDec::Seq(decs) => {
+      // each basis name may be bound only once at the same level.
+      let mut names = FxHashSet::<sml_hir::Name>::default();
+      let decs = decs
         .into_iter()
-        .map(|dec| get_bas_dec(cx, dec))
-        .collect::<Result<Vec<_>>>()?,
-    ),
+        .map(|dec| {
+          if let mlb_syntax::BasDec::Basis(binds) = &dec {
+            for (name, _) in binds {
+              if !names.insert(name.val.clone()) {
+                return Err(duplicate(cx, name.clone()));
+              }
+            }
+          }
+          get_bas_dec(cx, dec)
+        })
+        .collect::<Result<Vec<_>>>()?;
+      mlb_hir::BasDec::seq(decs)
+    }
   };
   Ok(ret)
 }
 
+fn duplicate<F>(
+  cx: &MlbCx<'_, F>,
+  name: text_size_util::WithRange<sml_hir::Name>,
+) -> GetInputError {
+  GetInputError {
+    source: Source {
+      path: None,
+      range: cx.pos_db.range(name.range),
+    },
+    path: cx.path.to_owned(),
+    kind: GetInputErrorKind::Duplicate(name.val),
+  }
+}
+
 fn get_bas_exp<F>(cx: &mut MlbCx<'_, F>, exp: mlb_syntax::BasExp) -> Result<mlb_hir::BasExp>
 where
   F: paths::FileSystem,
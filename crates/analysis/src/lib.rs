@@ -2,19 +2,85 @@
 
 #![deny(missing_debug_implementations, missing_docs, rust_2018_idioms)]
 
+mod debug_hir;
+mod div_by_zero;
+mod enclosing_decl;
 mod error;
+mod error_code;
+mod folding_range;
+mod format;
+mod inlay_hint;
+mod narrow_handle;
+mod num_label_gap;
+mod quick_fix;
+mod semantic_token;
+mod shadow_infix;
+mod symbol;
+mod token;
+mod tuple_record;
 
 pub mod input;
 
+use config::Severity;
+use fast_hash::FxHashSet;
 use fmt_util::sep_seq;
-use paths::{PathMap, WithPath};
+use paths::{PathId, PathMap, WithPath};
 use sml_syntax::ast::{AstNode as _, SyntaxNodePtr};
-use sml_syntax::{rowan::TokenAtOffset, SyntaxKind, SyntaxToken};
+use sml_syntax::rowan::{NodeOrToken, TokenAtOffset};
+use sml_syntax::token::Triviable as _;
+use sml_syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
 use std::fmt;
 
-pub use error::Error;
+pub use error::{DiagnosticTag, Error};
+pub use error_code::error_codes;
 pub use mlb_statics::StdBasis;
+pub use semantic_token::{SemanticToken, SemanticTokenKind};
+pub use sml_parse::parser::{Assoc, Infix};
+pub use sml_statics::SymbolKind;
+pub use symbol::Symbol;
 pub use text_pos::{Position, Range};
+pub use token::tokens;
+
+/// Statistics about an analysis, for tracking how complete type inference is.
+#[derive(Debug, Default)]
+pub struct AnalysisStats {
+  /// The number of source files.
+  pub source_files: usize,
+  /// The number of meta type variables generated by type inference, across all source files.
+  pub meta_vars: usize,
+  /// The number of those meta type variables that were solved to a type.
+  pub solved_meta_vars: usize,
+  /// The number of errors with each code, from lexing, parsing, lowering, and statics. Does not
+  /// include errors that depend on the config, like lints.
+  pub errors_by_code: std::collections::BTreeMap<u16, usize>,
+  /// The number of source files whose statics were re-used from the analysis before, instead of
+  /// being run again. See [`Analysis::update_file`].
+  pub reused_statics: usize,
+}
+
+impl AnalysisStats {
+  /// Returns the number of meta type variables that were not solved to a type.
+  pub fn unsolved_meta_vars(&self) -> usize {
+    self.meta_vars.saturating_sub(self.solved_meta_vars)
+  }
+}
+
+/// A name that could complete what is being written.
+#[derive(Debug, Clone)]
+pub struct CompletionItem {
+  /// The name.
+  pub label: String,
+  /// What kind of thing the name refers to.
+  pub kind: SymbolKind,
+  /// Extra detail, like the type scheme of a value.
+  pub detail: Option<String>,
+  /// The fixity of the name, if it is an infix value.
+  ///
+  /// This is the fixity at the end of the file, not at the position of the completion. It is
+  /// always `None` for members of a structure, like those completed after `S.`, since fixity is
+  /// not looked up for qualified names.
+  pub infix: Option<Infix>,
+}
 
 /// The url to go to for information about errors.
 pub const ERRORS_URL: &str = "https://github.com/azdavis/millet/blob/main/docs/errors.md";
@@ -25,7 +91,9 @@ pub struct Analysis {
   std_basis: mlb_statics::StdBasis,
   error_lines: config::ErrorLines,
   source_files: PathMap<mlb_statics::SourceFile>,
+  rel_paths: PathMap<std::path::PathBuf>,
   syms: sml_statics::Syms,
+  checked: mlb_statics::Checked,
 }
 
 impl Analysis {
@@ -35,18 +103,22 @@ impl Analysis {
       std_basis,
       error_lines,
       source_files: PathMap::default(),
+      rel_paths: PathMap::default(),
       syms: sml_statics::Syms::default(),
+      checked: mlb_statics::Checked::default(),
     }
   }
 
   /// Given the contents of one isolated file, return the errors for it.
   pub fn get_one(&self, contents: &str) -> Vec<Error> {
     let mut fix_env = mlb_statics::STD_BASIS_FIX_ENV.clone();
-    let (lex_errors, parsed, low) = mlb_statics::start_source_file(contents, &mut fix_env);
+    let (lex_errors, parsed, low) =
+      mlb_statics::start_source_file(sml_lower::Config::default(), contents, &mut fix_env);
+    // cheap, since the std basis syms are frozen.
     let mut syms = self.std_basis.syms().clone();
-    let basis = self.std_basis.basis().clone();
+    let basis = self.std_basis.basis();
     let mode = sml_statics::Mode::Regular(None);
-    let checked = sml_statics::get(&mut syms, &basis, mode, &low.arenas, low.root);
+    let checked = sml_statics::get(&mut syms, basis, mode, &low.arenas, low.root);
     let mut info = checked.info;
     mlb_statics::doc_comment::get(parsed.root.syntax(), &low, &mut info);
     let file = mlb_statics::SourceFile {
@@ -56,47 +128,178 @@ impl Analysis {
       lowered: low,
       statics_errors: checked.errors,
       info,
+      fix_env,
     };
-    source_file_errors(&file, &syms, self.error_lines)
+    source_file_errors(
+      &file,
+      &syms,
+      self.error_lines,
+      &[],
+      input::Lints::default(),
+      &input::Severities::default(),
+      Some(DEFAULT_MAX_ERRORS_PER_PATH),
+    )
   }
 
   /// Given information about many interdependent source files and their groupings, returns a
   /// mapping from source paths to errors.
   pub fn get_many(&mut self, input: &input::Input) -> PathMap<Vec<Error>> {
     let res = elapsed::log("mlb_statics::get", || {
-      let groups: paths::PathMap<_> = input
-        .groups
-        .iter()
-        .map(|(&path, group)| (path, &group.bas_dec))
-        .collect();
       mlb_statics::get(
         &self.std_basis,
+        input.lower_config,
+        &input.sources,
+        &group_decs(input),
+        input.root_group_id,
+      )
+    });
+    self.finish_many(input, res)
+  }
+
+  /// Like [`Self::get_many`], but re-uses the lexing, parsing, and lowering of the source files
+  /// from the last call to this or [`Self::get_many`], only re-running statics.
+  ///
+  /// The contents of the source files in `input` must be unchanged since that last call.
+  pub fn recheck_many(&mut self, input: &input::Input) -> PathMap<Vec<Error>> {
+    let prev = std::mem::take(&mut self.source_files);
+    let res = elapsed::log("mlb_statics::recheck", || {
+      mlb_statics::recheck(
+        &self.std_basis,
+        input.lower_config,
+        prev,
+        &input.sources,
+        &group_decs(input),
+        input.root_group_id,
+      )
+    });
+    self.finish_many(input, res)
+  }
+
+  /// Like [`Self::get_many`], but only re-lexes, re-parses, and re-lowers the source file at
+  /// `changed`, and only re-runs statics on `changed` and the source files checked after it. This
+  /// re-uses the rest from the last call to this, [`Self::get_many`], or [`Self::recheck_many`].
+  ///
+  /// The source files checked before `changed` cannot depend on it, so their statics, and what they
+  /// export, are re-used as is. The files after it may depend on it, so they are checked again to
+  /// see any change to what it exports. If the fixity declarations of `changed` also changed, every
+  /// file is processed again from scratch, since the files after it may now parse differently.
+  ///
+  /// Only the contents of the source file at `changed` may differ since that last call.
+  pub fn update_file(&mut self, input: &input::Input, changed: PathId) -> PathMap<Vec<Error>> {
+    let mut sml = std::mem::take(&mut self.source_files);
+    let old_fix_env = sml.remove(&changed).map(|file| file.fix_env);
+    let prev = mlb_statics::MlbStatics {
+      mlb_errors: Vec::new(),
+      syms: std::mem::take(&mut self.syms),
+      sml,
+      checked: std::mem::take(&mut self.checked),
+    };
+    let groups = group_decs(input);
+    let mut res = elapsed::log("mlb_statics::update", || {
+      mlb_statics::update(
+        &self.std_basis,
+        input.lower_config,
+        prev,
+        changed,
         &input.sources,
         &groups,
         input.root_group_id,
       )
     });
+    if old_fix_env.as_ref() != res.sml.get(&changed).map(|file| &file.fix_env) {
+      res = elapsed::log("mlb_statics::get", || {
+        mlb_statics::get(
+          &self.std_basis,
+          input.lower_config,
+          &input.sources,
+          &groups,
+          input.root_group_id,
+        )
+      });
+    }
+    self.finish_many(input, res)
+  }
+
+  fn finish_many(
+    &mut self,
+    input: &input::Input,
+    res: mlb_statics::MlbStatics,
+  ) -> PathMap<Vec<Error>> {
     self.source_files = res.sml;
+    self.rel_paths = input.rel_paths.clone();
     self.syms = res.syms;
-    std::iter::empty()
-      .chain(res.mlb_errors.into_iter().filter_map(|err| {
-        let group = input.groups.get(&err.path()).expect("no such group");
-        Some((
-          err.path(),
-          vec![Error {
-            range: group.pos_db.range(err.range())?,
-            message: err.to_string(),
-            code: err.to_code(),
-          }],
-        ))
-      }))
-      .chain(
-        self
-          .source_files
-          .iter()
-          .map(|(&path, file)| (path, source_file_errors(file, &self.syms, self.error_lines))),
-      )
-      .collect()
+    self.checked = res.checked;
+    let mut ret = PathMap::<Vec<Error>>::default();
+    let mut deprecated = Vec::<(sml_statics::Def, &sml_hir::Path)>::new();
+    for dep in &input.deprecated {
+      let def = dep
+        .path
+        .as_ref()
+        .and_then(|path| Some((self.std_basis.basis().get_val_def(path)?, path)));
+      match def {
+        Some(def) => deprecated.push(def),
+        None => {
+          let config_path = input
+            .config_path
+            .expect("deprecated values come from the config");
+          let error = Error {
+            range: dep.range,
+            message: format!("undefined deprecated value: {}", dep.name),
+            code: error_code::UNDEFINED_DEPRECATED,
+            severity: Severity::Error,
+            tags: Vec::new(),
+            related: Vec::new(),
+          };
+          if let Some(error) = configured(error, &input.severities) {
+            ret.entry(config_path).or_default().push(error);
+          }
+        }
+      }
+    }
+    let group_errors = input.groups.iter().flat_map(|(&path, group)| {
+      group.errors.iter().filter_map(move |err| {
+        let error = Error {
+          range: err.range()?,
+          message: err.to_string(),
+          code: err.to_code(),
+          severity: Severity::Error,
+          tags: Vec::new(),
+          related: Vec::new(),
+        };
+        Some((path, error))
+      })
+    });
+    let mlb_errors = res.mlb_errors.into_iter().filter_map(|err| {
+      let group = input.groups.get(&err.path()).expect("no such group");
+      let error = Error {
+        range: group.pos_db.range(err.range())?,
+        message: err.to_string(),
+        code: err.to_code(),
+        severity: Severity::Error,
+        tags: Vec::new(),
+        related: Vec::new(),
+      };
+      Some((err.path(), error))
+    });
+    // a group may have errors from both processing its file and its statics.
+    for (path, error) in group_errors.chain(mlb_errors) {
+      if let Some(error) = configured(error, &input.severities) {
+        ret.entry(path).or_default().push(error);
+      }
+    }
+    ret.extend(self.source_files.iter().map(|(&path, file)| {
+      let errors = source_file_errors(
+        file,
+        &self.syms,
+        self.error_lines,
+        &deprecated,
+        input.lints,
+        &input.severities,
+        input.max_errors_per_path,
+      );
+      (path, errors)
+    }));
+    ret
   }
 
   /// Returns a Markdown string with information about this position.
@@ -110,21 +313,99 @@ impl Analysis {
       };
       info.get_doc(def.idx)
     });
-    let parts: Vec<_> = [ty_md.as_deref(), def_doc, tok.kind().token_doc()]
-      .into_iter()
-      .flatten()
-      .collect();
+    let ty_var_md = ty_var_md(file, &tok, idx);
+    let parts: Vec<_> = [
+      ty_md.as_deref(),
+      ty_var_md.as_deref(),
+      def_doc,
+      tok.kind().token_doc(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
     let range = ptr.to_node(file.parsed.root.syntax()).text_range();
     let range = file.pos_db.range(range)?;
     Some((parts.join("\n\n---\n\n"), range))
   }
 
+  /// Returns a Markdown string with the type of the smallest expression containing all of this
+  /// range, and the range of that expression.
+  ///
+  /// Unlike [`Self::get_md`], which shows the innermost thing at a position, this is for showing the
+  /// type of a selection, like all of `f (g x) y`.
+  pub fn get_selection_ty_md(&self, range: WithPath<Range>) -> Option<(String, Range)> {
+    let file = self.source_files.get(&range.path)?;
+    let start = file.pos_db.text_size(range.val.start)?;
+    let end = file.pos_db.text_size(range.val.end)?;
+    let text_range = text_size_util::TextRange::new(start, end);
+    let root = file.parsed.root.syntax();
+    if !root.text_range().contains_range(text_range) {
+      return None;
+    }
+    let mut node = match root.covering_element(text_range) {
+      NodeOrToken::Node(node) => node,
+      NodeOrToken::Token(tok) => tok.parent()?,
+    };
+    loop {
+      // parentheses are lowered to the expression inside them.
+      let mut inner = node.clone();
+      while let Some(exp) = sml_syntax::ast::ParenExp::cast(inner.clone()).and_then(|x| x.exp()) {
+        inner = exp.syntax().clone();
+      }
+      let ptr = SyntaxNodePtr::new(&inner);
+      if let Some(idx @ sml_hir::Idx::Exp(_)) = file.lowered.ptrs.ast_to_hir(ptr) {
+        let ty_md = file.info.get_ty_md(&self.syms, idx)?;
+        let range = file.pos_db.range(node.text_range())?;
+        return Some((ty_md, range));
+      }
+      node = node.parent()?;
+    }
+  }
+
   /// Returns the range of the definition of the item at this position.
+  ///
+  /// For a record selector like `#foo`, this is the range of the field `foo` in the declaration of
+  /// the record type, if the type is declared with a `type` or `datatype`.
   pub fn get_def(&self, pos: WithPath<Position>) -> Option<WithPath<Range>> {
-    let (file, _, _, idx) = self.get_file_with_idx(pos)?;
+    let (file, _, ptr, idx) = self.get_file_with_idx(pos)?;
+    if ptr.kind() == SyntaxKind::SelectorExp {
+      return self.get_field_def(file, idx);
+    }
     self.def_to_path_and_range(file.info.get_def(idx)?)
   }
 
+  /// Returns the name of the std basis file containing the definition of the item at this position,
+  /// and the range of the definition in that file, if the definition is in the std basis.
+  ///
+  /// Use [`Self::std_basis_doc`] to get the contents of that file, to show as a read-only document.
+  pub fn get_std_basis_def(&self, pos: WithPath<Position>) -> Option<(&'static str, Range)> {
+    let (file, _, _, idx) = self.get_file_with_idx(pos)?;
+    let def = file.info.get_def(idx)?;
+    let name = match def.path {
+      sml_statics::DefPath::Regular(_) => return None,
+      sml_statics::DefPath::StdBasis(name) => name,
+    };
+    let contents = self.std_basis.get_contents(name)?;
+    // re-parse and re-lower, since we don't keep those for the std basis. they're deterministic, so
+    // the def idx is the same as when the std basis was checked.
+    let mut fix_env = mlb_statics::STD_BASIS_FIX_ENV.clone();
+    let (_, parsed, low) =
+      mlb_statics::start_source_file(sml_lower::Config::default(), contents, &mut fix_env);
+    let range = low
+      .ptrs
+      .hir_to_ast(def.idx)?
+      .to_node(parsed.root.syntax())
+      .text_range();
+    let range = text_pos::PositionDb::new(contents).range(range)?;
+    Some((name, range))
+  }
+
+  /// Returns the contents of the std basis file with this name, as returned by
+  /// [`Self::get_std_basis_def`].
+  pub fn std_basis_doc(&self, name: &str) -> Option<String> {
+    self.std_basis.get_contents(name).map(ToOwned::to_owned)
+  }
+
   /// Returns the ranges of the definitions of the types involved in the type of the item at this
   /// position.
   pub fn get_ty_defs(&self, pos: WithPath<Position>) -> Option<Vec<WithPath<Range>>> {
@@ -139,6 +420,315 @@ impl Analysis {
     )
   }
 
+  /// Returns the range of the definition of the outermost type constructor in the type of the item
+  /// at this position which is not from the std basis.
+  pub fn get_type_definition(&self, pos: WithPath<Position>) -> Option<WithPath<Range>> {
+    let (file, _, _, idx) = self.get_file_with_idx(pos)?;
+    self.def_to_path_and_range(file.info.get_ty_def(&self.syms, idx)?)
+  }
+
+  /// Returns the names of the type variables that may be generalized in the type of the item at
+  /// this position. For instance, this is one type variable for a use of the identity function.
+  pub fn free_ty_vars(&self, pos: WithPath<Position>) -> Option<Vec<String>> {
+    let (file, _, _, idx) = self.get_file_with_idx(pos)?;
+    file.info.get_ty_vars(idx)
+  }
+
+  /// Returns a stable identifier for the symbol at this position, for use by indexing tools to link
+  /// uses of a symbol to its definition across runs.
+  ///
+  /// The format is `<path>#<names>`, where `<path>` is the path of the file containing the
+  /// definition relative to the root, with `/` as the separator, and `<names>` is the names of the
+  /// structures, signatures, and functors containing the definition, followed by the name of the
+  /// symbol itself, separated by `.`. For instance, `src/foo.sml#Foo.bar` for `bar` defined in
+  /// `structure Foo` in `src/foo.sml`.
+  ///
+  /// If the symbol is local, e.g. bound in a `let`, a function parameter, or the first part of a
+  /// `local`, the format is instead `<path>#<names>@<line>:<column>`, where `<line>` and `<column>`
+  /// are the 1-based position of the definition. This distinguishes same-named locals in different
+  /// scopes.
+  ///
+  /// Returns `None` for symbols defined in the std basis.
+  pub fn moniker(&self, pos: WithPath<Position>) -> Option<String> {
+    let (file, _, _, idx) = self.get_file_with_idx(pos)?;
+    let def = file.info.get_def(idx).unwrap_or(sml_statics::Def {
+      path: sml_statics::DefPath::Regular(pos.path),
+      idx,
+    });
+    let def_path = match def.path {
+      sml_statics::DefPath::Regular(p) => p,
+      sml_statics::DefPath::StdBasis(_) => return None,
+    };
+    let def_file = self.source_files.get(&def_path)?;
+    let node = def_file
+      .lowered
+      .ptrs
+      .hir_to_ast(def.idx)?
+      .to_node(def_file.parsed.root.syntax());
+    let name = node
+      .descendants_with_tokens()
+      .filter_map(|x| x.into_token())
+      .find(|tok| tok.kind() == SyntaxKind::Name)?;
+    let mut names = vec![name.text().to_owned()];
+    let local = node
+      .ancestors()
+      .skip(1)
+      .any(|anc| is_local_scope(&anc, &node));
+    for node in node.ancestors().skip(1) {
+      let name = if let Some(bind) = sml_syntax::ast::StrBind::cast(node.clone()) {
+        bind.name()
+      } else if let Some(bind) = sml_syntax::ast::SigBind::cast(node.clone()) {
+        bind.name()
+      } else if let Some(bind) = sml_syntax::ast::FunctorBind::cast(node) {
+        bind.functor_name()
+      } else {
+        continue;
+      };
+      names.push(name?.text().to_owned());
+    }
+    names.reverse();
+    let rel_path: Vec<_> = self
+      .rel_paths
+      .get(&def_path)?
+      .iter()
+      .map(|x| x.to_string_lossy())
+      .collect();
+    let mut ret = format!("{}#{}", rel_path.join("/"), names.join("."));
+    if local {
+      let pos = def_file.pos_db.range(name.text_range())?.start;
+      ret.push_str(&format!("@{}:{}", pos.line + 1, pos.character + 1));
+    }
+    Some(ret)
+  }
+
+  /// Returns the kind of the symbol at this position.
+  pub fn symbol_kind(&self, pos: WithPath<Position>) -> Option<SymbolKind> {
+    let (file, _, _, idx) = self.get_file_with_idx(pos)?;
+    file
+      .info
+      .get_symbol_kind(idx)
+      .or_else(|| binding_symbol_kind(&file.lowered.arenas, idx))
+  }
+
+  /// Returns the edits, grouped by path, to rename the item at this position to `new_name`.
+  ///
+  /// Returns `None` if `new_name` is not a valid name, if the item is not renameable (e.g. it is
+  /// from the std basis), or if `new_name` is already in scope at the definition or any use of the
+  /// item.
+  pub fn rename(
+    &self,
+    pos: WithPath<Position>,
+    new_name: &str,
+  ) -> Option<PathMap<Vec<(Range, String)>>> {
+    if !is_name(new_name) {
+      return None;
+    }
+    let (file, tok, _, idx) = self.get_file_with_idx(pos)?;
+    if tok.kind() != SyntaxKind::Name {
+      return None;
+    }
+    let old_name = tok.text();
+    let def = file.info.get_def(idx).unwrap_or(sml_statics::Def {
+      path: sml_statics::DefPath::Regular(pos.path),
+      idx,
+    });
+    let def_path = match def.path {
+      sml_statics::DefPath::Regular(p) => p,
+      sml_statics::DefPath::StdBasis(_) => return None,
+    };
+    let mut ret = PathMap::<Vec<(Range, String)>>::default();
+    let def_file = self.source_files.get(&def_path)?;
+    // the renamed item would shadow, or be shadowed by, anything already named `new_name` in scope.
+    if name_in_scope(def_file, def.idx, new_name) {
+      return None;
+    }
+    let def_range = name_range(def_file, def.idx, old_name)?;
+    ret
+      .entry(def_path)
+      .or_default()
+      .push((def_range, new_name.to_owned()));
+    for (&path, file) in self.source_files.iter() {
+      for idx in file.info.get_with_def(def) {
+        if name_in_scope(file, idx, new_name) {
+          return None;
+        }
+        if let Some(range) = name_range(file, idx, old_name) {
+          ret
+            .entry(path)
+            .or_default()
+            .push((range, new_name.to_owned()));
+        }
+      }
+    }
+    for edits in ret.values_mut() {
+      edits.sort_unstable_by_key(|(range, _)| (range.start.line, range.start.character));
+      edits.dedup_by_key(|(range, _)| (range.start.line, range.start.character));
+    }
+    Some(ret)
+  }
+
+  /// Returns the symbols declared in the file at this path, nested under the structures,
+  /// signatures, and functors that contain them.
+  pub fn document_symbols(&self, path: paths::PathId) -> Vec<Symbol> {
+    match self.source_files.get(&path) {
+      Some(file) => symbol::get(&file.pos_db, &file.parsed.root),
+      None => Vec::new(),
+    }
+  }
+
+  /// Returns the ranges of the foldable regions in the file at this path, like `struct ... end`,
+  /// `let ... end`, and block comments. Regions that span only one line are not included.
+  ///
+  /// This only uses the syntax tree, so it works even if there are statics errors.
+  pub fn folding_ranges(&self, path: paths::PathId) -> Vec<Range> {
+    let file = match self.source_files.get(&path) {
+      Some(x) => x,
+      None => return Vec::new(),
+    };
+    folding_range::get(file.parsed.root.syntax())
+      .into_iter()
+      .filter_map(|range| file.pos_db.range(range))
+      .filter(|range| range.start.line != range.end.line)
+      .collect()
+  }
+
+  /// Returns the most general type of the value named `name` bound by a top-level `val` or `fun`
+  /// in the file at this path, rendered as in hover.
+  ///
+  /// For instance, this is `'a -> 'a` for `fun id x = x`, not the type of any one use of `id`.
+  pub fn top_level_type(&self, path: paths::PathId, name: &str) -> Option<String> {
+    let file = self.source_files.get(&path)?;
+    file.info.get_top_level_ty_scheme(&self.syms, name)
+  }
+
+  /// Returns the formatted text of the file at this path, or `None` if there is no such file or it
+  /// has lex or parse errors.
+  ///
+  /// The formatting is conservative: it re-indents the bodies of `struct`, `sig`, `let`, and
+  /// `local`, and normalizes the spacing between tokens on a line, but never moves tokens between
+  /// lines. Comments are kept, and formatting already formatted text does nothing.
+  pub fn format(&self, path: paths::PathId) -> Option<String> {
+    let file = self.source_files.get(&path)?;
+    if !file.lex_errors.is_empty() || !file.parsed.errors.is_empty() {
+      return None;
+    }
+    Some(format::get(file.parsed.root.syntax()))
+  }
+
+  /// Returns the ranges of the declarations containing this position, innermost first. For
+  /// instance, for a position in a `val` in a `struct` of a `structure`, this is the range of the
+  /// `val` and then the range of the `structure`.
+  ///
+  /// This only uses the syntax tree, so it works even if there are statics errors.
+  pub fn enclosing_decls(&self, pos: WithPath<Position>) -> Vec<Range> {
+    let file = match self.source_files.get(&pos.path) {
+      Some(x) => x,
+      None => return Vec::new(),
+    };
+    let tok = match get_token(file, pos.val) {
+      Some(x) => x,
+      None => return Vec::new(),
+    };
+    enclosing_decl::get(&tok)
+      .into_iter()
+      .filter_map(|range| file.pos_db.range(range))
+      .collect()
+  }
+
+  /// Returns the classified names and type variables in the file at this path, sorted by position.
+  ///
+  /// Names are classified by what statics resolved them to, like a constructor or a value, and by
+  /// the syntax alone if they did not resolve.
+  pub fn semantic_tokens(&self, path: paths::PathId) -> Vec<SemanticToken> {
+    match self.source_files.get(&path) {
+      Some(file) => semantic_token::get(file),
+      None => Vec::new(),
+    }
+  }
+
+  /// Returns a readable, deterministic dump of the lowered HIR of the file at this path, for
+  /// debugging and tool authors. Returns `None` if there is no such file.
+  ///
+  /// HIR nodes with no pointer back to the syntax tree are marked with `(no ptr)`.
+  pub fn debug_hir(&self, path: paths::PathId) -> Option<String> {
+    self.source_files.get(&path).map(debug_hir::get)
+  }
+
+  /// Returns the fixity of the infix name `name` at the end of the file at this path, or `None` if
+  /// the name is not infix there.
+  ///
+  /// This accounts for the std basis, and for `infix`, `infixr`, and `nonfix` declarations in this
+  /// file and in the files it depends on.
+  pub fn fixity(&self, path: paths::PathId, name: &str) -> Option<Infix> {
+    self.source_files.get(&path)?.fix_env.get(name).copied()
+  }
+
+  /// Returns the names in scope at this position, with the innermost binding of shadowed names.
+  ///
+  /// After a structure path followed by a `.`, like `List.`, returns only the members of that
+  /// structure, without fixities. See [`CompletionItem::infix`].
+  pub fn completions(&self, pos: WithPath<Position>) -> Vec<CompletionItem> {
+    self.completions_(pos).unwrap_or_default()
+  }
+
+  fn completions_(&self, pos: WithPath<Position>) -> Option<Vec<CompletionItem>> {
+    let file = self.source_files.get(&pos.path)?;
+    let offset = file.pos_db.text_size(pos.val)?;
+    let root = file.parsed.root.syntax();
+    // prefer the env of the innermost node containing the position. failing that, use the env
+    // before the first node after the position, or failing that, the env at the end of the file.
+    let mut containing = None::<(text_size_util::TextRange, sml_hir::Idx)>;
+    let mut after = None::<(text_size_util::TextRange, sml_hir::Idx)>;
+    for idx in file.info.env_indices() {
+      let range = match file.lowered.ptrs.hir_to_ast(idx) {
+        Some(ptr) => ptr.to_node(root).text_range(),
+        None => continue,
+      };
+      if range.contains_inclusive(offset) {
+        if containing.map_or(true, |(r, _)| range.len() < r.len()) {
+          containing = Some((range, idx));
+        }
+      } else if range.start() > offset
+        && after.map_or(true, |(r, _)| {
+          range.start() < r.start() || (range.start() == r.start() && range.len() > r.len())
+        })
+      {
+        after = Some((range, idx));
+      }
+    }
+    let idx = containing.or(after).map(|(_, idx)| idx);
+    let structures = structure_prefix(root, offset);
+    let items = file.info.completions(&self.syms, idx, &structures);
+    let items = items.into_iter().map(|item| {
+      // fixity is only looked up for unqualified values, and only at the end of the file.
+      let is_val = matches!(
+        item.kind,
+        SymbolKind::Value | SymbolKind::Constructor | SymbolKind::Exception
+      );
+      let infix = if structures.is_empty() && is_val {
+        file.fix_env.get(item.label.as_str()).copied()
+      } else {
+        None
+      };
+      CompletionItem {
+        label: item.label,
+        kind: item.kind,
+        detail: item.detail,
+        infix,
+      }
+    });
+    Some(items.collect())
+  }
+
+  /// Returns the inferred types to show after the patterns of `val` and `fun` bindings in the file
+  /// at this path, for those bindings without a type annotation.
+  pub fn inlay_hints(&self, path: paths::PathId) -> Vec<(Position, String)> {
+    match self.source_files.get(&path) {
+      Some(file) => inlay_hint::get(file, &self.syms),
+      None => Vec::new(),
+    }
+  }
+
   /// Given a position on a `case` expression, return the code and its range to fill the case with
   /// all of the variants of the head's type.
   pub fn fill_case(&self, pos: WithPath<Position>) -> Option<(Range, String)> {
@@ -147,10 +737,7 @@ impl Analysis {
     let case = ptr.to_node(file.parsed.root.syntax());
     let range = text_size_util::TextRange::empty(case.syntax().text_range().end());
     let range = file.pos_db.range(range)?;
-    let head_ast = case.exp()?;
-    let head_ptr = SyntaxNodePtr::new(head_ast.syntax());
-    let head = file.lowered.ptrs.ast_to_hir(head_ptr)?;
-    let variants = file.info.get_variants(&self.syms, head)?;
+    let variants = self.missing_case_variants(pos)?;
     let case = CaseDisplay {
       needs_starting_bar: case
         .matcher()
@@ -160,6 +747,121 @@ impl Analysis {
     Some((range, case.to_string()))
   }
 
+  /// Given a position on a `case` expression, return the names of the variants of the head's type
+  /// not already matched by the case's arms, and whether each takes an argument.
+  ///
+  /// Names of constructors that are infix (like `::`) are prefixed with `op`, so they may be used
+  /// as-is in a pattern.
+  ///
+  /// Returns `None` if no variants are missing, including if an arm matches anything.
+  pub fn missing_case_variants(&self, pos: WithPath<Position>) -> Option<Vec<(String, bool)>> {
+    let (file, _, ptr, _) = self.get_file_with_idx(pos)?;
+    let ptr = ptr.cast::<sml_syntax::ast::CaseExp>()?;
+    let case = ptr.to_node(file.parsed.root.syntax());
+    let head_ast = case.exp()?;
+    let head_ptr = SyntaxNodePtr::new(head_ast.syntax());
+    let head = file.lowered.ptrs.ast_to_hir(head_ptr)?;
+    let variants = file.info.get_variants(&self.syms, head)?;
+    let mut covered = FxHashSet::<&sml_hir::Name>::default();
+    for rule in case.matcher().into_iter().flat_map(|x| x.match_rules()) {
+      let pat = match rule.pat() {
+        Some(x) => x,
+        None => continue,
+      };
+      let pat = match file
+        .lowered
+        .ptrs
+        .ast_to_hir(SyntaxNodePtr::new(pat.syntax()))
+      {
+        Some(sml_hir::Idx::Pat(x)) => x,
+        _ => continue,
+      };
+      if covers_all(&file.lowered.arenas, &variants, pat, &mut covered) {
+        return None;
+      }
+    }
+    let ret: Vec<_> = variants
+      .iter()
+      .filter(|(name, _)| !covered.contains(name))
+      .map(|&(ref name, has_arg)| {
+        let name = if file.fix_env.contains_key(name.as_str()) {
+          format!("op {name}")
+        } else {
+          name.as_str().to_owned()
+        };
+        (name, has_arg)
+      })
+      .collect();
+    (!ret.is_empty()).then_some(ret)
+  }
+
+  /// Given a position on errors that can be fixed automatically, returns the code and its range to
+  /// fix each such error.
+  pub fn quick_fixes(&self, pos: WithPath<Position>) -> Vec<(Range, String)> {
+    let file = match self.source_files.get(&pos.path) {
+      Some(x) => x,
+      None => return Vec::new(),
+    };
+    let idx = match file.pos_db.text_size(pos.val) {
+      Some(x) => x,
+      None => return Vec::new(),
+    };
+    let root = file.parsed.root.syntax();
+    file
+      .parsed
+      .errors
+      .iter()
+      .filter(|err| err.range().contains_inclusive(idx))
+      .filter_map(|err| {
+        let (range, new_text) = quick_fix::get(root, err.to_code(), err.range())?;
+        Some((file.pos_db.range(range)?, new_text))
+      })
+      .collect()
+  }
+
+  /// Given a position in a tuple expression, pattern, or type, return the code and its range to
+  /// write the innermost such tuple as a record with numeric labels.
+  pub fn tuple_to_record(&self, pos: WithPath<Position>) -> Option<(Range, String)> {
+    let file = self.source_files.get(&pos.path)?;
+    let tok = get_token(file, pos.val)?;
+    let (range, new_text) = tuple_record::tuple_to_record(&tok)?;
+    Some((file.pos_db.range(range)?, new_text))
+  }
+
+  /// Given a position in a record expression, pattern, or type whose labels are `1` through `n` in
+  /// order for some `n >= 2`, return the code and its range to write the innermost such record as
+  /// a tuple.
+  pub fn record_to_tuple(&self, pos: WithPath<Position>) -> Option<(Range, String)> {
+    let file = self.source_files.get(&pos.path)?;
+    let tok = get_token(file, pos.val)?;
+    let (range, new_text) = tuple_record::record_to_tuple(&tok)?;
+    Some((file.pos_db.range(range)?, new_text))
+  }
+
+  /// Returns statistics about the source files from the last call to [`Self::get_many`],
+  /// [`Self::recheck_many`], or [`Self::update_file`]. This does not re-run any analysis.
+  pub fn stats(&self) -> AnalysisStats {
+    let mut ret = AnalysisStats {
+      source_files: self.source_files.len(),
+      reused_statics: self.checked.reused(),
+      ..AnalysisStats::default()
+    };
+    for file in self.source_files.values() {
+      let mv_stats = file.info.meta_var_stats();
+      ret.meta_vars += mv_stats.generated;
+      ret.solved_meta_vars += mv_stats.solved;
+      let codes = std::iter::empty()
+        .chain(file.lex_errors.iter().map(|e| e.to_code()))
+        .chain(file.parsed.errors.iter().map(|e| e.to_code()))
+        .chain(file.lowered.errors.iter().map(|e| e.to_code()))
+        .chain(file.statics_errors.iter().map(|e| e.to_code()));
+      for code in codes {
+        *ret.errors_by_code.entry(code).or_default() += 1;
+      }
+    }
+    ret
+  }
+
   fn get_file_with_idx(
     &self,
     pos: WithPath<Position>,
@@ -181,6 +883,55 @@ impl Analysis {
     }
   }
 
+  /// returns the range of the field selected by the selector at `idx`, in the innermost `type` or
+  /// `datatype` declaration in scope whose record type is exactly the selected record type.
+  fn get_field_def(
+    &self,
+    file: &mlb_statics::SourceFile,
+    idx: sml_hir::Idx,
+  ) -> Option<WithPath<Range>> {
+    let arenas = &file.lowered.arenas;
+    let param = match idx {
+      sml_hir::Idx::Exp(exp) => match &arenas.exp[exp] {
+        sml_hir::Exp::Fn(arms) => arms.first()?.0?,
+        _ => return None,
+      },
+      _ => return None,
+    };
+    let lab = match &arenas.pat[param] {
+      sml_hir::Pat::Record { rows, .. } => &rows.first()?.0,
+      _ => return None,
+    };
+    let scope = scope_idx(file, idx)?;
+    file
+      .info
+      .get_record_ty_defs(idx, scope)?
+      .into_iter()
+      .find_map(|(def, name)| {
+        let path = match def.path {
+          sml_statics::DefPath::Regular(p) => p,
+          sml_statics::DefPath::StdBasis(_) => return None,
+        };
+        let dec = match def.idx {
+          sml_hir::Idx::Dec(x) => x,
+          _ => return None,
+        };
+        let def_file = self.source_files.get(&path)?;
+        let rows = declared_record_ty(&def_file.lowered.arenas, dec, &name)?;
+        let ty = rows
+          .iter()
+          .find_map(|(l, ty)| if l == lab { *ty } else { None })?;
+        // the field is the whole row, like `foo : int`, which is the parent of the type.
+        let ty_node = def_file
+          .lowered
+          .ptrs
+          .hir_to_ast(ty.into())?
+          .to_node(def_file.parsed.root.syntax());
+        let row = ty_node.parent()?;
+        Some(path.wrap(def_file.pos_db.range(row.text_range())?))
+      })
+  }
+
   fn def_to_path_and_range(&self, def: sml_statics::Def) -> Option<WithPath<Range>> {
     let path = match def.path {
       sml_statics::DefPath::Regular(p) => p,
@@ -197,6 +948,234 @@ impl Analysis {
   }
 }
 
+/// the rows of the record type that is the type of the `type` named `name` or the argument of the
+/// `datatype` constructor named `name`, in `dec`.
+fn declared_record_ty<'a>(
+  arenas: &'a sml_hir::Arenas,
+  dec: sml_hir::la_arena::Idx<sml_hir::Dec>,
+  name: &sml_hir::Name,
+) -> Option<&'a [(sml_hir::Lab, sml_hir::TyIdx)]> {
+  let (dat_binds, ty_binds) = match &arenas.dec[dec] {
+    sml_hir::Dec::Ty(ty_binds) => (&[][..], &ty_binds[..]),
+    sml_hir::Dec::Datatype(dat_binds, ty_binds) | sml_hir::Dec::Abstype(dat_binds, ty_binds, _) => {
+      (&dat_binds[..], &ty_binds[..])
+    }
+    _ => return None,
+  };
+  let ty = match ty_binds.iter().find(|x| x.name == *name) {
+    Some(ty_bind) => ty_bind.ty,
+    None => {
+      let con_bind = dat_binds
+        .iter()
+        .flat_map(|x| x.cons.iter())
+        .find(|x| x.name == *name)?;
+      con_bind.ty.flatten()
+    }
+  };
+  match &arenas.ty[ty?] {
+    sml_hir::Ty::Record(rows) => Some(rows.as_slice()),
+    _ => None,
+  }
+}
+
+/// the kind of symbol bound by the declaration at `idx`, if any.
+fn binding_symbol_kind(arenas: &sml_hir::Arenas, idx: sml_hir::Idx) -> Option<SymbolKind> {
+  let ret = match idx {
+    sml_hir::Idx::StrDec(idx) => match &arenas.str_dec[idx] {
+      sml_hir::StrDec::Structure(_) => SymbolKind::Structure,
+      sml_hir::StrDec::Signature(_) => SymbolKind::Signature,
+      sml_hir::StrDec::Functor(_) => SymbolKind::Functor,
+      sml_hir::StrDec::Dec(_) | sml_hir::StrDec::Local(_, _) | sml_hir::StrDec::Seq(_) => {
+        return None
+      }
+    },
+    sml_hir::Idx::Dec(idx) => match &arenas.dec[idx] {
+      sml_hir::Dec::Ty(_)
+      | sml_hir::Dec::Datatype(_, _)
+      | sml_hir::Dec::DatatypeCopy(_, _)
+      | sml_hir::Dec::Abstype(_, _, _) => SymbolKind::Type,
+      sml_hir::Dec::Exception(_) => SymbolKind::Exception,
+      sml_hir::Dec::Val(_, _) => SymbolKind::Value,
+      sml_hir::Dec::Hole
+      | sml_hir::Dec::Local(_, _)
+      | sml_hir::Dec::Open(_)
+      | sml_hir::Dec::Seq(_) => return None,
+    },
+    sml_hir::Idx::Spec(idx) => match &arenas.spec[idx] {
+      sml_hir::Spec::Val(_, _) => SymbolKind::Value,
+      sml_hir::Spec::Ty(_)
+      | sml_hir::Spec::EqTy(_)
+      | sml_hir::Spec::Datatype(_)
+      | sml_hir::Spec::DatatypeCopy(_, _) => SymbolKind::Type,
+      sml_hir::Spec::Exception(_) => SymbolKind::Exception,
+      sml_hir::Spec::Str(_) => SymbolKind::Structure,
+      sml_hir::Spec::Include(_) | sml_hir::Spec::Sharing(_, _, _) | sml_hir::Spec::Seq(_) => {
+        return None
+      }
+    },
+    sml_hir::Idx::StrExp(_)
+    | sml_hir::Idx::SigExp(_)
+    | sml_hir::Idx::Exp(_)
+    | sml_hir::Idx::Pat(_)
+    | sml_hir::Idx::Ty(_) => return None,
+  };
+  Some(ret)
+}
+
+/// returns the range of the first token in the node for `idx` that is the name `name`.
+fn name_range(file: &mlb_statics::SourceFile, idx: sml_hir::Idx, name: &str) -> Option<Range> {
+  let node = file
+    .lowered
+    .ptrs
+    .hir_to_ast(idx)?
+    .to_node(file.parsed.root.syntax());
+  let tok = node
+    .descendants_with_tokens()
+    .filter_map(|x| x.into_token())
+    .find(|tok| tok.kind() == SyntaxKind::Name && tok.text() == name)?;
+  file.pos_db.range(tok.text_range())
+}
+
+/// returns whether the names bound by `node` are local to the ancestor `anc`, i.e. not visible to
+/// the structure-level declarations after `anc`.
+fn is_local_scope(anc: &SyntaxNode, node: &SyntaxNode) -> bool {
+  let local_dec = match anc.kind() {
+    SyntaxKind::LetExp | SyntaxKind::MatchRule | SyntaxKind::FunBindCase => return true,
+    SyntaxKind::LocalDec => sml_syntax::ast::LocalDec::cast(anc.clone())
+      .and_then(|x| x.local_dec())
+      .map(|x| x.syntax().text_range()),
+    SyntaxKind::LocalStrDec => sml_syntax::ast::LocalStrDec::cast(anc.clone())
+      .and_then(|x| x.local_dec())
+      .map(|x| x.syntax().text_range()),
+    _ => return false,
+  };
+  local_dec.map_or(false, |range| range.contains_range(node.text_range()))
+}
+
+/// returns whether `name` is in scope at `idx`, according to the nearest enclosing node for which
+/// statics recorded what is in scope. conservatively returns true if there is no such node.
+fn name_in_scope(file: &mlb_statics::SourceFile, idx: sml_hir::Idx, name: &str) -> bool {
+  scope_idx(file, idx)
+    .and_then(|scope| file.info.is_in_scope(scope, name))
+    .unwrap_or(true)
+}
+
+/// returns the innermost index enclosing `idx`, possibly `idx` itself, at which statics recorded
+/// what is in scope.
+///
+/// statics records this only at some indices, to keep analysis cheap, so this searches all of them.
+fn scope_idx(file: &mlb_statics::SourceFile, idx: sml_hir::Idx) -> Option<sml_hir::Idx> {
+  let root = file.parsed.root.syntax();
+  let range = file
+    .lowered
+    .ptrs
+    .hir_to_ast(idx)?
+    .to_node(root)
+    .text_range();
+  let mut ret = None::<(text_size_util::TextRange, sml_hir::Idx)>;
+  for scope in file.info.env_indices() {
+    let scope_range = match file.lowered.ptrs.hir_to_ast(scope) {
+      Some(ptr) => ptr.to_node(root).text_range(),
+      None => continue,
+    };
+    if scope_range.contains_range(range) && ret.map_or(true, |(r, _)| scope_range.len() < r.len()) {
+      ret = Some((scope_range, scope));
+    }
+  }
+  ret.map(|(_, scope)| scope)
+}
+
+/// returns a description of where the type variable `tok` at `idx` is bound, if it is one.
+fn ty_var_md(
+  file: &mlb_statics::SourceFile,
+  tok: &SyntaxToken,
+  idx: sml_hir::Idx,
+) -> Option<String> {
+  let is_ty_var = match idx {
+    sml_hir::Idx::Ty(ty) => matches!(file.lowered.arenas.ty[ty], sml_hir::Ty::Var(_)),
+    _ => false,
+  };
+  if tok.kind() != SyntaxKind::TyVar || !is_ty_var {
+    return None;
+  }
+  let name = tok.text();
+  let what = if name.starts_with("''") {
+    "an equality type variable"
+  } else {
+    "a type variable"
+  };
+  let site = match file.info.get_ty_var_site(idx) {
+    Some(x) => x,
+    None => return Some(format!("`{name}` is {what}. It is free.")),
+  };
+  let node = file
+    .lowered
+    .ptrs
+    .hir_to_ast(site)?
+    .to_node(file.parsed.root.syntax());
+  let start = node
+    .descendants_with_tokens()
+    .filter_map(|x| x.into_token())
+    .find(|x| !x.kind().is_trivia())?;
+  let line = file.pos_db.range(start.text_range())?.start.line + 1;
+  let binding = match site {
+    sml_hir::Idx::Dec(dec) if matches!(file.lowered.arenas.dec[dec], sml_hir::Dec::Val(_, _)) => {
+      // NOTE: the implicitly bound ty vars were added to the ty var seq in the HIR, but not in the
+      // syntax.
+      let explicit = node
+        .children()
+        .filter(|x| x.kind() == SyntaxKind::TyVarSeq)
+        .flat_map(|x| x.descendants_with_tokens())
+        .filter_map(|x| x.into_token())
+        .any(|x| x.kind() == SyntaxKind::TyVar && x.text() == name);
+      let how = if explicit { "explicitly" } else { "implicitly" };
+      format!("{how} bound at the `{}` on line {line}", start.text())
+    }
+    sml_hir::Idx::Spec(_) => format!("implicitly bound at the `val` on line {line}"),
+    _ => format!("bound at the `{}` on line {line}", start.text()),
+  };
+  Some(format!("`{name}` is {what}. It is {binding}."))
+}
+
+/// returns whether `s` lexes as exactly one name, and nothing else.
+fn is_name(s: &str) -> bool {
+  let lex = sml_lex::get(s);
+  lex.errors.is_empty() && matches!(lex.tokens[..], [tok] if tok.kind == SyntaxKind::Name)
+}
+
+/// returns the structure names in a path like `A.B.` (possibly followed by a partial name) just
+/// before the offset.
+fn structure_prefix(
+  root: &sml_syntax::SyntaxNode,
+  offset: text_size_util::TextSize,
+) -> Vec<sml_hir::Name> {
+  let mut ret = Vec::<sml_hir::Name>::new();
+  let mut tok = match root.token_at_offset(offset).left_biased() {
+    Some(x) => x,
+    None => return ret,
+  };
+  if tok.kind() == SyntaxKind::Name {
+    tok = match tok.prev_token() {
+      Some(x) => x,
+      None => return ret,
+    };
+  }
+  while tok.kind() == SyntaxKind::Dot {
+    match tok.prev_token() {
+      Some(name) if name.kind() == SyntaxKind::Name => {
+        ret.push(sml_hir::Name::new(name.text()));
+        tok = match name.prev_token() {
+          Some(x) => x,
+          None => break,
+        };
+      }
+      _ => break,
+    }
+  }
+  ret.reverse();
+  ret
+}
+
 fn get_token(file: &mlb_statics::SourceFile, pos: Position) -> Option<SyntaxToken> {
   let idx = file.pos_db.text_size(pos)?;
   if !file.parsed.root.syntax().text_range().contains(idx) {
@@ -218,71 +1197,286 @@ fn get_token(file: &mlb_statics::SourceFile, pos: Position) -> Option<SyntaxToke
 
 fn priority(kind: SyntaxKind) -> u8 {
   match kind {
+    // these begin and end with quotes, so a position at either end of one, even next to a name, is
+    // probably meant to be on it.
+    SyntaxKind::CharLit | SyntaxKind::StringLit => 6,
     SyntaxKind::Name => 5,
     SyntaxKind::OpKw => 4,
     SyntaxKind::TyVar => 3,
-    SyntaxKind::CharLit
-    | SyntaxKind::IntLit
-    | SyntaxKind::RealLit
-    | SyntaxKind::StringLit
-    | SyntaxKind::WordLit => 2,
-    SyntaxKind::Whitespace | SyntaxKind::BlockComment | SyntaxKind::Invalid => 0,
+    SyntaxKind::IntLit | SyntaxKind::RealLit | SyntaxKind::WordLit => 2,
+    SyntaxKind::Whitespace
+    | SyntaxKind::BlockComment
+    | SyntaxKind::Shebang
+    | SyntaxKind::Invalid => 0,
     _ => 1,
   }
 }
 
-/// The max number of errors per path.
-const MAX_ERRORS_PER_PATH: usize = 20;
+fn group_decs(input: &input::Input) -> PathMap<&mlb_hir::BasDec> {
+  input
+    .groups
+    .iter()
+    .map(|(&path, group)| (path, &group.bas_dec))
+    .collect()
+}
+
+/// The max number of errors per path, unless configured otherwise.
+pub(crate) const DEFAULT_MAX_ERRORS_PER_PATH: usize = 20;
+
+/// Returns the errors from lexing, parsing, and lowering `s`, without checking statics.
+///
+/// This is much faster than [`Analysis::get_one`], so it's useful for quick feedback while typing.
+/// It reports only syntax errors and unsupported language constructs, never type errors.
+pub fn check_syntax(s: &str) -> Vec<Error> {
+  let mut fix_env = mlb_statics::STD_BASIS_FIX_ENV.clone();
+  let (lex_errors, parsed, low) =
+    mlb_statics::start_source_file(sml_lower::Config::default(), s, &mut fix_env);
+  let pos_db = text_pos::PositionDb::new(s);
+  let mut ret: Vec<_> = syntax_errors(&pos_db, &lex_errors, &parsed, &low).collect();
+  ret.sort_by_key(|e| (e.range.start.line, e.range.start.character, e.code));
+  ret
+}
 
 fn source_file_errors(
   file: &mlb_statics::SourceFile,
   syms: &sml_statics::Syms,
   lines: config::ErrorLines,
+  deprecated: &[(sml_statics::Def, &sml_hir::Path)],
+  lints: input::Lints,
+  severities: &input::Severities,
+  max_errors: Option<usize>,
 ) -> Vec<Error> {
-  std::iter::empty()
-    .chain(file.lex_errors.iter().filter_map(|err| {
+  let mut ret: Vec<_> = syntax_errors(&file.pos_db, &file.lex_errors, &file.parsed, &file.lowered)
+    .chain(file.statics_errors.iter().filter_map(|err| {
+      let idx = err.idx();
+      let syntax = file
+        .lowered
+        .ptrs
+        .hir_to_ast(idx)
+        .expect("no pointer for idx");
       Some(Error {
-        range: file.pos_db.range(err.range())?,
-        message: err.display().to_string(),
+        range: file
+          .pos_db
+          .range(syntax.to_node(file.parsed.root.syntax()).text_range())?,
+        message: err.display(syms, file.info.meta_vars(), lines).to_string(),
         code: err.to_code(),
+        severity: err.severity(),
+        tags: Vec::new(),
+        related: err
+          .related()
+          .and_then(|(idx, message)| {
+            let ptr = file.lowered.ptrs.hir_to_ast(idx)?;
+            let range = ptr.to_node(file.parsed.root.syntax()).text_range();
+            Some((file.pos_db.range(range)?, message.to_owned()))
+          })
+          .into_iter()
+          .collect(),
       })
     }))
-    .chain(file.parsed.errors.iter().filter_map(|err| {
+    .chain(deprecated.iter().flat_map(|&(def, path)| {
+      file.info.get_with_def(def).filter_map(move |idx| {
+        let syntax = file.lowered.ptrs.hir_to_ast(idx)?;
+        Some(Error {
+          range: file
+            .pos_db
+            .range(syntax.to_node(file.parsed.root.syntax()).text_range())?,
+          message: format!("deprecated: {path}"),
+          code: error_code::DEPRECATED,
+          severity: Severity::Warning,
+          tags: vec![DiagnosticTag::Deprecated],
+          related: Vec::new(),
+        })
+      })
+    }))
+    .chain(
+      lints
+        .div_by_zero
+        .then(|| div_by_zero::get(file))
+        .into_iter()
+        .flatten()
+        .filter_map(|idx| {
+          let syntax = file.lowered.ptrs.hir_to_ast(idx)?;
+          Some(Error {
+            range: file
+              .pos_db
+              .range(syntax.to_node(file.parsed.root.syntax()).text_range())?,
+            message: "division by zero".to_owned(),
+            code: error_code::DIV_BY_ZERO,
+            severity: Severity::Warning,
+            tags: Vec::new(),
+            related: Vec::new(),
+          })
+        }),
+    )
+    .chain(
+      lints
+        .num_label_gap
+        .then(|| num_label_gap::get(file))
+        .into_iter()
+        .flatten()
+        .filter_map(|(idx, missing)| {
+          let syntax = file.lowered.ptrs.hir_to_ast(idx)?;
+          Some(Error {
+            range: file
+              .pos_db
+              .range(syntax.to_node(file.parsed.root.syntax()).text_range())?,
+            message: format!("missing numeric label {missing}, so this is not a tuple"),
+            code: error_code::NUM_LABEL_GAP,
+            severity: Severity::Info,
+            tags: Vec::new(),
+            related: Vec::new(),
+          })
+        }),
+    )
+    .chain(
+      lints
+        .shadow_infix
+        .then(|| shadow_infix::get(file))
+        .into_iter()
+        .flatten()
+        .filter_map(|(range, message)| {
+          Some(Error {
+            range: file.pos_db.range(range)?,
+            message,
+            code: error_code::SHADOW_INFIX,
+            severity: Severity::Warning,
+            tags: Vec::new(),
+            related: Vec::new(),
+          })
+        }),
+    )
+    .chain(
+      lints
+        .narrow_handle
+        .then(|| narrow_handle::get(file))
+        .into_iter()
+        .flatten()
+        .filter_map(|(idx, name)| {
+          let syntax = file.lowered.ptrs.hir_to_ast(idx)?;
+          Some(Error {
+            range: file
+              .pos_db
+              .range(syntax.to_node(file.parsed.root.syntax()).text_range())?,
+            message: format!(
+              "this `handle` only handles `{name}`; other exceptions are raised again"
+            ),
+            code: error_code::NARROW_HANDLE,
+            severity: Severity::Info,
+            tags: Vec::new(),
+            related: Vec::new(),
+          })
+        }),
+    )
+    .filter_map(|error| configured(error, severities))
+    .collect();
+  // sort by position, so that if we hide some errors, we keep the earliest ones.
+  ret.sort_by_key(|e| (e.range.start.line, e.range.start.character, e.code));
+  if let Some(max) = max_errors {
+    let hidden = ret.split_off(max.min(ret.len()));
+    let count = hidden.len();
+    // report the hidden errors at the first one.
+    if let Some(first) = hidden.into_iter().next() {
+      let noun = if count == 1 { "error" } else { "errors" };
+      let error = Error {
+        range: first.range,
+        message: format!("{count} more {noun} hidden"),
+        code: error_code::ERRORS_HIDDEN,
+        severity: Severity::Info,
+        tags: Vec::new(),
+        related: Vec::new(),
+      };
+      ret.extend(configured(error, severities));
+    }
+  }
+  ret
+}
+
+/// returns the errors from lexing, parsing, and lowering a file.
+fn syntax_errors<'a>(
+  pos_db: &'a text_pos::PositionDb,
+  lex_errors: &'a [sml_lex::Error],
+  parsed: &'a sml_parse::Parse,
+  lowered: &'a sml_lower::Lower,
+) -> impl Iterator<Item = Error> + 'a {
+  std::iter::empty()
+    .chain(lex_errors.iter().filter_map(|err| {
       Some(Error {
-        range: file.pos_db.range(err.range())?,
+        range: pos_db.range(err.range())?,
         message: err.display().to_string(),
         code: err.to_code(),
+        severity: Severity::Error,
+        tags: Vec::new(),
+        related: Vec::new(),
       })
     }))
-    .chain(file.lowered.errors.iter().filter_map(|err| {
+    .chain(parsed.errors.iter().filter_map(|err| {
       Some(Error {
-        range: file.pos_db.range(err.range())?,
+        range: pos_db.range(err.range())?,
         message: err.display().to_string(),
         code: err.to_code(),
+        severity: Severity::Error,
+        tags: Vec::new(),
+        related: Vec::new(),
       })
     }))
-    .chain(file.statics_errors.iter().filter_map(|err| {
-      let idx = err.idx();
-      let syntax = file
-        .lowered
-        .ptrs
-        .hir_to_ast(idx)
-        .expect("no pointer for idx");
+    .chain(lowered.errors.iter().filter_map(|err| {
       Some(Error {
-        range: file
-          .pos_db
-          .range(syntax.to_node(file.parsed.root.syntax()).text_range())?,
-        message: err.display(syms, file.info.meta_vars(), lines).to_string(),
+        range: pos_db.range(err.range())?,
+        message: err.display().to_string(),
         code: err.to_code(),
+        severity: Severity::Error,
+        tags: Vec::new(),
+        related: Vec::new(),
       })
     }))
-    .take(MAX_ERRORS_PER_PATH)
-    .collect()
+}
+
+/// applies the configured severity for the code of `error`, if any. returns `None` if errors with
+/// that code should not be reported.
+fn configured(mut error: Error, severities: &input::Severities) -> Option<Error> {
+  if let Some(&severity) = severities.get(&error.code) {
+    error.severity = severity?;
+  }
+  Some(error)
+}
+
+/// adds the variants whose constructors are at the head of `pat` to `covered`. returns whether
+/// `pat` matches anything, like a wildcard or variable.
+fn covers_all<'a>(
+  ars: &sml_hir::Arenas,
+  variants: &'a [(sml_hir::Name, bool)],
+  pat: sml_hir::PatIdx,
+  covered: &mut FxHashSet<&'a sml_hir::Name>,
+) -> bool {
+  let pat = match pat {
+    Some(x) => x,
+    None => return false,
+  };
+  match &ars.pat[pat] {
+    sml_hir::Pat::Wild => true,
+    sml_hir::Pat::SCon(_) | sml_hir::Pat::Record { .. } | sml_hir::Pat::Vector(_) => false,
+    sml_hir::Pat::Con(path, arg) => match variants.iter().find(|(name, _)| name == path.last()) {
+      Some((name, _)) => {
+        covered.insert(name);
+        false
+      }
+      // not a constructor of this type, so must be a variable.
+      None => arg.is_none() && path.structures().is_empty(),
+    },
+    sml_hir::Pat::Typed(pat, _) | sml_hir::Pat::As(_, pat) => {
+      covers_all(ars, variants, *pat, covered)
+    }
+    sml_hir::Pat::Or(or_pat) => std::iter::once(&or_pat.first)
+      .chain(or_pat.rest.iter())
+      .fold(false, |ac, &pat| {
+        covers_all(ars, variants, pat, covered) || ac
+      }),
+  }
 }
 
 struct CaseDisplay<'a> {
   needs_starting_bar: bool,
-  variants: &'a [(sml_hir::Name, bool)],
+  variants: &'a [(String, bool)],
 }
 
 impl fmt::Display for CaseDisplay<'_> {
@@ -302,7 +1496,7 @@ impl fmt::Display for CaseDisplay<'_> {
 }
 
 struct ArmDisplay<'a> {
-  name: &'a sml_hir::Name,
+  name: &'a str,
   has_arg: bool,
 }
 
@@ -0,0 +1,29 @@
+//! Detecting `handle`s that match only one exception constructor.
+
+/// Returns the indices of the patterns of `handle`s that have only one arm, whose pattern is an
+/// exception constructor, along with the name of that exception.
+pub(crate) fn get(file: &mlb_statics::SourceFile) -> Vec<(sml_hir::Idx, sml_hir::Name)> {
+  let arenas = &file.lowered.arenas;
+  arenas
+    .exp
+    .iter()
+    .filter_map(|(_, exp)| {
+      let pat = match exp {
+        sml_hir::Exp::Handle(_, arms) => match arms.as_slice() {
+          [(pat, _)] => (*pat)?,
+          _ => return None,
+        },
+        _ => return None,
+      };
+      let path = match &arenas.pat[pat] {
+        sml_hir::Pat::Con(path, _) => path,
+        _ => return None,
+      };
+      let is_exn = matches!(
+        file.info.get_symbol_kind(pat.into()),
+        Some(sml_statics::SymbolKind::Exception)
+      );
+      is_exn.then(|| (pat.into(), path.last().clone()))
+    })
+    .collect()
+}
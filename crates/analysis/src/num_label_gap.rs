@@ -0,0 +1,47 @@
+//! Detecting records whose numeric labels are one label away from making them a tuple.
+
+/// Returns the indices of records whose labels are all numeric, and are `1` through `n` except for
+/// exactly one missing label, along with that missing label.
+pub(crate) fn get(file: &mlb_statics::SourceFile) -> Vec<(sml_hir::Idx, usize)> {
+  let arenas = &file.lowered.arenas;
+  let exps = arenas.exp.iter().filter_map(|(idx, exp)| match exp {
+    sml_hir::Exp::Record(rows) => Some((idx.into(), missing_label(rows)?)),
+    _ => None,
+  });
+  let pats = arenas.pat.iter().filter_map(|(idx, pat)| match pat {
+    // a pattern with `...` may leave out labels on purpose.
+    sml_hir::Pat::Record {
+      rows,
+      allows_other: false,
+    } => Some((idx.into(), missing_label(rows)?)),
+    _ => None,
+  });
+  let tys = arenas.ty.iter().filter_map(|(idx, ty)| match ty {
+    sml_hir::Ty::Record(rows) => Some((idx.into(), missing_label(rows)?)),
+    _ => None,
+  });
+  exps.chain(pats).chain(tys).collect()
+}
+
+fn missing_label<T>(rows: &[(sml_hir::Lab, T)]) -> Option<usize> {
+  // with only one label, the record is never a tuple, so it's probably on purpose.
+  if rows.len() < 2 {
+    return None;
+  }
+  let mut labs = rows
+    .iter()
+    .map(|(lab, _)| match lab {
+      sml_hir::Lab::Num(n) => Some(*n),
+      sml_hir::Lab::Name(_) => None,
+    })
+    .collect::<Option<Vec<_>>>()?;
+  labs.sort_unstable();
+  if labs.first() != Some(&1) || labs.last() != Some(&(rows.len() + 1)) {
+    return None;
+  }
+  // since the labels are distinct, exactly one is missing.
+  labs
+    .iter()
+    .zip(1usize..)
+    .find_map(|(&lab, want)| (lab != want).then_some(want))
+}
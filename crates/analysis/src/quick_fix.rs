@@ -0,0 +1,40 @@
+//! Quick fixes for some errors.
+
+use sml_syntax::ast::{self, AstNode as _};
+use sml_syntax::{SyntaxKind, SyntaxNode};
+use text_size_util::TextRange;
+
+/// Returns the edit to fix the parse error with the given `code` and `range`, as the range to
+/// replace and the text to replace it with.
+pub(crate) fn get(root: &SyntaxNode, code: u16, range: TextRange) -> Option<(TextRange, String)> {
+  match code {
+    // non-infix name used as infix: declare the name infix before the declaration.
+    3001 => {
+      let tok = root.token_at_offset(range.start()).right_biased()?;
+      let dec = tok.parent()?.ancestors().find_map(ast::DecOne::cast)?;
+      let start = dec.syntax().text_range().start();
+      let new_text = match indent(dec.syntax()) {
+        Some(indent) => format!("infix {}\n{indent}", tok.text()),
+        None => format!("infix {} ", tok.text()),
+      };
+      Some((TextRange::empty(start), new_text))
+    }
+    // infix name used as non-infix without `op`: add the `op`.
+    3002 => Some((TextRange::empty(range.start()), "op ".to_owned())),
+    _ => None,
+  }
+}
+
+/// returns the indentation of `node`, if it is the first thing on its line.
+fn indent(node: &SyntaxNode) -> Option<String> {
+  match node.first_token()?.prev_token() {
+    None => Some(String::new()),
+    Some(tok) => {
+      if tok.kind() != SyntaxKind::Whitespace {
+        return None;
+      }
+      let (_, indent) = tok.text().rsplit_once('\n')?;
+      Some(indent.to_owned())
+    }
+  }
+}
@@ -0,0 +1,142 @@
+//! Classifying the names in a file, for semantic highlighting.
+
+use sml_statics::SymbolKind;
+use sml_syntax::ast::SyntaxNodePtr;
+use sml_syntax::{SyntaxKind as SK, SyntaxNode, SyntaxToken};
+use text_pos::Range;
+
+/// A classified token in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemanticToken {
+  /// The range of the token.
+  pub range: Range,
+  /// The kind of the token.
+  pub kind: SemanticTokenKind,
+}
+
+/// The kind of a semantic token.
+///
+/// Exception names are constructors (of the type `exn`), so they are classified as such.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum SemanticTokenKind {
+  Value,
+  Constructor,
+  Type,
+  TyVar,
+  Structure,
+  Signature,
+  Functor,
+}
+
+impl From<SymbolKind> for SemanticTokenKind {
+  fn from(val: SymbolKind) -> Self {
+    match val {
+      SymbolKind::Value => Self::Value,
+      SymbolKind::Constructor | SymbolKind::Exception => Self::Constructor,
+      SymbolKind::Type => Self::Type,
+      SymbolKind::TyVar => Self::TyVar,
+      SymbolKind::Structure => Self::Structure,
+      SymbolKind::Signature => Self::Signature,
+      SymbolKind::Functor => Self::Functor,
+    }
+  }
+}
+
+/// Returns the semantic tokens in the file, sorted by position.
+pub(crate) fn get(file: &mlb_statics::SourceFile) -> Vec<SemanticToken> {
+  // the tokens are visited in order, so the result is sorted.
+  file
+    .parsed
+    .root
+    .syntax()
+    .descendants_with_tokens()
+    .filter_map(|x| x.into_token())
+    .filter_map(|tok| {
+      let kind = get_kind(file, &tok)?;
+      let range = file.pos_db.range(tok.text_range())?;
+      Some(SemanticToken { range, kind })
+    })
+    .collect()
+}
+
+fn get_kind(file: &mlb_statics::SourceFile, tok: &SyntaxToken) -> Option<SemanticTokenKind> {
+  match tok.kind() {
+    SK::TyVar => return Some(SemanticTokenKind::TyVar),
+    SK::Name => {}
+    _ => return None,
+  }
+  let mut node = tok.parent()?;
+  if node.kind() == SK::NameStarEq {
+    node = node.parent()?;
+  }
+  let owner = match node.kind() {
+    SK::NameStarEqDot => {
+      // the `S` in `S.x`.
+      if node.children_with_tokens().any(|x| x.kind() == SK::Dot) {
+        return Some(SemanticTokenKind::Structure);
+      }
+      // NameStarEqDot -> Path -> the node containing the path.
+      node.parent()?.parent()?
+    }
+    _ => node,
+  };
+  resolved(file, &owner).or_else(|| lexical(owner.kind(), tok.text()))
+}
+
+/// returns the kind of the name in `owner` according to statics, if `owner` is a node whose HIR
+/// index has the kind of that name.
+fn resolved(file: &mlb_statics::SourceFile, owner: &SyntaxNode) -> Option<SemanticTokenKind> {
+  if !matches!(
+    owner.kind(),
+    SK::PathExp | SK::InfixExp | SK::ConPat | SK::InfixPat | SK::ConTy | SK::OneArgConTy
+  ) {
+    return None;
+  }
+  let mut idx = file.lowered.ptrs.ast_to_hir(SyntaxNodePtr::new(owner))?;
+  // `a + b` is lowered to `(op +) (a, b)`, and the whole application is what the node maps to.
+  if let (SK::InfixExp, sml_hir::Idx::Exp(exp)) = (owner.kind(), idx) {
+    if let sml_hir::Exp::App(func, _) = &file.lowered.arenas.exp[exp] {
+      idx = (*func)?.into();
+    }
+  }
+  file.info.get_symbol_kind(idx).map(Into::into)
+}
+
+/// returns the kind of the name `name` in `owner` from the syntax alone.
+fn lexical(owner: SK, name: &str) -> Option<SemanticTokenKind> {
+  let ret = match owner {
+    SK::PathExp | SK::InfixExp | SK::ConPat | SK::InfixPat => {
+      if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        SemanticTokenKind::Constructor
+      } else {
+        SemanticTokenKind::Value
+      }
+    }
+    SK::ValDesc
+    | SK::PrefixFunBindCaseHead
+    | SK::InfixFunBindCaseHead
+    | SK::TypedNamePat
+    | SK::AsPat
+    | SK::LabPatRow => SemanticTokenKind::Value,
+    SK::ConBind | SK::ExBind | SK::ExDesc | SK::EqPath => SemanticTokenKind::Constructor,
+    SK::TyBind
+    | SK::DatBind
+    | SK::TyDesc
+    | SK::DatCopyDec
+    | SK::DatCopySpec
+    | SK::ConTy
+    | SK::OneArgConTy
+    | SK::WhereTypeSigExp => SemanticTokenKind::Type,
+    SK::StrBind
+    | SK::StrDesc
+    | SK::FunctorArgNameSigExp
+    | SK::PathStrExp
+    | SK::OpenDec
+    | SK::WhereSigExp => SemanticTokenKind::Structure,
+    SK::SigBind | SK::NameSigExp => SemanticTokenKind::Signature,
+    SK::FunctorBind | SK::AppStrExp => SemanticTokenKind::Functor,
+    _ => return None,
+  };
+  Some(ret)
+}
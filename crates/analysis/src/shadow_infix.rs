@@ -0,0 +1,65 @@
+//! Detecting bindings that shadow std basis infix operators without declaring a fixity.
+
+use fast_hash::FxHashSet;
+use sml_parse::parser::{Assoc, STD_BASIS};
+use sml_syntax::ast::{self, AstNode as _};
+use sml_syntax::rowan::TextRange;
+
+/// Returns the ranges of value bindings whose names are std basis infix operators, along with the
+/// message for each. Names whose fixity is declared in this file are not reported.
+pub(crate) fn get(file: &mlb_statics::SourceFile) -> Vec<(TextRange, String)> {
+  let root = file.parsed.root.syntax();
+  let mut declared = FxHashSet::<String>::default();
+  for node in root.descendants() {
+    let names: Vec<_> = if let Some(dec) = ast::InfixDec::cast(node.clone()) {
+      dec.name_star_eqs().collect()
+    } else if let Some(dec) = ast::InfixrDec::cast(node.clone()) {
+      dec.name_star_eqs().collect()
+    } else if let Some(dec) = ast::NonfixDec::cast(node) {
+      dec.name_star_eqs().collect()
+    } else {
+      continue;
+    };
+    declared.extend(names.into_iter().map(|x| x.token.text().to_owned()));
+  }
+  file
+    .lowered
+    .arenas
+    .pat
+    .iter()
+    .filter_map(|(idx, pat)| {
+      let name = match pat {
+        sml_hir::Pat::Con(path, None) if path.structures().is_empty() => path.last(),
+        _ => return None,
+      };
+      let infix = STD_BASIS.get(name)?;
+      // only report if the name is still infix, and the user didn't say so explicitly.
+      if !file.fix_env.contains_key(name) || declared.contains(name.as_str()) {
+        return None;
+      }
+      if !matches!(
+        file.info.get_symbol_kind(idx.into()),
+        Some(sml_statics::SymbolKind::Value)
+      ) {
+        return None;
+      }
+      let node = file.lowered.ptrs.hir_to_ast(idx.into())?.to_node(root);
+      // the node may be larger than just the name, like for `fun` bindings.
+      let range = node
+        .descendants_with_tokens()
+        .filter_map(|x| x.into_token())
+        .find(|tok| tok.text() == name.as_str())
+        .map_or_else(|| node.text_range(), |tok| tok.text_range());
+      let kw = match infix.assoc {
+        Assoc::Left => "infix",
+        Assoc::Right => "infixr",
+      };
+      let msg = format!(
+        "`{name}` shadows an infix operator from the standard basis; consider declaring its fixity \
+         explicitly with `{kw} {} {name}` or `nonfix {name}`",
+        infix.prec
+      );
+      Some((range, msg))
+    })
+    .collect()
+}
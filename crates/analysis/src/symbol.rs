@@ -0,0 +1,369 @@
+//! Getting an outline of the symbols declared in a file.
+
+use sml_statics::SymbolKind;
+use sml_syntax::ast::{self, AstNode as _};
+use sml_syntax::SyntaxNode;
+use text_pos::{PositionDb, Range};
+
+/// A symbol declared in a file.
+#[derive(Debug)]
+pub struct Symbol {
+  /// The name of the symbol.
+  pub name: String,
+  /// The kind of the symbol.
+  pub kind: SymbolKind,
+  /// The range of the whole declaration of the symbol.
+  pub range: Range,
+  /// The symbols declared inside this one, like the declarations in a structure.
+  pub children: Vec<Symbol>,
+}
+
+pub(crate) fn get(pos_db: &PositionDb, root: &ast::Root) -> Vec<Symbol> {
+  let mut ret = Vec::new();
+  str_dec(&mut ret, pos_db, root.str_dec());
+  ret
+}
+
+fn push(
+  ac: &mut Vec<Symbol>,
+  pos_db: &PositionDb,
+  name: Option<sml_syntax::SyntaxToken>,
+  kind: SymbolKind,
+  node: &SyntaxNode,
+  children: Vec<Symbol>,
+) {
+  let (name, range) = match (name, pos_db.range(node.text_range())) {
+    (Some(name), Some(range)) => (name, range),
+    _ => return,
+  };
+  ac.push(Symbol {
+    name: name.text().to_owned(),
+    kind,
+    range,
+    children,
+  });
+}
+
+fn str_dec(ac: &mut Vec<Symbol>, pos_db: &PositionDb, str_dec: Option<ast::StrDec>) {
+  let str_dec = match str_dec {
+    Some(x) => x,
+    None => return,
+  };
+  for str_dec in str_dec.str_dec_in_seqs().filter_map(|x| x.str_dec_one()) {
+    match str_dec {
+      ast::StrDecOne::DecStrDec(str_dec) => dec_one(ac, pos_db, str_dec.dec_one()),
+      ast::StrDecOne::StructureStrDec(str_dec) => {
+        for str_bind in str_dec.str_binds() {
+          let mut children = Vec::new();
+          str_exp(&mut children, pos_db, str_bind.str_exp());
+          let name = str_bind.name();
+          let kind = SymbolKind::Structure;
+          push(ac, pos_db, name, kind, str_bind.syntax(), children);
+        }
+      }
+      ast::StrDecOne::LocalStrDec(str_dec) => {
+        self::str_dec(ac, pos_db, str_dec.local_dec());
+        self::str_dec(ac, pos_db, str_dec.in_dec());
+      }
+      ast::StrDecOne::SigDec(str_dec) => {
+        for sig_bind in str_dec.sig_binds() {
+          let mut children = Vec::new();
+          sig_exp(&mut children, pos_db, sig_bind.sig_exp());
+          let name = sig_bind.name();
+          let kind = SymbolKind::Signature;
+          push(ac, pos_db, name, kind, sig_bind.syntax(), children);
+        }
+      }
+      ast::StrDecOne::FunctorDec(str_dec) => {
+        for functor_bind in str_dec.functor_binds() {
+          let mut children = Vec::new();
+          str_exp(&mut children, pos_db, functor_bind.body());
+          let name = functor_bind.functor_name();
+          let kind = SymbolKind::Functor;
+          push(ac, pos_db, name, kind, functor_bind.syntax(), children);
+        }
+      }
+    }
+  }
+}
+
+fn str_exp(ac: &mut Vec<Symbol>, pos_db: &PositionDb, str_exp: Option<ast::StrExp>) {
+  match str_exp {
+    Some(ast::StrExp::StructStrExp(str_exp)) => str_dec(ac, pos_db, str_exp.str_dec()),
+    Some(ast::StrExp::AscriptionStrExp(str_exp)) => self::str_exp(ac, pos_db, str_exp.str_exp()),
+    Some(ast::StrExp::LetStrExp(str_exp)) => self::str_exp(ac, pos_db, str_exp.str_exp()),
+    Some(ast::StrExp::PathStrExp(_) | ast::StrExp::AppStrExp(_)) | None => {}
+  }
+}
+
+fn sig_exp(ac: &mut Vec<Symbol>, pos_db: &PositionDb, sig_exp: Option<ast::SigExp>) {
+  match sig_exp {
+    Some(ast::SigExp::SigSigExp(sig_exp)) => spec(ac, pos_db, sig_exp.spec()),
+    Some(ast::SigExp::WhereTypeSigExp(sig_exp)) => self::sig_exp(ac, pos_db, sig_exp.sig_exp()),
+    Some(ast::SigExp::WhereSigExp(sig_exp)) => self::sig_exp(ac, pos_db, sig_exp.sig_exp()),
+    Some(ast::SigExp::NameSigExp(_)) | None => {}
+  }
+}
+
+fn spec(ac: &mut Vec<Symbol>, pos_db: &PositionDb, spec: Option<ast::Spec>) {
+  let spec = match spec {
+    Some(x) => x,
+    None => return,
+  };
+  let specs = spec
+    .spec_with_tail_in_seqs()
+    .filter_map(|x| x.spec_with_tail())
+    .flat_map(|x| x.spec_in_seqs())
+    .filter_map(|x| x.spec_one());
+  for spec in specs {
+    match spec {
+      ast::SpecOne::ValSpec(spec) => {
+        for val_desc in spec.val_descs() {
+          let name = val_desc.name_star_eq().map(|x| x.token);
+          let kind = SymbolKind::Value;
+          push(ac, pos_db, name, kind, val_desc.syntax(), Vec::new());
+        }
+      }
+      ast::SpecOne::TySpec(spec) => ty_descs(ac, pos_db, spec.ty_descs()),
+      ast::SpecOne::EqTySpec(spec) => ty_descs(ac, pos_db, spec.ty_descs()),
+      ast::SpecOne::DatSpec(spec) => {
+        dat_binds(ac, pos_db, spec.dat_binds());
+        ty_binds(
+          ac,
+          pos_db,
+          spec.with_type().into_iter().flat_map(|x| x.ty_binds()),
+        );
+      }
+      ast::SpecOne::DatCopySpec(spec) => {
+        let name = spec.name();
+        push(
+          ac,
+          pos_db,
+          name,
+          SymbolKind::Type,
+          spec.syntax(),
+          Vec::new(),
+        );
+      }
+      ast::SpecOne::ExSpec(spec) => {
+        for ex_desc in spec.ex_descs() {
+          let name = ex_desc.name_star_eq().map(|x| x.token);
+          let kind = SymbolKind::Exception;
+          push(ac, pos_db, name, kind, ex_desc.syntax(), Vec::new());
+        }
+      }
+      ast::SpecOne::StrSpec(spec) => {
+        for str_desc in spec.str_descs() {
+          let mut children = Vec::new();
+          sig_exp(&mut children, pos_db, str_desc.sig_exp());
+          let name = str_desc.name();
+          let kind = SymbolKind::Structure;
+          push(ac, pos_db, name, kind, str_desc.syntax(), children);
+        }
+      }
+      ast::SpecOne::IncludeSpec(_) => {}
+    }
+  }
+}
+
+fn ty_descs<I>(ac: &mut Vec<Symbol>, pos_db: &PositionDb, iter: I)
+where
+  I: Iterator<Item = ast::TyDesc>,
+{
+  for ty_desc in iter {
+    let name = ty_desc.name();
+    push(
+      ac,
+      pos_db,
+      name,
+      SymbolKind::Type,
+      ty_desc.syntax(),
+      Vec::new(),
+    );
+  }
+}
+
+fn dec(ac: &mut Vec<Symbol>, pos_db: &PositionDb, dec: Option<ast::Dec>) {
+  let dec = match dec {
+    Some(x) => x,
+    None => return,
+  };
+  for dec in dec.dec_in_seqs() {
+    dec_one(ac, pos_db, dec.dec_one());
+  }
+}
+
+fn dec_one(ac: &mut Vec<Symbol>, pos_db: &PositionDb, dec: Option<ast::DecOne>) {
+  let dec = match dec {
+    Some(x) => x,
+    None => return,
+  };
+  match dec {
+    ast::DecOne::ValDec(dec) => {
+      for val_bind in dec.val_binds() {
+        let mut names = Vec::new();
+        pat(&mut names, val_bind.pat());
+        for name in names {
+          let kind = SymbolKind::Value;
+          push(ac, pos_db, Some(name), kind, val_bind.syntax(), Vec::new());
+        }
+      }
+    }
+    ast::DecOne::FunDec(dec) => {
+      for fun_bind in dec.fun_binds() {
+        // all the cases should have the same name, so just use the first one.
+        let name = fun_bind
+          .fun_bind_cases()
+          .find_map(|case| match case.fun_bind_case_head()? {
+            ast::FunBindCaseHead::PrefixFunBindCaseHead(head) => head.name_star_eq(),
+            ast::FunBindCaseHead::InfixFunBindCaseHead(head) => head.name_star_eq(),
+          })
+          .map(|x| x.token);
+        let kind = SymbolKind::Value;
+        push(ac, pos_db, name, kind, fun_bind.syntax(), Vec::new());
+      }
+    }
+    ast::DecOne::TyDec(dec) => ty_binds(ac, pos_db, dec.ty_binds()),
+    ast::DecOne::DatDec(dec) => {
+      dat_binds(ac, pos_db, dec.dat_binds());
+      ty_binds(
+        ac,
+        pos_db,
+        dec.with_type().into_iter().flat_map(|x| x.ty_binds()),
+      );
+    }
+    ast::DecOne::DatCopyDec(dec) => {
+      let name = dec.name();
+      push(ac, pos_db, name, SymbolKind::Type, dec.syntax(), Vec::new());
+    }
+    ast::DecOne::AbstypeDec(dec) => {
+      dat_binds(ac, pos_db, dec.dat_binds());
+      ty_binds(
+        ac,
+        pos_db,
+        dec.with_type().into_iter().flat_map(|x| x.ty_binds()),
+      );
+      self::dec(ac, pos_db, dec.dec());
+    }
+    ast::DecOne::ExDec(dec) => {
+      for ex_bind in dec.ex_binds() {
+        let name = ex_bind.name_star_eq().map(|x| x.token);
+        let kind = SymbolKind::Exception;
+        push(ac, pos_db, name, kind, ex_bind.syntax(), Vec::new());
+      }
+    }
+    ast::DecOne::LocalDec(dec) => {
+      self::dec(ac, pos_db, dec.local_dec());
+      self::dec(ac, pos_db, dec.in_dec());
+    }
+    ast::DecOne::HoleDec(_)
+    | ast::DecOne::OpenDec(_)
+    | ast::DecOne::InfixDec(_)
+    | ast::DecOne::InfixrDec(_)
+    | ast::DecOne::NonfixDec(_)
+    | ast::DecOne::DoDec(_) => {}
+  }
+}
+
+fn ty_binds<I>(ac: &mut Vec<Symbol>, pos_db: &PositionDb, iter: I)
+where
+  I: Iterator<Item = ast::TyBind>,
+{
+  for ty_bind in iter {
+    let name = ty_bind.name();
+    push(
+      ac,
+      pos_db,
+      name,
+      SymbolKind::Type,
+      ty_bind.syntax(),
+      Vec::new(),
+    );
+  }
+}
+
+fn dat_binds<I>(ac: &mut Vec<Symbol>, pos_db: &PositionDb, iter: I)
+where
+  I: Iterator<Item = ast::DatBind>,
+{
+  for dat_bind in iter {
+    let mut children = Vec::new();
+    for con_bind in dat_bind.con_binds() {
+      let name = con_bind.name_star_eq().map(|x| x.token);
+      let kind = SymbolKind::Constructor;
+      push(
+        &mut children,
+        pos_db,
+        name,
+        kind,
+        con_bind.syntax(),
+        Vec::new(),
+      );
+    }
+    let name = dat_bind.name();
+    push(
+      ac,
+      pos_db,
+      name,
+      SymbolKind::Type,
+      dat_bind.syntax(),
+      children,
+    );
+  }
+}
+
+/// collects the names that might be bound by the pat. these may include some names that are
+/// actually constructors, since we can't know that without statics.
+fn pat(ac: &mut Vec<sml_syntax::SyntaxToken>, pat: Option<ast::Pat>) {
+  let pat = match pat {
+    Some(x) => x,
+    None => return,
+  };
+  match pat {
+    ast::Pat::ConPat(pat) => match pat.pat() {
+      Some(arg) => self::pat(ac, Some(arg)),
+      None => {
+        let mut names = pat.path().into_iter().flat_map(|x| x.name_star_eq_dots());
+        if let (Some(name), None) = (names.next(), names.next()) {
+          ac.extend(name.name_star_eq().map(|x| x.token));
+        }
+      }
+    },
+    ast::Pat::RecordPat(pat) => {
+      for row in pat.pat_rows() {
+        match row.pat_row_inner() {
+          Some(ast::PatRowInner::LabAndPatPatRow(row)) => self::pat(ac, row.pat()),
+          Some(ast::PatRowInner::LabPatRow(row)) => {
+            ac.extend(row.name_star_eq().map(|x| x.token));
+            self::pat(ac, row.as_pat_tail().and_then(|x| x.pat()));
+          }
+          Some(ast::PatRowInner::RestPatRow(_)) | None => {}
+        }
+      }
+    }
+    ast::Pat::ParenPat(pat) => self::pat(ac, pat.pat()),
+    ast::Pat::TuplePat(pat) => {
+      for arg in pat.pat_args() {
+        self::pat(ac, arg.pat());
+      }
+    }
+    ast::Pat::ListPat(pat) => {
+      for arg in pat.pat_args() {
+        self::pat(ac, arg.pat());
+      }
+    }
+    ast::Pat::InfixPat(pat) => {
+      self::pat(ac, pat.lhs());
+      self::pat(ac, pat.rhs());
+    }
+    ast::Pat::TypedPat(pat) => self::pat(ac, pat.pat()),
+    ast::Pat::TypedNamePat(pat) => ac.extend(pat.name_star_eq().map(|x| x.token)),
+    ast::Pat::AsPat(pat) => {
+      ac.extend(pat.name_star_eq().map(|x| x.token));
+      self::pat(ac, pat.as_pat_tail().and_then(|x| x.pat()));
+    }
+    // every alternative must bind the same names.
+    ast::Pat::OrPat(pat) => self::pat(ac, pat.lhs()),
+    ast::Pat::WildcardPat(_) | ast::Pat::SConPat(_) | ast::Pat::VectorPat(_) => {}
+  }
+}
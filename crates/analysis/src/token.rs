@@ -0,0 +1,33 @@
+//! Lexing a string into classified tokens, without parsing.
+
+use sml_syntax::rowan::{TextRange, TextSize};
+use sml_syntax::SyntaxKind;
+use text_pos::Range;
+
+/// Returns the tokens of `s`, including trivia like whitespace and comments, with their ranges.
+///
+/// This never fails. Comments and string literals that are not closed before the end of `s` are
+/// [`SyntaxKind::Invalid`], as are characters that cannot start any token.
+pub fn tokens(s: &str) -> Vec<(SyntaxKind, Range)> {
+  let lexed = sml_lex::get(s);
+  let pos_db = text_pos::PositionDb::new(s);
+  let mut start = TextSize::default();
+  lexed
+    .tokens
+    .iter()
+    .filter_map(|tok| {
+      let range = TextRange::at(start, TextSize::of(tok.text));
+      start = range.end();
+      let unclosed = lexed
+        .errors
+        .iter()
+        .any(|e| e.is_unclosed() && e.range() == range);
+      let kind = if unclosed {
+        SyntaxKind::Invalid
+      } else {
+        tok.kind
+      };
+      Some((kind, pos_db.range(range)?))
+    })
+    .collect()
+}
@@ -0,0 +1,123 @@
+//! Converting between tuples and records with numeric labels.
+//!
+//! `(a, b)` is sugar for `{1 = a, 2 = b}`, and similarly for patterns and types.
+
+use sml_syntax::ast::{self, AstNode};
+use sml_syntax::{SyntaxKind, SyntaxNode, SyntaxToken};
+use text_size_util::TextRange;
+
+/// Returns the range of the innermost tuple containing `tok`, and the text of the equivalent
+/// record.
+pub(crate) fn tuple_to_record(tok: &SyntaxToken) -> Option<(TextRange, String)> {
+  tok.parent()?.ancestors().find_map(|node| {
+    let new_text = tuple_to_record_text(&node)?;
+    Some((node.text_range(), new_text))
+  })
+}
+
+/// Returns the range of the innermost record containing `tok` that could be a tuple, and the text
+/// of the equivalent tuple.
+pub(crate) fn record_to_tuple(tok: &SyntaxToken) -> Option<(TextRange, String)> {
+  tok.parent()?.ancestors().find_map(|node| {
+    let new_text = record_to_tuple_text(&node)?;
+    Some((node.text_range(), new_text))
+  })
+}
+
+fn tuple_to_record_text(node: &SyntaxNode) -> Option<String> {
+  if let Some(exp) = ast::TupleExp::cast(node.clone()) {
+    record_text(exp.exp_args().map(|x| x.exp()), "=")
+  } else if let Some(pat) = ast::TuplePat::cast(node.clone()) {
+    record_text(pat.pat_args().map(|x| x.pat()), "=")
+  } else if let Some(ty) = ast::TupleTy::cast(node.clone()) {
+    let tys = std::iter::once(ty.ty()).chain(ty.star_tys().map(|x| x.ty()));
+    record_text(tys, ":")
+  } else {
+    None
+  }
+}
+
+fn record_to_tuple_text(node: &SyntaxNode) -> Option<String> {
+  if let Some(exp) = ast::RecordExp::cast(node.clone()) {
+    let exps = tuple_elems(
+      exp
+        .exp_rows()
+        .map(|row| (row.lab(), row.eq_exp().and_then(|x| x.exp()))),
+    )?;
+    let exps: Vec<_> = exps.iter().map(|x| x.syntax().to_string()).collect();
+    Some(format!("({})", exps.join(", ")))
+  } else if let Some(pat) = ast::RecordPat::cast(node.clone()) {
+    let pats = tuple_elems(pat.pat_rows().map(|row| match row.pat_row_inner() {
+      Some(ast::PatRowInner::LabAndPatPatRow(row)) => (row.lab(), row.pat()),
+      _ => (None, None),
+    }))?;
+    let pats: Vec<_> = pats.iter().map(|x| x.syntax().to_string()).collect();
+    Some(format!("({})", pats.join(", ")))
+  } else if let Some(ty) = ast::RecordTy::cast(node.clone()) {
+    let tys = tuple_elems(ty.ty_rows().map(|row| (row.lab(), row.ty())))?;
+    let tys: Vec<_> = tys
+      .iter()
+      .map(|ty| match ty {
+        ast::Ty::FnTy(_) | ast::Ty::TupleTy(_) => format!("({})", ty.syntax()),
+        _ => ty.syntax().to_string(),
+      })
+      .collect();
+    let text = tys.join(" * ");
+    Some(if ty_needs_parens(node) {
+      format!("({text})")
+    } else {
+      text
+    })
+  } else {
+    None
+  }
+}
+
+/// returns whether a tuple type replacing the type `node` must be in parentheses to keep the same
+/// grouping.
+fn ty_needs_parens(node: &SyntaxNode) -> bool {
+  let parent = match node.parent() {
+    Some(x) => x,
+    None => return false,
+  };
+  match parent.kind() {
+    SyntaxKind::OneArgConTy | SyntaxKind::TupleTy | SyntaxKind::StarTy => true,
+    SyntaxKind::FnTy => ast::FnTy::cast(parent)
+      .and_then(|x| x.param())
+      .map_or(false, |x| x.syntax() == node),
+    _ => false,
+  }
+}
+
+/// returns e.g. `{1 = a, 2 = b}` for `a`, `b`, if there are at least 2 elements.
+fn record_text<I, N>(iter: I, sep: &str) -> Option<String>
+where
+  I: Iterator<Item = Option<N>>,
+  N: AstNode,
+{
+  let rows = iter
+    .enumerate()
+    .map(|(idx, x)| Some(format!("{} {sep} {}", idx + 1, x?.syntax())))
+    .collect::<Option<Vec<_>>>()?;
+  (rows.len() >= 2).then(|| format!("{{{}}}", rows.join(", ")))
+}
+
+/// returns the elements if the labels are exactly `1` through `n` in order, for some `n >= 2`.
+///
+/// the order matters, since the elements are evaluated in the order they are written.
+fn tuple_elems<I, N>(iter: I) -> Option<Vec<N>>
+where
+  I: Iterator<Item = (Option<ast::Lab>, Option<N>)>,
+{
+  let mut ret = Vec::<N>::new();
+  for (idx, (lab, elem)) in iter.enumerate() {
+    let lab = lab?;
+    if !matches!(lab.kind, ast::LabKind::IntLit)
+      || lab.token.text().parse::<usize>().ok()? != idx + 1
+    {
+      return None;
+    }
+    ret.push(elem?);
+  }
+  (ret.len() >= 2).then_some(ret)
+}
@@ -1,3 +1,8 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
 fn usage() {
   let current_exe_name = std::env::current_exe()
     .ok()
@@ -5,10 +10,34 @@ fn usage() {
     .unwrap_or_else(|| "<unknown>".to_owned());
   println!("usage:");
   println!("  {current_exe_name} [options] <path>");
+  println!("  {current_exe_name} files <path>");
+  println!("  {current_exe_name} lsp");
   let rest_of_usage = r#"
+commands:
+  files
+    instead of analyzing, print the path of every source file reachable from the root group, one
+    per line, in the order they are analyzed
+  lsp
+    instead of analyzing, run the language server, communicating with the client over stdin and
+    stdout
+
 options:
+  --deny-warnings
+    exit with a nonzero status if there are any warnings, not just if there are any errors
   -h, --help
     show this help
+  --no-std-basis
+    only have the primitive types, constructors, and operators in scope, like `int`, `::`, and `+`,
+    not the rest of the standard basis, like `List` and `print`. useful for courses where students
+    define those themselves
+  --output <format>
+    how to print diagnostics. <format> is one of:
+    - human (the default): one diagnostic per line
+    - json: one JSON array of all diagnostics, each an object with path, range, message,
+      severity, and code
+  --watch
+    after analyzing, watch the project for changes to source files, group files, and the config
+    file, and re-analyze when they change. runs until interrupted
 
 arguments:
   <path>
@@ -17,72 +46,385 @@ arguments:
       - a single .cm or .mlb file
       - a millet.toml config file specifying a single .cm or .mlb file
     - a .cm or .mlb file
+
+exit status:
+  0 if there were no errors (and no warnings, with --deny-warnings), else 1
 "#;
   print!("{rest_of_usage}");
 }
 
-fn run() -> usize {
+/// How to print diagnostics.
+#[derive(Debug, Clone, Copy)]
+enum Output {
+  Human,
+  Json,
+}
+
+/// A diagnostic, as reported to the user.
+#[derive(Debug, Serialize)]
+struct Diagnostic {
+  path: String,
+  #[serde(serialize_with = "serialize_range")]
+  range: Option<analysis::Range>,
+  message: String,
+  severity: &'static str,
+  code: u16,
+}
+
+/// A 1-based range.
+#[derive(Debug, Serialize)]
+struct DiagnosticRange {
+  start: DiagnosticPosition,
+  end: DiagnosticPosition,
+}
+
+/// A 1-based position.
+#[derive(Debug, Serialize)]
+struct DiagnosticPosition {
+  line: u32,
+  col: u32,
+}
+
+impl From<analysis::Position> for DiagnosticPosition {
+  fn from(pos: analysis::Position) -> Self {
+    Self {
+      line: pos.line + 1,
+      col: pos.character + 1,
+    }
+  }
+}
+
+impl From<analysis::Range> for DiagnosticRange {
+  fn from(range: analysis::Range) -> Self {
+    Self {
+      start: range.start.into(),
+      end: range.end.into(),
+    }
+  }
+}
+
+fn serialize_range<S>(range: &Option<analysis::Range>, serializer: S) -> Result<S::Ok, S::Error>
+where
+  S: serde::Serializer,
+{
+  range.map(DiagnosticRange::from).serialize(serializer)
+}
+
+/// Counts of the diagnostics from one analysis.
+#[derive(Debug, Default)]
+struct Summary {
+  errors: usize,
+  warnings: usize,
+  files: usize,
+}
+
+impl Summary {
+  fn new(diagnostics: &[Diagnostic]) -> Self {
+    let mut ret = Self::default();
+    let mut files = BTreeSet::<&str>::new();
+    for d in diagnostics {
+      match d.severity {
+        "error" => ret.errors += 1,
+        "warning" => ret.warnings += 1,
+        _ => continue,
+      }
+      files.insert(d.path.as_str());
+    }
+    ret.files = files.len();
+    ret
+  }
+
+  fn is_empty(&self) -> bool {
+    self.errors == 0 && self.warnings == 0
+  }
+
+  /// Returns whether the analysis should be considered successful.
+  fn ok(&self, deny_warnings: bool) -> bool {
+    self.errors == 0 && (!deny_warnings || self.warnings == 0)
+  }
+}
+
+/// Prints the diagnostics, then returns a summary of them.
+fn report(output: Output, diagnostics: Vec<Diagnostic>) -> Summary {
+  match output {
+    Output::Human => {
+      for d in &diagnostics {
+        print!("{}", d.path);
+        if let Some(r) = &d.range {
+          print!(":{}", r.start);
+        }
+        println!(": {}[{}]: {}", d.severity, d.code, d.message);
+      }
+    }
+    Output::Json => match serde_json::to_string(&diagnostics) {
+      Ok(s) => println!("{s}"),
+      Err(e) => println!("error[1997]: couldn't serialize diagnostics: {e}"),
+    },
+  }
+  Summary::new(&diagnostics)
+}
+
+/// Runs the CLI, then returns whether it was successful.
+fn run() -> bool {
   let mut args = pico_args::Arguments::from_env();
   if args.contains(["-h", "--help"]) {
     usage();
-    return 0;
+    return true;
   }
-  let path: String = match args.free_from_str() {
-    Ok(x) => x,
+  let watch = args.contains("--watch");
+  let no_std_basis = args.contains("--no-std-basis");
+  let deny_warnings = args.contains("--deny-warnings");
+  let output = match args.opt_value_from_str::<_, String>("--output") {
+    Ok(None) => Output::Human,
+    Ok(Some(x)) => match x.as_str() {
+      "human" => Output::Human,
+      "json" => Output::Json,
+      _ => {
+        println!("error[1997]: unknown output format: {x}");
+        return false;
+      }
+    },
     Err(e) => {
       println!("error[1997]: {e}");
-      return 1;
+      return false;
     }
   };
-  let fs = paths::RealFileSystem::default();
-  let mut root = match analysis::input::get_root(&fs, std::path::Path::new(path.as_str())) {
+  let mut path: String = match args.free_from_str() {
     Ok(x) => x,
     Err(e) => {
-      handle_get_input_error(e);
-      return 1;
+      println!("error[1997]: {e}");
+      return false;
     }
   };
-  let inp = match analysis::input::get(&fs, &mut root) {
-    Ok(x) => x,
-    Err(e) => {
-      handle_get_input_error(e);
-      return 1;
+  if path == "lsp" {
+    return match lang_srv::run_stdio() {
+      Ok(()) => true,
+      Err(e) => {
+        // stdout is the protocol channel.
+        eprintln!("error[1997]: {e}");
+        false
+      }
+    };
+  }
+  let files = path == "files";
+  if files {
+    path = match args.free_from_str() {
+      Ok(x) => x,
+      Err(e) => {
+        println!("error[1997]: {e}");
+        return false;
+      }
+    };
+  }
+  let fs = paths::RealFileSystem::default();
+  let path = Path::new(path.as_str());
+  if files {
+    let (root, inp) = match get_input(&fs, path) {
+      Ok(x) => x,
+      Err(d) => {
+        report(output, vec![d]);
+        return false;
+      }
+    };
+    for path in inp.ordered_sources() {
+      println!("{}", root.as_paths().get_rel_path(path).display());
     }
+    return true;
+  }
+  let std_basis = if no_std_basis {
+    analysis::StdBasis::minimal()
+  } else {
+    analysis::StdBasis::full()
+  };
+  let mut an = analysis::Analysis::new(std_basis, config::ErrorLines::One);
+  if watch {
+    run_watch(&fs, path, output, &mut an)
+  }
+  let summary = analyze(&fs, path, output, &mut an);
+  if let Output::Human = output {
+    print_summary(&summary);
+  }
+  summary.ok(deny_warnings)
+}
+
+fn get_input(
+  fs: &paths::RealFileSystem,
+  path: &Path,
+) -> Result<(analysis::input::Root, analysis::input::Input), Diagnostic> {
+  let mut root = analysis::input::get_root(fs, path).map_err(get_input_error_diagnostic)?;
+  let inp = analysis::input::get(fs, &mut root).map_err(get_input_error_diagnostic)?;
+  Ok((root, inp))
+}
+
+/// Analyzes the project at `path`, prints the diagnostics, then returns a summary of them.
+///
+/// If the input couldn't be gotten, that is reported as the only diagnostic, an error.
+fn analyze(
+  fs: &paths::RealFileSystem,
+  path: &Path,
+  output: Output,
+  an: &mut analysis::Analysis,
+) -> Summary {
+  let (root, inp) = match get_input(fs, path) {
+    Ok(x) => x,
+    Err(d) => return report(output, vec![d]),
   };
-  let mut an = analysis::Analysis::new(analysis::StdBasis::full(), config::ErrorLines::One);
   let got = an.get_many(&inp);
-  let num_errors: usize = got.iter().map(|(_, errors)| errors.len()).sum();
-  for (path, errors) in got {
-    for e in errors {
-      let path = root.as_paths().get_rel_path(path).display();
-      println!(
-        "{}:{}: error[{}]: {}",
-        path, e.range.start, e.code, e.message
-      );
+  let diagnostics: Vec<_> = got
+    .into_iter()
+    .flat_map(|(path, errors)| {
+      let path = root.as_paths().get_rel_path(path).display().to_string();
+      errors.into_iter().map(move |e| Diagnostic {
+        path: path.clone(),
+        range: Some(e.range),
+        message: e.message,
+        severity: severity_str(e.severity),
+        code: e.code,
+      })
+    })
+    .collect();
+  report(output, diagnostics)
+}
+
+/// How long to wait between checking for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long there must be no further changes after a change before re-analyzing. This prevents
+/// rapid successive saves from triggering many analyses.
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Analyzes the project at `path`, then re-analyzes it whenever files in it change, forever.
+///
+/// Each analysis prints all diagnostics anew, so the diagnostics for a deleted file are not
+/// printed after it is deleted. Errors getting the input are reported like any other diagnostic,
+/// and do not stop the watching.
+fn run_watch(
+  fs: &paths::RealFileSystem,
+  path: &Path,
+  output: Output,
+  an: &mut analysis::Analysis,
+) -> ! {
+  let dir = if path.is_dir() {
+    path
+  } else {
+    match path.parent() {
+      Some(x) if !x.as_os_str().is_empty() => x,
+      _ => Path::new("."),
+    }
+  };
+  let mut snapshot = get_snapshot(dir);
+  loop {
+    let summary = analyze(fs, path, output, an);
+    if let Output::Human = output {
+      print_summary(&summary);
+      println!("watching for changes...");
     }
+    loop {
+      std::thread::sleep(POLL_INTERVAL);
+      let new = get_snapshot(dir);
+      if new != snapshot {
+        snapshot = new;
+        break;
+      }
+    }
+    loop {
+      std::thread::sleep(DEBOUNCE_INTERVAL);
+      let new = get_snapshot(dir);
+      if new == snapshot {
+        break;
+      }
+      snapshot = new;
+    }
+  }
+}
+
+/// A map from file paths to when they were last modified.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Returns a snapshot of the files in `dir` that are relevant to analysis, recursively. Ignores
+/// hidden files and directories, and files that couldn't be read.
+fn get_snapshot(dir: &Path) -> Snapshot {
+  let mut ret = Snapshot::new();
+  let mut stack = vec![dir.to_owned()];
+  while let Some(dir) = stack.pop() {
+    let entries = match std::fs::read_dir(&dir) {
+      Ok(x) => x,
+      Err(_) => continue,
+    };
+    for entry in entries.flatten() {
+      let path = entry.path();
+      let hidden = path
+        .file_name()
+        .and_then(|x| x.to_str())
+        .map_or(true, |x| x.starts_with('.'));
+      if hidden {
+        continue;
+      }
+      let md = match entry.metadata() {
+        Ok(x) => x,
+        Err(_) => continue,
+      };
+      if md.is_dir() {
+        stack.push(path);
+      } else if is_relevant(path.as_path()) {
+        if let Ok(modified) = md.modified() {
+          ret.insert(path, modified);
+        }
+      }
+    }
+  }
+  ret
+}
+
+fn is_relevant(path: &Path) -> bool {
+  if path.file_name().and_then(|x| x.to_str()) == Some(config::FILE_NAME) {
+    return true;
   }
-  num_errors
+  matches!(
+    path.extension().and_then(|x| x.to_str()),
+    Some("sml" | "sig" | "fun" | "cm" | "mlb")
+  )
 }
 
-fn handle_get_input_error(e: analysis::input::GetInputError) {
-  print!("{}", e.path().display());
-  if let Some(r) = e.range() {
-    print!(":{}", r.start);
+fn get_input_error_diagnostic(e: analysis::input::GetInputError) -> Diagnostic {
+  Diagnostic {
+    path: e.path().display().to_string(),
+    range: e.range(),
+    message: e.to_string(),
+    severity: "error",
+    code: e.to_code(),
   }
-  let code = e.to_code();
-  println!(": error[{code}]: {e}");
+}
+
+fn severity_str(severity: config::Severity) -> &'static str {
+  match severity {
+    config::Severity::Error => "error",
+    config::Severity::Warning => "warning",
+    config::Severity::Info => "info",
+  }
+}
+
+fn print_summary(summary: &Summary) {
+  if summary.is_empty() {
+    println!("no errors or warnings");
+    return;
+  }
+  println!(
+    "{}, {} across {}. see {} for more information",
+    plural(summary.errors, "error"),
+    plural(summary.warnings, "warning"),
+    plural(summary.files, "file"),
+    analysis::ERRORS_URL
+  );
+}
+
+fn plural(n: usize, noun: &str) -> String {
+  let suffix = if n == 1 { "" } else { "s" };
+  format!("{n} {noun}{suffix}")
 }
 
 fn main() {
-  match run() {
-    0 => {}
-    n => {
-      let suffix = if n == 1 { "" } else { "s" };
-      println!(
-        "{n} error{suffix}. see {} for more information",
-        analysis::ERRORS_URL
-      );
-      std::process::exit(1)
-    }
+  if !run() {
+    std::process::exit(1)
   }
 }
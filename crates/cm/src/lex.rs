@@ -28,7 +28,7 @@ fn token<'s>(idx: &mut usize, b: u8, bs: &'s [u8]) -> Result<Option<Token<'s>>>
   match block_comment::get(idx, b, bs) {
     Ok(Some(block_comment::Consumed)) => return Ok(None),
     Ok(None) => {}
-    Err(block_comment::UnclosedError) => {
+    Err(block_comment::UnclosedError { start }) => {
       return Err(Error::new(
         ErrorKind::UnclosedComment,
         TextRange::new(mk_text_size(start), mk_text_size(*idx)),
@@ -16,16 +16,33 @@ pub struct Root {
   pub version: u16,
   /// The workspace config.
   pub workspace: Option<Workspace>,
+  /// The language config.
+  pub language: Option<Language>,
 }
 
 /// The workspace config.
 #[derive(Debug, Deserialize)]
 pub struct Workspace {
-  /// The root group filename.
-  pub root: Option<SmolStr>,
+  /// The root group filename, or filenames if there are many.
+  pub root: Option<RootGroups>,
   /// Path vars, for expansion in MLB/CM paths.
   #[serde(rename = "path-vars")]
   pub path_vars: Option<FxHashMap<SmolStr, PathVar>>,
+  /// The max number of errors to report per file. 0 means no limit.
+  #[serde(rename = "max-errors-per-file")]
+  pub max_errors_per_file: Option<usize>,
+  /// How to report errors with certain codes, keyed by the code.
+  pub diagnostics: Option<FxHashMap<SmolStr, DiagnosticSeverity>>,
+}
+
+/// The root group filenames.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum RootGroups {
+  /// One root group.
+  One(SmolStr),
+  /// Many root groups, each analyzed independently of the others.
+  Many(Vec<SmolStr>),
 }
 
 /// A path var setting.
@@ -39,6 +56,59 @@ pub enum PathVar {
   Path(SmolStr),
 }
 
+/// How to report errors with a certain code.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum DiagnosticSeverity {
+  /// Report as an error.
+  #[serde(rename = "error")]
+  Error,
+  /// Report as a warning.
+  #[serde(rename = "warning")]
+  Warning,
+  /// Do not report.
+  #[serde(rename = "ignore")]
+  Ignore,
+}
+
+/// The language config.
+#[derive(Debug, Deserialize)]
+pub struct Language {
+  /// Fully-qualified names of std basis values whose uses should be reported as deprecated.
+  pub deprecated: Option<Vec<SmolStr>>,
+  /// Whether to report integer division or modulo by a literal zero.
+  #[serde(rename = "div-by-zero")]
+  pub div_by_zero: Option<bool>,
+  /// Whether to report records whose numeric labels are one label away from making them a tuple.
+  #[serde(rename = "num-label-gap")]
+  pub num_label_gap: Option<bool>,
+  /// Whether to report bindings that shadow std basis infix operators without declaring a fixity.
+  #[serde(rename = "shadow-infix")]
+  pub shadow_infix: Option<bool>,
+  /// Whether to report `handle`s that match only one exception constructor.
+  #[serde(rename = "narrow-handle")]
+  pub narrow_handle: Option<bool>,
+  /// Extensions to the language beyond what the Definition allows.
+  pub extensions: Option<Extensions>,
+}
+
+/// The language extensions config.
+#[derive(Debug, Deserialize)]
+pub struct Extensions {
+  /// Whether to allow vector expressions and patterns, like `#[1, 2]`.
+  pub vectors: Option<bool>,
+}
+
+/// How severe an error is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+  /// An error, which should be fixed.
+  Error,
+  /// A warning, which indicates code that is allowed but is likely a mistake.
+  Warning,
+  /// Extra information.
+  Info,
+}
+
 /// How many lines an error message may have.
 #[derive(Debug, Clone, Copy)]
 pub enum ErrorLines {
@@ -193,28 +193,42 @@ impl State {
       let path = url_to_path_id(&self.file_system, root, &url)?;
       let range = analysis_range(params.range);
       let mut actions = Vec::<lsp_types::CodeActionOrCommand>::new();
-      if let Some((range, new_text)) = self.analysis.fill_case(path.wrap(range.start)) {
-        actions.push(lsp_types::CodeActionOrCommand::CodeAction(
-          lsp_types::CodeAction {
-            title: "Fill case".to_owned(),
-            kind: Some(lsp_types::CodeActionKind::QUICKFIX),
-            edit: Some(lsp_types::WorkspaceEdit {
-              document_changes: Some(lsp_types::DocumentChanges::Edits(vec![
-                lsp_types::TextDocumentEdit {
-                  text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
-                    uri: url,
-                    version: None,
-                  },
-                  edits: vec![lsp_types::OneOf::Left(lsp_types::TextEdit {
-                    range: lsp_range(range),
-                    new_text,
-                  })],
-                },
-              ])),
-              ..Default::default()
-            }),
-            ..Default::default()
-          },
+      let pos = path.wrap(range.start);
+      if let Some((range, new_text)) = self.analysis.fill_case(pos) {
+        actions.push(code_action(
+          url.clone(),
+          "Fill case",
+          lsp_types::CodeActionKind::QUICKFIX,
+          range,
+          new_text,
+        ));
+      }
+      for (range, new_text) in self.analysis.quick_fixes(pos) {
+        let title = format!("Insert `{}`", new_text.trim());
+        actions.push(code_action(
+          url.clone(),
+          title.as_str(),
+          lsp_types::CodeActionKind::QUICKFIX,
+          range,
+          new_text,
+        ));
+      }
+      if let Some((range, new_text)) = self.analysis.tuple_to_record(pos) {
+        actions.push(code_action(
+          url.clone(),
+          "Convert tuple to record",
+          lsp_types::CodeActionKind::REFACTOR_REWRITE,
+          range,
+          new_text,
+        ));
+      }
+      if let Some((range, new_text)) = self.analysis.record_to_tuple(pos) {
+        actions.push(code_action(
+          url,
+          "Convert record to tuple",
+          lsp_types::CodeActionKind::REFACTOR_REWRITE,
+          range,
+          new_text,
         ));
       }
       self.send_response(Response::new_ok(id, actions));
@@ -308,7 +322,16 @@ impl State {
           match file_url(e.path()) {
             Ok(url) => {
               root.has_diagnostics.insert(url.clone());
-              self.send_diagnostics(url, vec![diagnostic(e.to_string(), e.range(), e.to_code())]);
+              self.send_diagnostics(
+                url,
+                vec![diagnostic(
+                  e.to_string(),
+                  e.range(),
+                  e.to_code(),
+                  config::Severity::Error,
+                  Vec::new(),
+                )],
+              );
               true
             }
             Err(_) => false,
@@ -333,7 +356,7 @@ impl State {
           continue;
         }
       };
-      let ds = diagnostics(errors);
+      let ds = diagnostics(&url, errors);
       if ds.is_empty() || has_diagnostics.len() >= MAX_FILES_WITH_ERRORS {
         continue;
       }
@@ -356,7 +379,8 @@ impl State {
   }
 
   fn publish_diagnostics_one(&mut self, url: Url, text: &str) {
-    self.send_diagnostics(url, diagnostics(self.analysis.get_one(text)));
+    let ds = diagnostics(&url, self.analysis.get_one(text));
+    self.send_diagnostics(url, ds);
   }
 
   fn send_diagnostics(&mut self, url: Url, diagnostics: Vec<lsp_types::Diagnostic>) {
@@ -421,29 +445,73 @@ fn file_url(path: &std::path::Path) -> Result<Url> {
   Url::parse(&format!("file://{}", path.display())).with_context(|| "couldn't parse URL")
 }
 
-fn diagnostics(errors: Vec<analysis::Error>) -> Vec<lsp_types::Diagnostic> {
+fn diagnostics(url: &Url, errors: Vec<analysis::Error>) -> Vec<lsp_types::Diagnostic> {
   errors
     .into_iter()
-    .map(|err| diagnostic(err.message, Some(err.range), err.code))
+    .map(|err| {
+      let mut ret = diagnostic(
+        err.message,
+        Some(err.range),
+        err.code,
+        err.severity,
+        err.tags,
+      );
+      if !err.related.is_empty() {
+        let related = err
+          .related
+          .into_iter()
+          .map(|(range, message)| lsp_types::DiagnosticRelatedInformation {
+            location: lsp_types::Location {
+              uri: url.clone(),
+              range: lsp_range(range),
+            },
+            message,
+          })
+          .collect();
+        ret.related_information = Some(related);
+      }
+      ret
+    })
     .collect()
 }
 
-fn diagnostic(message: String, range: Option<analysis::Range>, code: u16) -> lsp_types::Diagnostic {
+fn diagnostic(
+  message: String,
+  range: Option<analysis::Range>,
+  code: u16,
+  severity: config::Severity,
+  tags: Vec<analysis::DiagnosticTag>,
+) -> lsp_types::Diagnostic {
   let href =
     Url::parse(&format!("{}#{}", analysis::ERRORS_URL, code)).expect("couldn't parse error URL");
   lsp_types::Diagnostic {
     range: range.map(lsp_range).unwrap_or_default(),
-    severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+    severity: Some(lsp_severity(severity)),
     code: Some(lsp_types::NumberOrString::Number(code.into())),
     code_description: Some(lsp_types::CodeDescription { href }),
     source: Some("Millet".to_owned()),
     message,
     related_information: None,
-    tags: None,
+    tags: (!tags.is_empty()).then(|| tags.into_iter().map(lsp_diagnostic_tag).collect()),
     data: None,
   }
 }
 
+fn lsp_severity(severity: config::Severity) -> lsp_types::DiagnosticSeverity {
+  match severity {
+    config::Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+    config::Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+    config::Severity::Info => lsp_types::DiagnosticSeverity::INFORMATION,
+  }
+}
+
+fn lsp_diagnostic_tag(tag: analysis::DiagnosticTag) -> lsp_types::DiagnosticTag {
+  match tag {
+    analysis::DiagnosticTag::Unnecessary => lsp_types::DiagnosticTag::UNNECESSARY,
+    analysis::DiagnosticTag::Deprecated => lsp_types::DiagnosticTag::DEPRECATED,
+  }
+}
+
 fn lsp_range(range: analysis::Range) -> lsp_types::Range {
   lsp_types::Range {
     start: lsp_position(range.start),
@@ -512,3 +580,29 @@ where
   let pos = analysis_position(params.position);
   Ok(path.wrap(pos))
 }
+
+fn code_action(
+  uri: Url,
+  title: &str,
+  kind: lsp_types::CodeActionKind,
+  range: analysis::Range,
+  new_text: String,
+) -> lsp_types::CodeActionOrCommand {
+  lsp_types::CodeActionOrCommand::CodeAction(lsp_types::CodeAction {
+    title: title.to_owned(),
+    kind: Some(kind),
+    edit: Some(lsp_types::WorkspaceEdit {
+      document_changes: Some(lsp_types::DocumentChanges::Edits(vec![
+        lsp_types::TextDocumentEdit {
+          text_document: lsp_types::OptionalVersionedTextDocumentIdentifier { uri, version: None },
+          edits: vec![lsp_types::OneOf::Left(lsp_types::TextEdit {
+            range: lsp_range(range),
+            new_text,
+          })],
+        },
+      ])),
+      ..Default::default()
+    }),
+    ..Default::default()
+  })
+}
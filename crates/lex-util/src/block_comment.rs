@@ -6,12 +6,19 @@ pub struct Consumed;
 
 /// An error for an unclosed comment.
 #[derive(Debug)]
-pub struct UnclosedError;
+pub struct UnclosedError {
+  /// The byte offset of the start of the outermost unclosed comment.
+  pub start: usize,
+}
 
 /// Requires `bs.get(*idx) == Some(&b)`.
+///
+/// If the comment is unclosed, all of the rest of `bs` is consumed.
 pub fn get(idx: &mut usize, b: u8, bs: &[u8]) -> Result<Option<Consumed>, UnclosedError> {
   debug_assert_eq!(bs.get(*idx), Some(&b));
   if b == b'(' && bs.get(*idx + 1) == Some(&b'*') {
+    // the outermost comment is the first one, since we return once it is closed.
+    let start = *idx;
     *idx += 2;
     let mut level = 1_usize;
     loop {
@@ -28,10 +35,26 @@ pub fn get(idx: &mut usize, b: u8, bs: &[u8]) -> Result<Option<Consumed>, Unclos
           }
         }
         (Some(_), Some(_)) => *idx += 1,
-        (_, None) => return Err(UnclosedError),
+        (_, None) => {
+          *idx = bs.len();
+          return Err(UnclosedError { start });
+        }
         (None, Some(_)) => unreachable!("cannot have a byte after EOF"),
       }
     }
   }
   Ok(None)
 }
+
+/// Requires `bs.get(*idx) == Some(&b)`.
+///
+/// Returns whether there is a `*)` at `*idx`, which is not closing any comment since we are not in
+/// one. If so, consumes it, so the caller may report an error and keep going.
+pub fn unmatched_close(idx: &mut usize, b: u8, bs: &[u8]) -> bool {
+  debug_assert_eq!(bs.get(*idx), Some(&b));
+  let ret = b == b'*' && bs.get(*idx + 1) == Some(&b')');
+  if ret {
+    *idx += 2;
+  }
+  ret
+}
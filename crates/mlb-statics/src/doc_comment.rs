@@ -54,12 +54,19 @@ fn get_comment(node: &SyntaxNode) -> Option<String> {
       }
     };
   }
+  // the lexer already handles nested comments, so the whole comment is this one token. but the
+  // lines must be stripped of only their leading `(*` or `*`, not any `*` from a nested comment.
   let mut lines: Vec<_> = tok
     .text()
     .lines()
-    .filter_map(|line| {
-      let (_, s) = line.split_once('*')?;
-      Some(s.strip_prefix(' ').unwrap_or(s))
+    .enumerate()
+    .filter_map(|(idx, line)| {
+      let line = line.trim_start();
+      let prefix = if idx == 0 { "(*" } else { "*" };
+      match line.strip_prefix(prefix) {
+        Some(s) => Some(s.strip_prefix(' ').unwrap_or(s)),
+        None => (!line.is_empty()).then_some(line),
+      }
     })
     .collect();
   let is_doc_comment = !lines.is_empty() && lines.remove(0) == "!" && lines.pop()? == ")";
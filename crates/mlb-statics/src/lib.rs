@@ -24,6 +24,31 @@ pub struct MlbStatics {
   ///
   /// NOTE see comment in impl about having files analyzed more than once.
   pub sml: paths::PathMap<SourceFile>,
+  /// The statics of the source files, for [`update`] to re-use.
+  pub checked: Checked,
+}
+
+/// The statics of each source file, in the order they were checked.
+#[derive(Debug, Default)]
+pub struct Checked {
+  files: Vec<CheckedFile>,
+  reused: usize,
+}
+
+impl Checked {
+  /// Returns how many source files had their statics re-used instead of run again.
+  pub fn reused(&self) -> usize {
+    self.reused
+  }
+}
+
+#[derive(Debug, Clone)]
+struct CheckedFile {
+  path: paths::PathId,
+  /// the syms before checking the file.
+  syms: sml_statics::SymsCheckpoint,
+  /// what the file exported.
+  basis: sml_statics::basis::Basis,
 }
 
 /// A source file.
@@ -36,6 +61,8 @@ pub struct SourceFile {
   pub lowered: sml_lower::Lower,
   pub statics_errors: Vec<sml_statics::Error>,
   pub info: sml_statics::Info,
+  /// The fixity environment after parsing this file.
+  pub fix_env: sml_parse::parser::FixEnv,
 }
 
 /// An error.
@@ -63,6 +90,9 @@ impl Error {
   }
 }
 
+/// The codes returned by [`Error::to_code`], with short descriptions.
+pub const ERROR_CODES: &[(u16, &str)] = &[(1998, "undefined export")];
+
 impl fmt::Display for Error {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     write!(f, "undefined {}: {}", self.item, self.name.val)
@@ -94,9 +124,56 @@ struct Cx {
   cache: paths::PathMap<MBasis>,
   sml: paths::PathMap<SourceFile>,
   mlb_errors: Vec<Error>,
+  checked: Checked,
+  /// source files to re-use the lexing, parsing, and lowering from.
+  prev: paths::PathMap<SourceFile>,
+  /// the statics from the previous run, in order.
+  prev_checked: Vec<CheckedFile>,
+  /// how many of `prev_checked` to try to re-use.
+  reuse: usize,
 }
 
 impl Cx {
+  fn new(syms: sml_statics::Syms, prev: paths::PathMap<SourceFile>) -> Self {
+    Self {
+      syms,
+      cache: paths::PathMap::default(),
+      sml: paths::PathMap::default(),
+      mlb_errors: Vec::new(),
+      checked: Checked::default(),
+      prev,
+      prev_checked: Vec::new(),
+      reuse: 0,
+    }
+  }
+
+  /// returns the source file at `path` and what it exported, if its statics from the previous run
+  /// may be re-used. once one may not be re-used, no later ones may be either, since they may depend
+  /// on it.
+  fn reuse_statics(
+    &mut self,
+    path: paths::PathId,
+  ) -> Option<(SourceFile, sml_statics::basis::Basis)> {
+    let idx = self.checked.files.len();
+    if idx >= self.reuse {
+      return None;
+    }
+    let prev = self.prev_checked.get(idx).filter(|x| x.path == path);
+    let file = prev.and_then(|_| self.prev.remove(&path));
+    match (prev, file) {
+      (Some(prev), Some(file)) => {
+        let basis = prev.basis.clone();
+        self.checked.files.push(prev.clone());
+        self.checked.reused += 1;
+        Some((file, basis))
+      }
+      _ => {
+        self.reuse = 0;
+        None
+      }
+    }
+  }
+
   fn undef(
     &mut self,
     path: paths::PathId,
@@ -109,6 +186,7 @@ impl Cx {
 
 #[derive(Debug, Clone, Copy)]
 struct Files<'a> {
+  lower_config: sml_lower::Config,
   sml: &'a paths::PathMap<String>,
   mlb: &'a paths::PathMap<&'a mlb_hir::BasDec>,
   std_basis: &'a MBasis,
@@ -133,22 +211,73 @@ impl MBasis {
 /// Runs analysis.
 pub fn get(
   std_basis: &StdBasis,
+  lower_config: sml_lower::Config,
+  sml: &paths::PathMap<String>,
+  mlb: &paths::PathMap<&mlb_hir::BasDec>,
+  root_mlb: paths::PathId,
+) -> MlbStatics {
+  let prev = paths::PathMap::default();
+  recheck(std_basis, lower_config, prev, sml, mlb, root_mlb)
+}
+
+/// Like [`get`], but re-uses the lexing, parsing, and lowering of the source files in `prev`,
+/// only re-running statics on them.
+///
+/// The contents of the source files in `prev` must be unchanged since they were processed.
+pub fn recheck(
+  std_basis: &StdBasis,
+  lower_config: sml_lower::Config,
+  prev: paths::PathMap<SourceFile>,
+  sml: &paths::PathMap<String>,
+  mlb: &paths::PathMap<&mlb_hir::BasDec>,
+  root_mlb: paths::PathId,
+) -> MlbStatics {
+  let cx = Cx::new(std_basis.syms().clone(), prev);
+  run(cx, std_basis, lower_config, sml, mlb, root_mlb)
+}
+
+/// Like [`recheck`], but also re-uses the statics of the source files checked before `changed` in
+/// `prev`, only re-running statics on `changed` and the source files checked after it.
+///
+/// `prev` must be from processing the same group files, without the source file at `changed`. Only
+/// the contents of the source file at `changed` may differ since then.
+pub fn update(
+  std_basis: &StdBasis,
+  lower_config: sml_lower::Config,
+  prev: MlbStatics,
+  changed: paths::PathId,
+  sml: &paths::PathMap<String>,
+  mlb: &paths::PathMap<&mlb_hir::BasDec>,
+  root_mlb: paths::PathId,
+) -> MlbStatics {
+  let mut cx = Cx::new(prev.syms, prev.sml);
+  cx.prev_checked = prev.checked.files;
+  match cx.prev_checked.iter().position(|x| x.path == changed) {
+    Some(idx) => {
+      // forget the syms from `changed` and after, since those files will be checked again.
+      cx.syms.rollback(&cx.prev_checked[idx].syms);
+      cx.reuse = idx;
+    }
+    None => cx.syms = std_basis.syms().clone(),
+  }
+  run(cx, std_basis, lower_config, sml, mlb, root_mlb)
+}
+
+fn run(
+  mut cx: Cx,
+  std_basis: &StdBasis,
+  lower_config: sml_lower::Config,
   sml: &paths::PathMap<String>,
   mlb: &paths::PathMap<&mlb_hir::BasDec>,
   root_mlb: paths::PathId,
 ) -> MlbStatics {
-  let mut cx = Cx {
-    syms: std_basis.syms().clone(),
-    cache: paths::PathMap::default(),
-    sml: paths::PathMap::default(),
-    mlb_errors: Vec::new(),
-  };
   let std_basis = MBasis {
     fix_env: STD_BASIS_FIX_ENV.clone(),
     bas_env: FxHashMap::default(),
     basis: std_basis.basis().clone(),
   };
   let files = Files {
+    lower_config,
     sml,
     mlb,
     std_basis: &std_basis,
@@ -158,6 +287,7 @@ pub fn get(
     mlb_errors: cx.mlb_errors,
     syms: cx.syms,
     sml: cx.sml,
+    checked: cx.checked,
   }
 }
 
@@ -240,20 +370,50 @@ fn get_bas_dec(
     }
     mlb_hir::BasDec::Path(path, kind) => match kind {
       mlb_hir::PathKind::Sml => {
-        let contents = files.sml.get(path).expect("no sml file for path id");
-        let mut fix_env = scope.fix_env.clone();
-        let (lex_errors, parsed, low) = start_source_file(contents, &mut fix_env);
+        if let Some((file, basis)) = cx.reuse_statics(*path) {
+          ac.append(MBasis {
+            fix_env: file.fix_env.clone(),
+            bas_env: FxHashMap::default(),
+            basis,
+          });
+          cx.sml.insert(*path, file);
+          return;
+        }
+        let (pos_db, lex_errors, parsed, low, fix_env) = match cx.prev.remove(path) {
+          Some(file) => (
+            file.pos_db,
+            file.lex_errors,
+            file.parsed,
+            file.lowered,
+            file.fix_env,
+          ),
+          None => {
+            let contents = files.sml.get(path).expect("no sml file for path id");
+            let mut fix_env = scope.fix_env.clone();
+            let (lex_errors, parsed, low) =
+              start_source_file(files.lower_config, contents, &mut fix_env);
+            let pos_db = text_pos::PositionDb::new(contents);
+            (pos_db, lex_errors, parsed, low, fix_env)
+          }
+        };
         let mode = sml_statics::Mode::Regular(Some(*path));
+        let syms = cx.syms.checkpoint();
         let checked = sml_statics::get(&mut cx.syms, &scope.basis, mode, &low.arenas, low.root);
+        cx.checked.files.push(CheckedFile {
+          path: *path,
+          syms,
+          basis: checked.basis.clone(),
+        });
         let mut info = checked.info;
         doc_comment::get(parsed.root.syntax(), &low, &mut info);
         let file = SourceFile {
-          pos_db: text_pos::PositionDb::new(contents),
+          pos_db,
           lex_errors,
           parsed,
           lowered: low,
           statics_errors: checked.errors,
           info,
+          fix_env: fix_env.clone(),
         };
         ac.append(MBasis {
           fix_env,
@@ -281,12 +441,13 @@ fn get_bas_dec(
 
 /// Processes a single source file.
 pub fn start_source_file(
+  lower_config: sml_lower::Config,
   contents: &str,
   fix_env: &mut sml_parse::parser::FixEnv,
 ) -> (Vec<sml_lex::Error>, sml_parse::Parse, sml_lower::Lower) {
   let lexed = sml_lex::get(contents);
   let parsed = sml_parse::get(&lexed.tokens, fix_env);
-  let mut lowered = sml_lower::get(&parsed.root);
+  let mut lowered = sml_lower::get(lower_config, &parsed.root);
   sml_ty_var_scope::get(&mut lowered.arenas, lowered.root);
   (lexed.errors, parsed, lowered)
 }
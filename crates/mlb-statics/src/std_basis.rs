@@ -2,6 +2,7 @@ use crate::{doc_comment, start_source_file};
 use fast_hash::FxHashMap;
 use sml_statics::{basis, Info, Syms};
 use sml_syntax::ast::AstNode as _;
+use std::borrow::Cow;
 
 /// A standard basis.
 #[derive(Debug, Clone)]
@@ -9,6 +10,8 @@ pub struct StdBasis {
   syms: Syms,
   basis: basis::Basis,
   info: FxHashMap<&'static str, Info>,
+  /// only text-io.sml is owned, since it's patched before being checked.
+  contents: FxHashMap<&'static str, Cow<'static, str>>,
 }
 
 impl StdBasis {
@@ -44,6 +47,11 @@ impl StdBasis {
   pub fn get_info(&self, s: &str) -> Option<&Info> {
     self.info.get(s)
   }
+
+  /// Look up a std basis file's contents, as they were checked.
+  pub fn get_contents(&self, s: &str) -> Option<&str> {
+    self.contents.get(s).map(AsRef::as_ref)
+  }
 }
 
 const STREAM_IO_REGULAR: &str = "  structure StreamIO : STREAM_IO";
@@ -60,60 +68,69 @@ where
 {
   let (mut syms, mut basis) = basis::minimal();
   let mut imperative_io_hack = None::<String>;
-  let info: FxHashMap<_, _> = files
-    .map(|(name, mut contents)| {
-      if name == "imperative-io.sml" {
-        let mut lines: Vec<_> = contents
-          .lines()
-          .skip(5)
-          .map(|line| {
-            if line == STREAM_IO_REGULAR {
-              STREAM_IO_TEXT
-            } else {
-              line
-            }
-          })
-          .collect();
-        assert_eq!(lines.pop().unwrap(), "end");
-        imperative_io_hack = Some(lines.join("\n"));
-      }
-      let owned_contents: String;
-      if name == "text-io.sml" {
-        let lines: Vec<_> = contents
-          .lines()
-          .map(|line| {
-            if line == INCLUDE_IMPERATIVE_IO_HACK {
-              imperative_io_hack.as_deref().unwrap()
-            } else {
-              line
-            }
-          })
-          .collect();
-        owned_contents = lines.join("\n");
-        contents = &owned_contents;
-      }
-      let mut fix_env = crate::STD_BASIS_FIX_ENV.clone();
-      let (lex_errors, parsed, low) = start_source_file(contents, &mut fix_env);
-      if let Some(e) = lex_errors.first() {
-        panic!("{name}: lex error: {}", e.display());
-      }
-      if let Some(e) = parsed.errors.first() {
-        panic!("{name}: parse error: {}", e.display());
-      }
-      if let Some(e) = low.errors.first() {
-        panic!("{name}: lower error: {}", e.display());
-      }
-      let mode = sml_statics::Mode::StdBasis(name);
-      let checked = sml_statics::get(&mut syms, &basis, mode, &low.arenas, low.root);
-      basis.append(checked.basis);
-      if let Some(e) = checked.errors.first() {
-        let e = e.display(&syms, checked.info.meta_vars(), config::ErrorLines::One);
-        panic!("{name}: statics error: {e}");
-      }
-      let mut info = checked.info;
-      doc_comment::get(parsed.root.syntax(), &low, &mut info);
-      (name, info)
-    })
-    .collect();
-  StdBasis { syms, basis, info }
+  let mut info = FxHashMap::<&'static str, Info>::default();
+  let mut all_contents = FxHashMap::<&'static str, Cow<'static, str>>::default();
+  for (name, contents) in files {
+    if name == "imperative-io.sml" {
+      let mut lines: Vec<_> = contents
+        .lines()
+        .skip(5)
+        .map(|line| {
+          if line == STREAM_IO_REGULAR {
+            STREAM_IO_TEXT
+          } else {
+            line
+          }
+        })
+        .collect();
+      assert_eq!(lines.pop().unwrap(), "end");
+      imperative_io_hack = Some(lines.join("\n"));
+    }
+    let contents = if name == "text-io.sml" {
+      let lines: Vec<_> = contents
+        .lines()
+        .map(|line| {
+          if line == INCLUDE_IMPERATIVE_IO_HACK {
+            imperative_io_hack.as_deref().unwrap()
+          } else {
+            line
+          }
+        })
+        .collect();
+      Cow::Owned(lines.join("\n"))
+    } else {
+      Cow::Borrowed(contents)
+    };
+    let mut fix_env = crate::STD_BASIS_FIX_ENV.clone();
+    let (lex_errors, parsed, low) =
+      start_source_file(sml_lower::Config::default(), &contents, &mut fix_env);
+    if let Some(e) = lex_errors.first() {
+      panic!("{name}: lex error: {}", e.display());
+    }
+    if let Some(e) = parsed.errors.first() {
+      panic!("{name}: parse error: {}", e.display());
+    }
+    if let Some(e) = low.errors.first() {
+      panic!("{name}: lower error: {}", e.display());
+    }
+    let mode = sml_statics::Mode::StdBasis(name);
+    let checked = sml_statics::get(&mut syms, &basis, mode, &low.arenas, low.root);
+    basis.append(checked.basis);
+    if let Some(e) = checked.errors.first() {
+      let e = e.display(&syms, checked.info.meta_vars(), config::ErrorLines::One);
+      panic!("{name}: statics error: {e}");
+    }
+    let mut file_info = checked.info;
+    doc_comment::get(parsed.root.syntax(), &low, &mut file_info);
+    info.insert(name, file_info);
+    all_contents.insert(name, contents);
+  }
+  // the std basis syms are cloned for every analysis, but never changed.
+  syms.freeze();
+  StdBasis {
+    syms,
+    basis,
+    info,
+    contents: all_contents,
+  }
 }
@@ -2,13 +2,14 @@ use crate::types::{Error, ErrorKind, Result, Token};
 use lex_util::{advance_while, block_comment, is_whitespace};
 use text_size_util::{mk_text_size, TextRange, WithRange};
 
-pub(crate) fn get(s: &str) -> Result<Vec<WithRange<Token<'_>>>> {
+/// Errors that do not stop lexing are pushed to `errors`.
+pub(crate) fn get<'s>(s: &'s str, errors: &mut Vec<Error>) -> Result<Vec<WithRange<Token<'s>>>> {
   let bs = s.as_bytes();
   let mut idx = 0usize;
   let mut tokens = Vec::<WithRange<Token<'_>>>::new();
   while let Some(&b) = bs.get(idx) {
     let old = idx;
-    if let Some(val) = token(&mut idx, b, bs)? {
+    if let Some(val) = token(&mut idx, b, bs, errors)? {
       let range = TextRange::new(mk_text_size(old), mk_text_size(idx));
       tokens.push(WithRange { val, range });
     }
@@ -19,18 +20,30 @@ pub(crate) fn get(s: &str) -> Result<Vec<WithRange<Token<'_>>>> {
 
 const PUNCTUATION: [(u8, Token<'_>); 2] = [(b';', Token::Semicolon), (b'=', Token::Eq)];
 
-fn token<'s>(idx: &mut usize, b: u8, bs: &'s [u8]) -> Result<Option<Token<'s>>> {
+fn token<'s>(
+  idx: &mut usize,
+  b: u8,
+  bs: &'s [u8],
+  errors: &mut Vec<Error>,
+) -> Result<Option<Token<'s>>> {
   let start = *idx;
   match block_comment::get(idx, b, bs) {
     Ok(Some(block_comment::Consumed)) => return Ok(None),
     Ok(None) => {}
-    Err(block_comment::UnclosedError) => {
+    Err(block_comment::UnclosedError { start }) => {
       return Err(Error::new(
         ErrorKind::UnclosedComment,
         TextRange::new(mk_text_size(start), mk_text_size(*idx)),
       ));
     }
   }
+  if block_comment::unmatched_close(idx, b, bs) {
+    errors.push(Error::new(
+      ErrorKind::UnmatchedCloseComment,
+      TextRange::new(mk_text_size(start), mk_text_size(*idx)),
+    ));
+    return Ok(None);
+  }
   if is_whitespace(b) {
     *idx += 1;
     advance_while(idx, bs, is_whitespace);
@@ -42,11 +55,9 @@ fn token<'s>(idx: &mut usize, b: u8, bs: &'s [u8]) -> Result<Option<Token<'s>>>
       return Ok(Some(tok));
     }
   }
-  // TODO support all SML string features
   if b == b'"' {
     *idx += 1;
-    advance_while(idx, bs, |b| b != b'"');
-    *idx += 1;
+    string(start, idx, bs, &mut String::new())?;
     return Ok(Some(Token::String(
       std::str::from_utf8(&bs[start..*idx]).unwrap(),
     )));
@@ -90,3 +101,137 @@ fn token<'s>(idx: &mut usize, b: u8, bs: &'s [u8]) -> Result<Option<Token<'s>>>
   };
   Ok(Some(ret))
 }
+
+/// Returns the contents of the string literal `s`, which must have already been successfully lexed
+/// as a [`Token::String`], with escapes processed.
+pub(crate) fn string_contents(s: &str) -> String {
+  let mut idx = 1usize;
+  let mut ret = String::new();
+  string(0, &mut idx, s.as_bytes(), &mut ret).expect("already lexed");
+  ret
+}
+
+/// requires we just entered a string (so `bs[start]` is a `"` and `*idx == start + 1`). pushes the
+/// contents of the string, with escapes processed, onto `out`.
+fn string(start: usize, idx: &mut usize, bs: &[u8], out: &mut String) -> Result<()> {
+  loop {
+    let b = match bs.get(*idx) {
+      None | Some(b'\n') => {
+        return Err(Error::new(
+          ErrorKind::UnclosedString,
+          TextRange::new(mk_text_size(start), mk_text_size(*idx)),
+        ))
+      }
+      Some(&b) => b,
+    };
+    match b {
+      b'"' => {
+        *idx += 1;
+        return Ok(());
+      }
+      b'\\' => {
+        let esc_start = *idx;
+        *idx += 1;
+        match escape(idx, bs, out) {
+          Some(Escape::Ok) => {}
+          Some(Escape::Invalid) => {
+            return Err(Error::new(
+              ErrorKind::InvalidStringEscape,
+              TextRange::new(mk_text_size(esc_start), mk_text_size(*idx)),
+            ))
+          }
+          None => {
+            return Err(Error::new(
+              ErrorKind::UnclosedString,
+              TextRange::new(mk_text_size(start), mk_text_size(*idx)),
+            ))
+          }
+        }
+      }
+      _ => {
+        let s = *idx;
+        advance_while(idx, bs, |b| !matches!(b, b'"' | b'\\' | b'\n'));
+        // the bytes we stopped at are all ASCII, so this is on a char boundary.
+        out.push_str(std::str::from_utf8(&bs[s..*idx]).unwrap());
+      }
+    }
+  }
+}
+
+enum Escape {
+  Ok,
+  Invalid,
+}
+
+/// requires we just passed a `\`. returns None iff the input ended (or had a newline) before the
+/// escape was complete.
+fn escape(idx: &mut usize, bs: &[u8], out: &mut String) -> Option<Escape> {
+  let b = *bs.get(*idx)?;
+  *idx += 1;
+  let c = match b {
+    b'a' => '\x07',
+    b'b' => '\x08',
+    b't' => '\t',
+    b'n' => '\n',
+    b'v' => '\x0b',
+    b'f' => '\x0c',
+    b'r' => '\r',
+    b'"' => '"',
+    b'\\' => '\\',
+    b'^' => {
+      let b = *bs.get(*idx)?;
+      *idx += 1;
+      if !(64..=95).contains(&b) {
+        return Some(Escape::Invalid);
+      }
+      char::from(b - 64)
+    }
+    b'u' => match digits(idx, bs, 4, 16)?.and_then(char::from_u32) {
+      Some(c) => c,
+      None => return Some(Escape::Invalid),
+    },
+    b'0'..=b'9' => {
+      *idx -= 1;
+      match digits(idx, bs, 3, 10)?.and_then(|n| u8::try_from(n).ok()) {
+        Some(n) => char::from(n),
+        None => return Some(Escape::Invalid),
+      }
+    }
+    _ => {
+      if !is_whitespace(b) {
+        return Some(Escape::Invalid);
+      }
+      // the `\...\` gap: whitespace between two backslashes is ignored.
+      loop {
+        let b = *bs.get(*idx)?;
+        *idx += 1;
+        if b == b'\\' {
+          return Some(Escape::Ok);
+        }
+        if !is_whitespace(b) {
+          return Some(Escape::Invalid);
+        }
+      }
+    }
+  };
+  out.push(c);
+  Some(Escape::Ok)
+}
+
+/// parses exactly `n` digits in the given `radix`. returns None iff the input ended (or had a
+/// newline) first, and Some(None) iff one of the bytes was not a digit.
+fn digits(idx: &mut usize, bs: &[u8], n: usize, radix: u32) -> Option<Option<u32>> {
+  let mut ret = 0u32;
+  for _ in 0..n {
+    let b = *bs.get(*idx)?;
+    if b == b'\n' {
+      return None;
+    }
+    *idx += 1;
+    match char::from(b).to_digit(radix) {
+      Some(d) => ret = ret * radix + d,
+      None => return Some(None),
+    }
+  }
+  Some(Some(ret))
+}
@@ -14,7 +14,11 @@ mod types;
 pub use types::{BasDec, BasExp, Error, Namespace, ParsedPath, PathKind, Result};
 
 /// Process the contents of a ML Basis file.
-pub fn get(s: &str, env: &paths::slash_var_path::Env) -> Result<BasDec> {
-  let tokens = lex::get(s)?;
-  parse::get(&tokens, env)
+///
+/// Also returns the errors that did not stop processing, like a stray `*)`.
+pub fn get(s: &str, env: &paths::slash_var_path::Env) -> Result<(BasDec, Vec<Error>)> {
+  let mut errors = Vec::<Error>::new();
+  let tokens = lex::get(s, &mut errors)?;
+  let ret = parse::get(&tokens, env)?;
+  Ok((ret, errors))
 }
@@ -68,10 +68,10 @@ impl<'a> Parser<'a> {
     }
   }
 
-  fn string(&self) -> Result<WithRange<&'a str>> {
+  fn string(&self) -> Result<WithRange<String>> {
     match self.cur_tok() {
       Some(tok) => match tok.val {
-        Token::String(s) => Ok(tok.wrap(s)),
+        Token::String(s) => Ok(tok.wrap(crate::lex::string_contents(s))),
         _ => self.err(ErrorKind::ExpectedName),
       },
       _ => self.err(ErrorKind::ExpectedName),
@@ -154,31 +154,18 @@ fn bas_dec_one(p: &mut Parser<'_>) -> Result<BasDecOne> {
       p.bump();
       BasDec::Export(Namespace::Functor, names_seq(p)?)
     }
-    // TODO allow string paths as well
     Token::BarePath(path) => {
       p.bump();
-      let path = match paths::slash_var_path::get(path, p.env) {
-        Ok(x) => x,
-        Err(e) => {
-          if let paths::slash_var_path::Error::Undefined(var) = &e {
-            // ignore the sml lib paths (http://mlton.org/MLBasisPathMap) since they're baked in.
-            if var == "SML_LIB" {
-              return Ok(BasDecOne::Ignore);
-            }
-          }
-          return p.err(ErrorKind::SlashVarPathError(e));
-        }
-      };
-      let kind = match path_kind(path.as_path()) {
-        Some(x) => x,
-        None => return p.err(ErrorKind::PathNotSmlOrMlb),
-      };
-      BasDec::Path(tok.wrap(ParsedPath { path, kind }))
+      return path_bas_dec(p, tok.wrap(path));
+    }
+    Token::String(path) => {
+      p.bump();
+      let path = crate::lex::string_contents(path);
+      return path_bas_dec(p, tok.wrap(path.as_str()));
     }
     Token::Ann => {
       p.bump();
       let s = p.string()?;
-      let s = s.wrap(s.val.to_owned());
       p.bump();
       p.eat(Token::In)?;
       let bd = bas_dec(p)?;
@@ -190,6 +177,27 @@ fn bas_dec_one(p: &mut Parser<'_>) -> Result<BasDecOne> {
   Ok(BasDecOne::Ok(ret))
 }
 
+fn path_bas_dec(p: &mut Parser<'_>, s: WithRange<&str>) -> Result<BasDecOne> {
+  let path = match paths::slash_var_path::get(s.val, p.env) {
+    Ok(x) => x,
+    Err(e) => {
+      if let paths::slash_var_path::Error::Undefined(var) = &e {
+        // ignore the sml lib paths (http://mlton.org/MLBasisPathMap) since they're baked in.
+        if var == "SML_LIB" {
+          return Ok(BasDecOne::Ignore);
+        }
+      }
+      return p.err(ErrorKind::SlashVarPathError(e));
+    }
+  };
+  let kind = match path_kind(path.as_path()) {
+    Some(x) => x,
+    None => return p.err(ErrorKind::PathNotSmlOrMlb),
+  };
+  let ret = BasDec::Path(s.wrap(ParsedPath { path, kind }));
+  Ok(BasDecOne::Ok(ret))
+}
+
 fn path_kind(path: &Path) -> Option<PathKind> {
   let ret = match path.extension()?.to_str()? {
     "sml" | "sig" | "fun" => PathKind::Sml,
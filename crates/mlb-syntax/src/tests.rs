@@ -1,5 +1,6 @@
 fn check(s: &str) {
-  crate::get(s, &paths::slash_var_path::Env::default()).unwrap();
+  let (_, errors) = crate::get(s, &paths::slash_var_path::Env::default()).unwrap();
+  assert!(errors.is_empty(), "{errors:?}");
 }
 
 #[test]
@@ -25,3 +26,116 @@ signature ARRAY_UTIL
 "#,
   );
 }
+
+fn check_path(s: &str, want: &str) {
+  match crate::get(s, &paths::slash_var_path::Env::default())
+    .unwrap()
+    .0
+  {
+    crate::BasDec::Path(path) => assert_eq!(path.val.as_path(), std::path::Path::new(want)),
+    bd => panic!("not a path: {bd:?}"),
+  }
+}
+
+fn check_err(s: &str, want_msg: &str, want_range: std::ops::Range<u32>) {
+  let e = crate::get(s, &paths::slash_var_path::Env::default()).unwrap_err();
+  assert_eq!(e.to_string(), want_msg);
+  let want_range = text_size_util::TextRange::new(want_range.start.into(), want_range.end.into());
+  assert_eq!(e.text_range(), want_range);
+}
+
+#[test]
+fn string_path() {
+  check_path(r#""foo bar.sml""#, "foo bar.sml");
+}
+
+#[test]
+fn string_path_escaped_quote() {
+  check_path(r#""foo\"bar\".sml""#, r#"foo"bar".sml"#);
+}
+
+#[test]
+fn string_path_escaped_backslash() {
+  check_path(r#""foo\\bar.sml""#, r"foo\bar.sml");
+}
+
+#[test]
+fn string_path_escapes() {
+  check_path(r#""a\tb\n\065\u0042.sml""#, "a\tb\nAB.sml");
+}
+
+#[test]
+fn string_path_gap() {
+  check_path("\"foo/\\  \n  \\bar.sml\"", "foo/bar.sml");
+}
+
+#[test]
+fn string_ann_escaped_quote() {
+  check(r#"ann "foo \"bar\"" in a.sml end"#);
+}
+
+#[test]
+fn string_unclosed() {
+  check_err(r#"a.sml "b.sml"#, "unclosed string literal", 6..12);
+}
+
+#[test]
+fn string_unclosed_newline() {
+  check_err("\"a.sml\nb.sml\"", "unclosed string literal", 0..6);
+}
+
+#[test]
+fn string_unclosed_escaped_quote() {
+  check_err(r#""a.sml\""#, "unclosed string literal", 0..8);
+}
+
+#[test]
+fn string_invalid_escape() {
+  check_err(r#""a\q.sml""#, "invalid string escape", 2..4);
+}
+
+#[test]
+fn string_invalid_escape_decimal() {
+  check_err(r#""a\999.sml""#, "invalid string escape", 2..6);
+}
+
+#[test]
+fn string_invalid_escape_gap() {
+  check_err(r#""a\  b\.sml""#, "invalid string escape", 2..6);
+}
+
+#[test]
+fn unclosed_comment_nested() {
+  check_err("a.sml (* x (* y *) z", "unclosed block comment", 6..20);
+}
+
+#[test]
+fn unmatched_close_comment() {
+  let (bas_dec, errors) =
+    crate::get("a.sml *) b.sml", &paths::slash_var_path::Env::default()).unwrap();
+  match bas_dec {
+    crate::BasDec::Seq(bds) => assert_eq!(bds.len(), 2),
+    bd => panic!("not a seq: {bd:?}"),
+  }
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].to_string(), "unmatched close comment");
+  let want_range = text_size_util::TextRange::new(6.into(), 8.into());
+  assert_eq!(errors[0].text_range(), want_range);
+}
+
+#[test]
+fn unmatched_close_comment_lex_continues() {
+  let mut errors = Vec::new();
+  let tokens = crate::lex::get("a.sml *) b.sml (* c *) d.sml", &mut errors).unwrap();
+  let tokens: Vec<_> = tokens.into_iter().map(|x| x.val).collect();
+  assert_eq!(
+    tokens,
+    [
+      crate::types::Token::BarePath("a.sml"),
+      crate::types::Token::BarePath("b.sml"),
+      crate::types::Token::BarePath("d.sml"),
+    ]
+  );
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].to_string(), "unmatched close comment");
+}
@@ -10,6 +10,9 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub(crate) enum ErrorKind {
   InvalidSource,
   UnclosedComment,
+  UnmatchedCloseComment,
+  UnclosedString,
+  InvalidStringEscape,
   Expected(Token<'static>),
   ExpectedBasExp,
   ExpectedBasDec,
@@ -38,6 +41,9 @@ impl fmt::Display for Error {
     match &self.0.val {
       ErrorKind::InvalidSource => f.write_str("invalid source character"),
       ErrorKind::UnclosedComment => f.write_str("unclosed block comment"),
+      ErrorKind::UnmatchedCloseComment => f.write_str("unmatched close comment"),
+      ErrorKind::UnclosedString => f.write_str("unclosed string literal"),
+      ErrorKind::InvalidStringEscape => f.write_str("invalid string escape"),
       ErrorKind::Expected(tok) => write!(f, "expected `{tok}`"),
       ErrorKind::ExpectedBasExp => f.write_str("expected a basis expression"),
       ErrorKind::ExpectedBasDec => f.write_str("expected a basis declaration"),
@@ -178,6 +178,8 @@ pub enum Exp {
   SCon(SCon),
   Path(Path),
   Record(Vec<(Lab, ExpIdx)>),
+  /// Only allowed when vectors are enabled.
+  Vector(Vec<ExpIdx>),
   Let(DecIdx, ExpIdx),
   App(ExpIdx, ExpIdx),
   Handle(ExpIdx, Vec<(PatIdx, ExpIdx)>),
@@ -251,6 +253,8 @@ pub enum Pat {
     rows: Vec<(Lab, PatIdx)>,
     allows_other: bool,
   },
+  /// Only allowed when vectors are enabled.
+  Vector(Vec<PatIdx>),
   Typed(PatIdx, TyIdx),
   /// the Definition defines as-pats as having a built-in optional type annotation. however, it
   /// appears that lowering from `vid : ty as pat` to `vid as (pat : ty)` should be equivalent
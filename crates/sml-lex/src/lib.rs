@@ -36,6 +36,15 @@ impl Error {
     &self.kind
   }
 
+  /// Returns whether this is for a comment or string literal that was not closed before the end of
+  /// the input. The range of such an error is the range of that whole token.
+  pub fn is_unclosed(&self) -> bool {
+    matches!(
+      self.kind,
+      ErrorKind::UnclosedComment | ErrorKind::UnclosedStringLit
+    )
+  }
+
   /// Returns the code for this.
   pub fn to_code(&self) -> u16 {
     match self.kind {
@@ -52,6 +61,19 @@ impl Error {
   }
 }
 
+/// The codes returned by [`Error::to_code`], with short descriptions.
+pub const ERROR_CODES: &[(u16, &str)] = &[
+  (2001, "invalid source character"),
+  (2002, "unclosed comment"),
+  (2003, "incomplete type variable"),
+  (2004, "unclosed string literal"),
+  (2005, "negative word literal"),
+  (2006, "character literal not of length 1"),
+  (2007, "missing digits in number literal"),
+  (2008, "invalid string escape"),
+  (2009, "non-whitespace in string continuation"),
+];
+
 /// An error kind.
 #[derive(Debug)]
 enum ErrorKind {
@@ -89,6 +111,15 @@ pub fn get(s: &str) -> Lex<'_> {
   let bs = s.as_bytes();
   let mut tokens = Vec::new();
   let mut cx = Cx::default();
+  // a shebang line, as allowed by some tooling for scripts. only allowed at the very start.
+  if bs.starts_with(b"#!") {
+    advance_while(&mut cx.i, bs, |b| b != b'\n');
+    let text = std::str::from_utf8(&bs[..cx.i]).unwrap();
+    tokens.push(Token {
+      kind: SK::Shebang,
+      text,
+    });
+  }
   while cx.i < bs.len() {
     let start = cx.i;
     let kind = go(&mut cx, bs);
@@ -119,7 +150,7 @@ fn go(cx: &mut Cx, bs: &[u8]) -> SK {
   match block_comment::get(&mut cx.i, b, bs) {
     Ok(None) => {}
     Ok(Some(block_comment::Consumed)) => return SK::BlockComment,
-    Err(block_comment::UnclosedError) => {
+    Err(block_comment::UnclosedError { start }) => {
       err(cx, start, ErrorKind::UnclosedComment);
       return SK::BlockComment;
     }
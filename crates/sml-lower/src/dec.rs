@@ -43,6 +43,7 @@ pub(crate) fn get_one(cx: &mut Cx, dec: ast::DecOne) -> sml_hir::DecIdx {
           let ptr = SyntaxNodePtr::new(fun_bind.syntax());
           let mut name = None::<sml_syntax::SyntaxToken>;
           let mut num_pats = None::<usize>;
+          let mut reported_num_pats = false;
           let arms: Vec<_> = fun_bind
             .fun_bind_cases()
             .map(|case| {
@@ -79,7 +80,10 @@ pub(crate) fn get_one(cx: &mut Cx, dec: ast::DecOne) -> sml_hir::DecIdx {
               match num_pats {
                 None => num_pats = Some(pats.len()),
                 Some(num_pats) => {
-                  if num_pats != pats.len() {
+                  // only report the first case that differs from the first case, since if the
+                  // first case is the odd one out, every other case would differ.
+                  if num_pats != pats.len() && !reported_num_pats {
+                    reported_num_pats = true;
                     cx.err(
                       case.syntax().text_range(),
                       ErrorKind::FunBindWrongNumPats(num_pats, pats.len()),
@@ -52,6 +52,9 @@ pub(crate) fn get(cx: &mut Cx, exp: Option<ast::Exp>) -> sml_hir::ExpIdx {
         ptr.clone(),
       );
       let body = cx.exp(name(fresh.as_str()), ptr.clone());
+      // NOTE: every part of this `fn` points at the selector, but the selector points back at only
+      // the `fn` itself, since that is allocated last. so hovering over any part of the selector
+      // shows the type of the whole `fn`.
       sml_hir::Exp::Fn(vec![(param, body)])
     }
     // sml_def(5)
@@ -68,11 +71,20 @@ pub(crate) fn get(cx: &mut Cx, exp: Option<ast::Exp>) -> sml_hir::ExpIdx {
       })
     }
     ast::Exp::VectorExp(exp) => {
-      cx.err(
-        exp.syntax().text_range(),
-        ErrorKind::Unsupported("vector expressions"),
-      );
-      return None;
+      if !cx.config.vectors {
+        // emit an error, but lower anyway.
+        cx.err(
+          exp.syntax().text_range(),
+          ErrorKind::Unsupported("vector expressions"),
+        );
+      }
+      sml_hir::Exp::Vector(
+        exp
+          .list_exp()?
+          .exp_args()
+          .map(|x| get(cx, x.exp()))
+          .collect(),
+      )
     }
     ast::Exp::SeqExp(exp) => return exps_in_seq(cx, exp.exps_in_seq(), ptr),
     ast::Exp::LetExp(exp) => {
@@ -92,16 +104,14 @@ pub(crate) fn get(cx: &mut Cx, exp: Option<ast::Exp>) -> sml_hir::ExpIdx {
     }
     ast::Exp::TypedExp(exp) => sml_hir::Exp::Typed(get(cx, exp.exp()), ty::get(cx, exp.ty())),
     ast::Exp::AndalsoExp(exp) => {
-      let cond = get(cx, exp.lhs());
-      let yes = get(cx, exp.rhs());
-      let no = cx.exp(name("false"), ptr.clone());
-      if_(cx, cond, yes, no, ptr.clone())
+      let lhs = get(cx, exp.lhs());
+      let rhs = get(cx, exp.rhs());
+      short_circuit(cx, lhs, rhs, false, ptr.clone())
     }
     ast::Exp::OrelseExp(exp) => {
-      let cond = get(cx, exp.lhs());
-      let yes = cx.exp(name("true"), ptr.clone());
-      let no = get(cx, exp.rhs());
-      if_(cx, cond, yes, no, ptr.clone())
+      let lhs = get(cx, exp.lhs());
+      let rhs = get(cx, exp.rhs());
+      short_circuit(cx, lhs, rhs, true, ptr.clone())
     }
     ast::Exp::HandleExp(exp) => {
       sml_hir::Exp::Handle(get(cx, exp.exp()), matcher(cx, exp.matcher()))
@@ -187,7 +197,8 @@ where
 /// 2. `(case e1 of _ => ... => case en of _ => e)`
 /// 3. `((fn _ => ... (fn _ => (fn _ => e) en) ...) e1)`
 ///
-/// the vec must not be empty, since we need a last expression `e`.
+/// if the vec is empty, there is no last expression `e`, so this returns `None`. this may happen
+/// with malformed input.
 fn exp_idx_in_seq<A, B>(cx: &mut Cx, exps: A, ptr: SyntaxNodePtr) -> sml_hir::ExpIdx
 where
   A: IntoIterator<IntoIter = B>,
@@ -216,6 +227,29 @@ fn if_(
   case(cx, cond, vec![(yes_pat, yes), (no_pat, no)], ptr)
 }
 
+/// lowers `lhs andalso rhs` if `short` is `false`, else `lhs orelse rhs`.
+///
+/// the arm that returns `short` without evaluating `rhs` comes first. this doesn't change the
+/// meaning, since the patterns are disjoint, but it means statics checks `rhs` against `bool` and
+/// reports any error on `rhs` itself, instead of on the whole expression.
+fn short_circuit(
+  cx: &mut Cx,
+  lhs: sml_hir::ExpIdx,
+  rhs: sml_hir::ExpIdx,
+  short: bool,
+  ptr: SyntaxNodePtr,
+) -> sml_hir::Exp {
+  let (short_name, long_name) = if short {
+    ("true", "false")
+  } else {
+    ("false", "true")
+  };
+  let short_pat = cx.pat(pat::name(short_name), ptr.clone());
+  let short_exp = cx.exp(name(short_name), ptr.clone());
+  let long_pat = cx.pat(pat::name(long_name), ptr.clone());
+  case(cx, lhs, vec![(short_pat, short_exp), (long_pat, rhs)], ptr)
+}
+
 pub(crate) fn case(
   cx: &mut Cx,
   head: sml_hir::ExpIdx,
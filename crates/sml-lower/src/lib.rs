@@ -12,4 +12,4 @@ mod ty;
 mod util;
 
 pub use root::get;
-pub use util::{Error, Lower, Ptrs};
+pub use util::{Config, Error, Lower, Ptrs, ERROR_CODES};
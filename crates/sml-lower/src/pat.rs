@@ -87,11 +87,20 @@ fn get_or(cx: &mut Cx, pat: ast::Pat) -> Option<sml_hir::OrPat> {
       })
     }
     ast::Pat::VectorPat(pat) => {
-      cx.err(
-        pat.syntax().text_range(),
-        ErrorKind::Unsupported("vector patterns"),
-      );
-      return None;
+      if !cx.config.vectors {
+        // emit an error, but lower anyway.
+        cx.err(
+          pat.syntax().text_range(),
+          ErrorKind::Unsupported("vector patterns"),
+        );
+      }
+      sml_hir::Pat::Vector(
+        pat
+          .list_pat()?
+          .pat_args()
+          .map(|x| get(cx, x.pat()))
+          .collect(),
+      )
     }
     ast::Pat::InfixPat(pat) => {
       let func = sml_hir::Path::one(sml_hir::Name::new(pat.name_star_eq()?.token.text()));
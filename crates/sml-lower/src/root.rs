@@ -1,10 +1,13 @@
 use crate::top_dec;
-use crate::util::{Cx, Lower};
+use crate::util::{Config, Cx, Lower};
 use sml_syntax::ast;
 
 /// Does the conversion.
-pub fn get(root: &ast::Root) -> Lower {
-  let mut cx = Cx::default();
+pub fn get(config: Config, root: &ast::Root) -> Lower {
+  let mut cx = Cx {
+    config,
+    ..Cx::default()
+  };
   let idx = top_dec::get_str_dec(&mut cx, root.str_dec());
   cx.finish(idx)
 }
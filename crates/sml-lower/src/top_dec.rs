@@ -186,6 +186,7 @@ fn get_spec_one(cx: &mut Cx, spec: ast::SpecOne) -> sml_hir::SpecIdx {
       spec
         .val_descs()
         .filter_map(|x| {
+          // NOTE: if there's no name, like with `val op + : ...`, the parser already errored.
           Some(sml_hir::ValDesc {
             name: sml_hir::Name::new(x.name_star_eq()?.token.text()),
             ty: ty::get(cx, x.ty()),
@@ -240,7 +241,10 @@ fn get_spec_one(cx: &mut Cx, spec: ast::SpecOne) -> sml_hir::SpecIdx {
         .map(|x| sml_hir::Spec::Include(get_sig_exp(cx, Some(x))))
         .collect();
       if specs.is_empty() {
-        cx.err(spec.syntax().text_range(), ErrorKind::RequiresOperand);
+        let range = spec
+          .include_kw()
+          .map_or_else(|| spec.syntax().text_range(), |x| x.text_range());
+        cx.err(range, ErrorKind::RequiresOperand);
       }
       seq(cx, ptr.clone(), specs)
     }
@@ -63,6 +63,20 @@ impl Error {
   }
 }
 
+/// The codes returned by [`Error::to_code`], with short descriptions.
+pub const ERROR_CODES: &[(u16, &str)] = &[
+  (4001, "`fun` cases with different names"),
+  (4002, "`fun` cases with different numbers of patterns"),
+  (4003, "invalid integer literal"),
+  (4004, "invalid real literal"),
+  (4005, "invalid numeric label"),
+  (4006, "multiple `...` pattern rows"),
+  (4007, "`...` pattern row not last"),
+  (4008, "preceding `|`"),
+  (4009, "`open` or `include` without operands"),
+  (4999, "unsupported language construct"),
+];
+
 #[derive(Debug)]
 pub(crate) enum ErrorKind {
   FunBindMismatchedName(String, String),
@@ -106,6 +120,13 @@ impl fmt::Display for ErrorKind {
   }
 }
 
+/// Options for lowering.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Config {
+  /// Whether to allow vector expressions and patterns, which are an extension to SML.
+  pub vectors: bool,
+}
+
 /// The result of lowering.
 #[derive(Debug)]
 pub struct Lower {
@@ -121,6 +142,7 @@ pub struct Lower {
 
 #[derive(Debug, Default)]
 pub(crate) struct Cx {
+  pub(crate) config: Config,
   fresh_idx: u32,
   errors: Vec<Error>,
   arenas: sml_hir::Arenas,
@@ -2,7 +2,7 @@ use crate::exp::exp;
 use crate::parser::{ErrorKind, Exited, Expected, Infix, Parser};
 use crate::pat::{at_pat, pat};
 use crate::ty::{of_ty, ty, ty_annotation, ty_var_seq};
-use crate::util::{eat_name_star, many_sep, maybe_semi_sep, must, name_star_eq, path};
+use crate::util::{eat_name_star, many_sep, maybe_semi_sep, must, name_star, name_star_eq, path};
 use sml_syntax::SyntaxKind as SK;
 
 pub(crate) fn dec(p: &mut Parser<'_>) -> Exited {
@@ -60,19 +60,27 @@ pub(crate) fn dec_one(p: &mut Parser<'_>) -> bool {
             if saw_op {
               p.bump();
             }
-            if let Some(name) = eat_name_star(p) {
-              if !saw_op && p.is_infix(name.text) {
+            // report the error before eating, so it is reported at the name.
+            if !saw_op && name_star(p, 0) {
+              let name = p.peek().unwrap();
+              if p.is_infix(name.text) {
                 p.error(ErrorKind::InfixWithoutOp);
               }
             }
+            eat_name_star(p);
             p.exit(en, SK::PrefixFunBindCaseHead);
           }
           while at_pat(p).is_some() {
             // no body
           }
         }
-        let _ = ty_annotation(p);
-        p.eat(SK::Eq);
+        if ty_annotation(p).is_some() {
+          p.eat(SK::Eq);
+        } else if p.at(SK::Eq) {
+          p.bump();
+        } else {
+          p.expect_one_of(&[Expected::Kind(SK::Colon), Expected::Kind(SK::Eq)]);
+        }
         exp(p);
         true
       })
@@ -183,7 +191,7 @@ where
     f(p, text);
   }
   if !got {
-    p.error(ErrorKind::Expected(Expected::Kind(SK::Name)));
+    p.error(ErrorKind::Expected(vec![Expected::Kind(SK::Name)]));
   }
 }
 
@@ -234,11 +242,13 @@ fn ty_binds(p: &mut Parser<'_>) {
 
 fn infix_fun_bind_case_head_inner(p: &mut Parser<'_>) {
   must(p, at_pat, Expected::Pat);
-  if let Some(name) = eat_name_star(p) {
+  if name_star(p, 0) {
+    let name = p.peek().unwrap();
     if !p.is_infix(name.text) {
       p.error(ErrorKind::NotInfix);
     }
   }
+  eat_name_star(p);
   must(p, at_pat, Expected::Pat);
 }
 
@@ -1,10 +1,10 @@
 use crate::dec::dec;
-use crate::parser::{ErrorKind, Exited, Expected, Infix, Parser};
+use crate::parser::{Exited, Expected, InfixOp, Parser};
 use crate::pat::pat;
 use crate::ty::ty;
 use crate::util::{
-  comma_sep, lab, many_sep, must, name_star_eq, path, path_no_infix, scon, should_break,
-  ShouldBreak,
+  close, comma_sep, lab, many_sep, must, name_star_eq, path, path_no_infix, scon, should_break,
+  trailing_comma, ShouldBreak,
 };
 use sml_syntax::SyntaxKind as SK;
 
@@ -46,21 +46,23 @@ fn exp_prec(p: &mut Parser<'_>, min_prec: ExpPrec) -> Option<Exited> {
     p.abandon(en);
     let mut ex = at_exp(p)?;
     loop {
-      let op_info = if name_star_eq(p) {
-        let text = p.peek().unwrap().text;
-        p.get_infix(text)
+      let op = if name_star_eq(p) {
+        p.get_infix_op()
       } else {
         None
       };
-      ex = if let Some(op_info) = op_info {
-        match should_break_exp(ExpPrec::Infix(op_info), min_prec) {
+      ex = if let Some(op) = op {
+        match should_break_exp(ExpPrec::Infix(op), min_prec) {
           ShouldBreak::Yes => break,
           ShouldBreak::No => {}
-          ShouldBreak::Error => p.error(ErrorKind::SameFixityDiffAssoc),
+          ShouldBreak::Error => match min_prec {
+            ExpPrec::Infix(prev) => p.same_fixity_diff_assoc(prev, op),
+            _ => unreachable!("only infix ops can have the same fixity"),
+          },
         }
         let en = p.precede(ex);
         p.bump();
-        must(p, |p| exp_prec(p, ExpPrec::Infix(op_info)), Expected::Exp);
+        must(p, |p| exp_prec(p, ExpPrec::Infix(op)), Expected::Exp);
         p.exit(en, SK::InfixExp)
       } else if p.at(SK::Colon) {
         if matches!(min_prec, ExpPrec::Infix(_)) {
@@ -189,10 +191,15 @@ fn at_exp_l_round(p: &mut Parser<'_>) -> SK {
     if p.at(SK::RRound) {
       p.bump();
     } else {
-      p.error(ErrorKind::Expected(Expected::LRoundExpTail));
+      p.expect_one_of(&[
+        Expected::Kind(SK::RRound),
+        Expected::Kind(SK::Comma),
+        Expected::Kind(SK::Semicolon),
+      ]);
     }
     return SK::ParenExp;
   }
+  let trailing = trailing_comma(p, SK::RRound);
   let kind = p.bump().kind;
   let (wrap, overall) = match kind {
     SK::Semicolon => (SK::ExpInSeq, SK::SeqExp),
@@ -200,15 +207,24 @@ fn at_exp_l_round(p: &mut Parser<'_>) -> SK {
     _ => unreachable!("just checked at either ; or , above"),
   };
   p.exit(en, wrap);
+  if trailing {
+    p.bump();
+    return overall;
+  }
   loop {
     let en = p.enter();
     exp(p);
     if p.at(kind) {
+      let trailing = trailing_comma(p, SK::RRound);
       p.bump();
       p.exit(en, wrap);
+      if trailing {
+        p.bump();
+        break;
+      }
     } else {
       p.exit(en, wrap);
-      p.eat(SK::RRound);
+      close(p, SK::RRound);
       break;
     }
   }
@@ -247,7 +263,7 @@ fn at_exp_hd(p: &mut Parser<'_>) -> bool {
 
 #[derive(Debug, Clone, Copy)]
 enum ExpPrec {
-  Infix(Infix),
+  Infix(InfixOp),
   Andalso,
   Orelse,
   Min,
@@ -255,7 +271,7 @@ enum ExpPrec {
 
 fn should_break_exp(prec: ExpPrec, min_prec: ExpPrec) -> ShouldBreak {
   match (prec, min_prec) {
-    (ExpPrec::Infix(prec), ExpPrec::Infix(min_prec)) => should_break(prec, min_prec),
+    (ExpPrec::Infix(prec), ExpPrec::Infix(min_prec)) => should_break(prec.info, min_prec.info),
     (_, ExpPrec::Min) | (ExpPrec::Infix(_), _) | (ExpPrec::Andalso, ExpPrec::Orelse) => {
       ShouldBreak::No
     }
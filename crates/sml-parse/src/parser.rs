@@ -265,11 +265,16 @@ impl<'a> Parser<'a> {
     if self.at(kind) {
       Some(self.bump())
     } else {
-      self.error(ErrorKind::Expected(Expected::Kind(kind)));
+      self.error(ErrorKind::Expected(vec![Expected::Kind(kind)]));
       None
     }
   }
 
+  /// Errors with all of `es`, for when any of them would have been valid at the current token.
+  pub(crate) fn expect_one_of(&mut self, es: &[Expected]) {
+    self.error(ErrorKind::Expected(es.to_vec()));
+  }
+
   // sml-specific methods //
 
   pub(crate) fn insert_infix(&mut self, name: &str, info: Infix) {
@@ -280,6 +285,40 @@ impl<'a> Parser<'a> {
     self.fix_env.get(name).copied()
   }
 
+  /// Returns the current token as an infix operator, if it is one.
+  pub(crate) fn get_infix_op(&mut self) -> Option<InfixOp> {
+    let tok = self.peek()?;
+    let info = self.get_infix(tok.text)?;
+    Some(self.infix_op(info))
+  }
+
+  /// Returns the current token as an infix operator with the given info.
+  pub(crate) fn infix_op(&mut self, info: Infix) -> InfixOp {
+    self.peek().expect("infix op with no tokens");
+    InfixOp {
+      info,
+      tok_idx: self.tok_idx,
+    }
+  }
+
+  /// Errors for the consecutive infix operators `prev` and `cur`.
+  pub(crate) fn same_fixity_diff_assoc(&mut self, prev: InfixOp, cur: InfixOp) {
+    let prev = self.infix_name(prev);
+    let cur = self.infix_name(cur);
+    self.error(ErrorKind::SameFixityDiffAssoc(prev, cur));
+  }
+
+  fn infix_name(&self, op: InfixOp) -> InfixName {
+    let tok = self.tokens[op.tok_idx];
+    let start = self.tokens[..op.tok_idx]
+      .iter()
+      .fold(TextSize::from(0), |ac, tok| ac + TextSize::of(tok.text));
+    InfixName {
+      name: tok.text.to_owned(),
+      range: TextRange::at(start, TextSize::of(tok.text)),
+    }
+  }
+
   pub(crate) fn is_infix(&mut self, name: &str) -> bool {
     self.fix_env.contains_key(name)
   }
@@ -384,7 +423,7 @@ impl fmt::Debug for Event {
 // sml-specific types //
 
 /// Information about an infix name.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Infix {
   /// The precedence.
   pub prec: u16,
@@ -410,6 +449,20 @@ impl Infix {
   }
 }
 
+/// An infix operator token.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct InfixOp {
+  pub(crate) info: Infix,
+  tok_idx: usize,
+}
+
+/// The name and range of an infix operator token.
+#[derive(Debug)]
+pub(crate) struct InfixName {
+  name: String,
+  range: TextRange,
+}
+
 /// Associativity for infix operators.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(missing_docs)]
@@ -449,20 +502,34 @@ impl Error {
       ErrorKind::InfixWithoutOp => 3002,
       ErrorKind::InvalidFixity(_) => 3003,
       ErrorKind::NegativeFixity => 3004,
-      ErrorKind::SameFixityDiffAssoc => 3005,
+      ErrorKind::SameFixityDiffAssoc(_, _) => 3005,
       ErrorKind::Expected(_) => 3006,
+      ErrorKind::TrailingComma => 3007,
     }
   }
 }
 
+/// The codes returned by [`Error::to_code`], with short descriptions.
+pub const ERROR_CODES: &[(u16, &str)] = &[
+  (3001, "non-infix name used as infix"),
+  (3002, "infix name used as non-infix without `op`"),
+  (3003, "invalid fixity"),
+  (3004, "negative fixity"),
+  (3005, "same fixity, different associativity"),
+  (3006, "expected something else"),
+  (3007, "trailing comma"),
+];
+
 #[derive(Debug)]
 pub(crate) enum ErrorKind {
   NotInfix,
   InfixWithoutOp,
   InvalidFixity(std::num::ParseIntError),
   NegativeFixity,
-  SameFixityDiffAssoc,
-  Expected(Expected),
+  SameFixityDiffAssoc(InfixName, InfixName),
+  TrailingComma,
+  /// all of the things that would have been valid at this token.
+  Expected(Vec<Expected>),
 }
 
 impl fmt::Display for ErrorKind {
@@ -472,15 +539,34 @@ impl fmt::Display for ErrorKind {
       ErrorKind::InfixWithoutOp => f.write_str("infix name used as non-infix without `op`"),
       ErrorKind::InvalidFixity(e) => write!(f, "invalid fixity: {e}"),
       ErrorKind::NegativeFixity => f.write_str("fixity is negative"),
-      ErrorKind::SameFixityDiffAssoc => {
-        f.write_str("consecutive infix names with same fixity but different associativity")
+      ErrorKind::SameFixityDiffAssoc(prev, cur) => write!(
+        f,
+        "consecutive infix names `{}` and `{}` with same fixity but different associativity",
+        prev.name, cur.name
+      ),
+      ErrorKind::TrailingComma => f.write_str("trailing comma"),
+      ErrorKind::Expected(es) => {
+        f.write_str("expected ")?;
+        for (idx, e) in es.iter().enumerate() {
+          if idx != 0 {
+            if es.len() > 2 {
+              f.write_str(",")?;
+            }
+            if idx + 1 == es.len() {
+              f.write_str(" or ")?;
+            } else {
+              f.write_str(" ")?;
+            }
+          }
+          e.fmt(f)?;
+        }
+        Ok(())
       }
-      ErrorKind::Expected(e) => write!(f, "expected {e}"),
     }
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub(crate) enum Expected {
   Exp,
   Lab,
@@ -489,7 +575,6 @@ pub(crate) enum Expected {
   SigExp,
   StrExp,
   Ty,
-  LRoundExpTail,
   Item,
   Kind(SK),
 }
@@ -504,7 +589,6 @@ impl fmt::Display for Expected {
       Expected::SigExp => f.write_str("a signature expression"),
       Expected::StrExp => f.write_str("a structure expression"),
       Expected::Ty => f.write_str("a type"),
-      Expected::LRoundExpTail => f.write_str("`)`, `,`, or `;`"),
       Expected::Item => f.write_str("a top-level item"),
       Expected::Kind(k) => k.fmt(f),
     }
@@ -515,18 +599,23 @@ impl fmt::Display for Expected {
 struct BuilderSink {
   builder: GreenNodeBuilder<'static>,
   range: TextRange,
+  /// the range of the last non-trivia token. errors are reported here, so that an error at the end
+  /// of the file (e.g. after `val x =`) is not reported on trailing whitespace or comments.
+  non_trivia_range: TextRange,
   errors: Vec<Error>,
   kinds: Vec<ErrorKind>,
 }
 
 impl BuilderSink {
   fn extend_errors(&mut self) {
-    let errors = std::mem::take(&mut self.kinds)
-      .into_iter()
-      .map(|kind| Error {
-        range: self.range,
-        kind,
-      });
+    let errors = std::mem::take(&mut self.kinds).into_iter().map(|kind| {
+      let range = match &kind {
+        // point at both names.
+        ErrorKind::SameFixityDiffAssoc(prev, cur) => prev.range.cover(cur.range),
+        _ => self.non_trivia_range,
+      };
+      Error { range, kind }
+    });
     self.errors.extend(errors);
   }
 
@@ -541,6 +630,7 @@ impl BuilderSink {
     let end = start + TextSize::of(token.text);
     self.range = TextRange::new(start, end);
     if !is_trivia {
+      self.non_trivia_range = self.range;
       self.extend_errors();
     }
   }
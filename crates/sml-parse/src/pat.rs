@@ -1,8 +1,8 @@
-use crate::parser::{Entered, ErrorKind, Exited, Expected, Infix, Parser};
+use crate::parser::{Entered, ErrorKind, Exited, Expected, Infix, InfixOp, Parser};
 use crate::ty::ty_annotation;
 use crate::util::{
-  comma_sep, eat_name_star, lab, must, name_star, path, path_no_infix, scon, should_break,
-  ShouldBreak,
+  close, comma_sep, eat_name_star, lab, must, name_star, path, path_no_infix, scon, should_break,
+  trailing_comma, ShouldBreak,
 };
 use sml_syntax::SyntaxKind as SK;
 
@@ -30,7 +30,7 @@ enum AtPatHd {
   /// corresponds to `ConPatState::Entered`.
   ConPatArg(Entered),
   /// we're parsing an infix pat.
-  Infix(ConPatState, Infix),
+  Infix(ConPatState, InfixOp),
 }
 
 /// kind of gross for the tricky ones (as pat, con pat with arg, infix pat).
@@ -67,16 +67,15 @@ fn pat_prec(p: &mut Parser<'_>, min_prec: PatPrec) -> Option<Exited> {
   loop {
     let ex = if at_pat_hd(p) {
       let at_pat_hd = if name_star(p, 0) {
-        let tok = p.peek().unwrap();
-        match p.get_infix(tok.text) {
+        match p.get_infix_op() {
           None => match state {
             ConPatState::Entered(en) => AtPatHd::ConPatArg(en),
             ConPatState::Exited(_) => {
               p.error(ErrorKind::NotInfix);
-              AtPatHd::Infix(state, Infix::left(0))
+              AtPatHd::Infix(state, p.infix_op(Infix::left(0)))
             }
           },
-          Some(op_info) => AtPatHd::Infix(state, op_info),
+          Some(op) => AtPatHd::Infix(state, op),
         }
       } else {
         match state {
@@ -89,21 +88,20 @@ fn pat_prec(p: &mut Parser<'_>, min_prec: PatPrec) -> Option<Exited> {
           must(p, at_pat, Expected::Pat);
           p.exit(en, SK::ConPat)
         }
-        AtPatHd::Infix(st, op_info) => {
+        AtPatHd::Infix(st, op) => {
           state = st;
-          let should_break = match min_prec {
-            PatPrec::Min | PatPrec::Or => ShouldBreak::No,
-            PatPrec::Infix(min_prec) => should_break(op_info, min_prec),
-          };
-          match should_break {
-            ShouldBreak::Yes => break,
-            ShouldBreak::No => {}
-            ShouldBreak::Error => p.error(ErrorKind::SameFixityDiffAssoc),
+          match min_prec {
+            PatPrec::Min | PatPrec::Or => {}
+            PatPrec::Infix(prev) => match should_break(op.info, prev.info) {
+              ShouldBreak::Yes => break,
+              ShouldBreak::No => {}
+              ShouldBreak::Error => p.same_fixity_diff_assoc(prev, op),
+            },
           }
           let ex = state.exit(p);
           let en = p.precede(ex);
           p.bump();
-          must(p, |p| pat_prec(p, PatPrec::Infix(op_info)), Expected::Pat);
+          must(p, |p| pat_prec(p, PatPrec::Infix(op)), Expected::Pat);
           p.exit(en, SK::InfixPat)
         }
       }
@@ -137,7 +135,7 @@ fn pat_prec(p: &mut Parser<'_>, min_prec: PatPrec) -> Option<Exited> {
 enum PatPrec {
   Min,
   Or,
-  Infix(Infix),
+  Infix(InfixOp),
 }
 
 /// when adding more cases to this, update [`at_pat_hd`]
@@ -225,17 +223,27 @@ fn at_pat_l_round(p: &mut Parser<'_>) -> SK {
     p.bump();
     return SK::ParenPat;
   }
+  let trailing = trailing_comma(p, SK::RRound);
   p.eat(SK::Comma);
   p.exit(en, SK::PatArg);
+  if trailing {
+    p.bump();
+    return SK::TuplePat;
+  }
   loop {
     let en = p.enter();
     must(p, pat, Expected::Pat);
     if p.at(SK::Comma) {
+      let trailing = trailing_comma(p, SK::RRound);
       p.bump();
       p.exit(en, SK::PatArg);
+      if trailing {
+        p.bump();
+        break;
+      }
     } else {
       p.exit(en, SK::PatArg);
-      p.eat(SK::RRound);
+      close(p, SK::RRound);
       break;
     }
   }
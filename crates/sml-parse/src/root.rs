@@ -7,9 +7,37 @@ pub(crate) fn root(p: &mut Parser<'_>) {
   while p.peek().is_some() {
     if !str_dec(p) {
       // avoid infinite loop
-      p.error(ErrorKind::Expected(Expected::Item));
+      p.error(ErrorKind::Expected(vec![Expected::Item]));
       p.bump();
+      // report only one error for the whole run of tokens that can't start an item.
+      while p.peek().map_or(false, |tok| !starts_item(tok.kind)) {
+        p.bump();
+      }
     }
   }
   p.exit(entered, SK::Root);
 }
+
+/// returns whether a top-level item may start with this, so parsing may resume there after an
+/// error.
+pub(crate) fn starts_item(kind: SK) -> bool {
+  matches!(
+    kind,
+    SK::FunctorKw
+      | SK::SignatureKw
+      | SK::StructureKw
+      | SK::LocalKw
+      | SK::DotDotDot
+      | SK::ValKw
+      | SK::FunKw
+      | SK::TypeKw
+      | SK::DatatypeKw
+      | SK::AbstypeKw
+      | SK::ExceptionKw
+      | SK::OpenKw
+      | SK::InfixKw
+      | SK::InfixrKw
+      | SK::NonfixKw
+      | SK::DoKw
+  )
+}
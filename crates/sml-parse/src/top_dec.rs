@@ -251,15 +251,19 @@ fn ty_spec(p: &mut Parser<'_>) {
 fn spec_with_tail(p: &mut Parser<'_>) -> bool {
   let en = p.enter();
   let mut ret = maybe_semi_sep(p, SK::SpecInSeq, spec_one);
-  while p.at(SK::SharingKw) {
-    ret = true;
-    let en = p.enter();
-    p.bump();
-    if p.at(SK::TypeKw) {
+  // like with `where`, the first 'sharing' must actually be 'sharing', but further ones can be
+  // 'and'. this is an SML/NJ extension.
+  if p.at(SK::SharingKw) {
+    while p.at(SK::SharingKw) || p.at(SK::AndKw) {
+      ret = true;
+      let en = p.enter();
       p.bump();
+      if p.at(SK::TypeKw) {
+        p.bump();
+      }
+      many_sep(p, SK::Eq, SK::PathEq, |p| must(p, path, Expected::Path));
+      p.exit(en, SK::SharingTail);
     }
-    many_sep(p, SK::Eq, SK::PathEq, |p| must(p, path, Expected::Path));
-    p.exit(en, SK::SharingTail);
   }
   p.exit(en, SK::SpecWithTail);
   ret
@@ -1,4 +1,5 @@
 use crate::parser::{Assoc, ErrorKind, Exited, Expected, Infix, Parser};
+use crate::root::starts_item;
 use sml_syntax::{token::Token, SyntaxKind as SK};
 
 /// emits an error and returns false if `f` failed. otherwise returns `true`.
@@ -8,7 +9,7 @@ where
 {
   let ret = f(p).is_some();
   if !ret {
-    p.error(ErrorKind::Expected(e));
+    p.error(ErrorKind::Expected(vec![e]));
   }
   ret
 }
@@ -41,7 +42,7 @@ where
 ///
 /// - always uses `,` as the separator
 /// - allows 0 occurrences of `f`
-/// - returns only after eating `end`
+/// - returns only after eating `end`, or recovering with [`close`]
 pub(crate) fn comma_sep<'a, F>(p: &mut Parser<'a>, end: SK, wrap: SK, mut f: F)
 where
   F: FnMut(&mut Parser<'a>),
@@ -54,16 +55,62 @@ where
     let en = p.enter();
     f(p);
     if p.at(SK::Comma) {
+      let trailing = trailing_comma(p, end);
       p.bump();
       p.exit(en, wrap);
+      if trailing {
+        p.bump();
+        break;
+      }
     } else {
       p.exit(en, wrap);
-      p.eat(end);
+      close(p, end);
       break;
     }
   }
 }
 
+/// if we're at a `,` right before `end`, errors at the `,` and returns true. then the caller should
+/// eat both.
+pub(crate) fn trailing_comma(p: &mut Parser<'_>, end: SK) -> bool {
+  let ret = p.at(SK::Comma) && p.at_n(1, end);
+  if ret {
+    p.error(ErrorKind::TrailingComma);
+  }
+  ret
+}
+
+/// eats `end`, which closes some brackets. if we're not at `end`, errors, and then skips tokens
+/// until the `end` matching the opening bracket, so the rest of the enclosing item still parses.
+/// stops early without eating anything else if a different closing bracket or a top-level item is
+/// found first, since then `end` is probably just missing.
+pub(crate) fn close(p: &mut Parser<'_>, end: SK) {
+  if p.eat(end).is_some() {
+    return;
+  }
+  let mut depth = 0usize;
+  while let Some(tok) = p.peek() {
+    match tok.kind {
+      SK::LRound | SK::LSquare | SK::LCurly | SK::LetKw => depth += 1,
+      SK::RRound | SK::RSquare | SK::RCurly | SK::EndKw => {
+        if depth == 0 {
+          if tok.kind == end {
+            p.bump();
+          }
+          return;
+        }
+        depth -= 1;
+      }
+      kind => {
+        if depth == 0 && (starts_item(kind) || kind == SK::InKw) {
+          return;
+        }
+      }
+    }
+    p.bump();
+  }
+}
+
 /// if `f` returns false, it consumed nothing. (but it may consume nothing and return `true`.) stops
 /// if `sep` is not found. `wrap` will wrap both `f` and `sep` if present.
 ///
@@ -156,7 +203,7 @@ pub(crate) fn lab(p: &mut Parser<'_>) {
   if p.at(SK::Name) || p.at(SK::Star) || p.at(SK::IntLit) {
     p.bump();
   } else {
-    p.error(ErrorKind::Expected(Expected::Lab));
+    p.error(ErrorKind::Expected(vec![Expected::Lab]));
   }
 }
 
@@ -175,7 +222,7 @@ pub(crate) fn eat_name_star<'a>(p: &mut Parser<'a>) -> Option<Token<'a, SK>> {
   if name_star(p, 0) {
     Some(p.bump())
   } else {
-    p.error(ErrorKind::Expected(Expected::Kind(SK::Name)));
+    p.error(ErrorKind::Expected(vec![Expected::Kind(SK::Name)]));
     None
   }
 }
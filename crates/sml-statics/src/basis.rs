@@ -1,8 +1,9 @@
 //! Bases.
 
+use crate::get_env::get_val_info;
 use crate::types::{
-  Bs, CompositeOverload, Env, EnvLike as _, EnvStack, FunEnv, IdStatus, Overload, RecordTy, SigEnv,
-  StrEnv, Sym, Syms, Ty, TyEnv, TyInfo, TyScheme, TyVarKind, ValEnv, ValInfo,
+  Bs, CompositeOverload, Def, Env, EnvLike as _, EnvStack, Equality, FunEnv, IdStatus, Overload,
+  RecordTy, SigEnv, StrEnv, Sym, Syms, Ty, TyEnv, TyInfo, TyScheme, TyVarKind, ValEnv, ValInfo,
 };
 use fast_hash::map;
 
@@ -53,17 +54,30 @@ impl Basis {
     self.inner.as_mut_fun_env().insert(name, env);
     true
   }
+
+  /// Returns the definition site of the value at `path` in this, if there is one.
+  pub fn get_val_def(&self, path: &sml_hir::Path) -> Option<Def> {
+    get_val_info(&self.inner.env, path).ok()??.def
+  }
 }
 
 /// Returns the minimal basis and symbols.
 ///
 /// This is distinct from std_basis in analysis. This (mostly) just has the definitions that can't
 /// be expressed with regular SML files, like `int` and `real` and `string`. Also `bool` and `list`
-/// because rebinding their constructor names is forbidden.
+/// because rebinding their constructor names is forbidden, and `vector` because vector expressions
+/// and patterns have that type.
 pub fn minimal() -> (Syms, Basis) {
   let mut syms = Syms::default();
-  for sym in [Sym::INT, Sym::WORD, Sym::REAL, Sym::CHAR, Sym::STRING] {
-    insert_special(&mut syms, sym, basic_datatype(Ty::zero(sym), &[]));
+  let basics = [
+    (Sym::INT, Equality::Args),
+    (Sym::WORD, Equality::Args),
+    (Sym::REAL, Equality::Never),
+    (Sym::CHAR, Equality::Args),
+    (Sym::STRING, Equality::Args),
+  ];
+  for (sym, equality) in basics {
+    insert_special(&mut syms, sym, basic_datatype(Ty::zero(sym), &[], equality));
   }
   syms.overloads().int.push(Sym::INT);
   syms.overloads().word.push(Sym::WORD);
@@ -73,7 +87,7 @@ pub fn minimal() -> (Syms, Basis) {
   insert_special(
     &mut syms,
     Sym::BOOL,
-    basic_datatype(Ty::BOOL, &["true", "false"]),
+    basic_datatype(Ty::BOOL, &["true", "false"], Equality::Args),
   );
   let list_info = {
     let list = |a: Ty| Ty::Con(vec![a], Sym::LIST);
@@ -83,6 +97,7 @@ pub fn minimal() -> (Syms, Basis) {
       ty_scheme: alpha_list.clone(),
       val_env: datatype_ve([("nil", alpha_list), ("::", cons)]),
       def: None,
+      equality: Equality::Args,
     }
   };
   insert_special(&mut syms, Sym::LIST, list_info);
@@ -93,18 +108,31 @@ pub fn minimal() -> (Syms, Basis) {
       ty_scheme: TyScheme::one(|a| (ref_(a), None)),
       val_env: datatype_ve([("ref", con)]),
       def: None,
+      // refs admit equality no matter their contents.
+      equality: Equality::Always,
     }
   };
   insert_special(&mut syms, Sym::REF, ref_info);
-  let aliases = [("unit", Ty::Record(RecordTy::new())), ("exn", Ty::EXN)];
+  let vector_info = TyInfo {
+    ty_scheme: TyScheme::one(|a| (Ty::Con(vec![a], Sym::VECTOR), None)),
+    val_env: ValEnv::default(),
+    def: None,
+    equality: Equality::Args,
+  };
+  insert_special(&mut syms, Sym::VECTOR, vector_info);
+  let aliases = [
+    ("unit", Ty::Record(RecordTy::new()), Equality::Args),
+    ("exn", Ty::EXN, Equality::Never),
+  ];
   let ty_env: TyEnv = syms
     .iter()
     .map(|(a, b)| (a.clone(), b.clone()))
-    .chain(aliases.into_iter().map(|(name, ty)| {
+    .chain(aliases.into_iter().map(|(name, ty, equality)| {
       let ti = TyInfo {
         ty_scheme: TyScheme::zero(ty),
         val_env: ValEnv::default(),
         def: None,
+        equality,
       };
       (sml_hir::Name::new(name), ti)
     }))
@@ -170,13 +198,14 @@ fn insert_special(syms: &mut Syms, sym: Sym, ty_info: TyInfo) {
   syms.finish(started, ty_info);
 }
 
-fn basic_datatype(ty: Ty, ctors: &[&str]) -> TyInfo {
+fn basic_datatype(ty: Ty, ctors: &[&str], equality: Equality) -> TyInfo {
   let ty_scheme = TyScheme::zero(ty);
   let val_env = datatype_ve(ctors.iter().map(|&x| (x, ty_scheme.clone())));
   TyInfo {
     ty_scheme,
     val_env,
     def: None,
+    equality,
   }
 }
 
@@ -0,0 +1,93 @@
+//! Completions from the names in scope in an environment.
+
+use crate::get_env::get_env;
+use crate::info::SymbolKind;
+use crate::types::{Env, EnvStack, MetaVarInfo, MetaVarNames, Syms};
+use fast_hash::FxHashSet;
+
+/// A name that could complete what is being written.
+#[derive(Debug, Clone)]
+pub struct CompletionItem {
+  /// The name.
+  pub label: String,
+  /// What kind of thing the name refers to.
+  pub kind: SymbolKind,
+  /// Extra detail, like the type scheme of a value.
+  pub detail: Option<String>,
+}
+
+/// if `structures` is empty, returns all the names in scope in `env`, keeping only the innermost of
+/// shadowed names. else returns only the members of the structure reached by `structures`.
+pub(crate) fn get(
+  syms: &Syms,
+  mv_info: &MetaVarInfo,
+  env: &EnvStack,
+  structures: &[sml_hir::Name],
+) -> Vec<CompletionItem> {
+  let mut cx = Cx {
+    syms,
+    mv_info,
+    seen: FxHashSet::default(),
+    ret: Vec::new(),
+  };
+  if structures.is_empty() {
+    for env in env.iter_rev() {
+      cx.add_env(env);
+    }
+  } else if let Ok(Some(env)) = get_env(env, structures) {
+    cx.add_env(env);
+  }
+  let mut ret = cx.ret;
+  ret.sort_unstable_by(|a, b| a.label.cmp(&b.label));
+  ret
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Namespace {
+  Val,
+  Ty,
+  Str,
+}
+
+struct Cx<'a> {
+  syms: &'a Syms,
+  mv_info: &'a MetaVarInfo,
+  seen: FxHashSet<(Namespace, &'a sml_hir::Name)>,
+  ret: Vec<CompletionItem>,
+}
+
+impl<'a> Cx<'a> {
+  fn add_env(&mut self, env: &'a Env) {
+    for (name, val_info) in env.val_env.iter() {
+      if !self.seen.insert((Namespace::Val, name)) {
+        continue;
+      }
+      let mut mvs = MetaVarNames::new(self.mv_info);
+      mvs.extend_for(&val_info.ty_scheme.ty);
+      let detail = val_info.ty_scheme.display(&mvs, self.syms).to_string();
+      self.ret.push(CompletionItem {
+        label: name.as_str().to_owned(),
+        kind: val_info.id_status.symbol_kind(),
+        detail: Some(detail),
+      });
+    }
+    for name in env.ty_env.keys() {
+      if self.seen.insert((Namespace::Ty, name)) {
+        self.ret.push(CompletionItem {
+          label: name.as_str().to_owned(),
+          kind: SymbolKind::Type,
+          detail: None,
+        });
+      }
+    }
+    for name in env.str_env.keys() {
+      if self.seen.insert((Namespace::Str, name)) {
+        self.ret.push(CompletionItem {
+          label: name.as_str().to_owned(),
+          kind: SymbolKind::Structure,
+          detail: None,
+        });
+      }
+    }
+  }
+}
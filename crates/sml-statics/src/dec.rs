@@ -3,7 +3,7 @@ use crate::get_env::{get_env_from_str_path, get_ty_info, get_val_info};
 use crate::pat_match::Pat;
 use crate::st::St;
 use crate::types::{
-  generalize, generalize_fixed, Cx, Env, EnvLike as _, FixedTyVars, Generalizable,
+  generalize, generalize_fixed, Cx, Env, EnvLike as _, Equality, FixedTyVars, Generalizable,
   HasRecordMetaVars, IdStatus, StartedSym, Ty, TyEnv, TyInfo, TyScheme, ValEnv, ValInfo,
 };
 use crate::unify::unify;
@@ -22,6 +22,9 @@ pub(crate) fn get(
     Some(x) => x,
     None => return,
   };
+  if !matches!(ars.dec[dec], sml_hir::Dec::Seq(_)) {
+    st.info().insert_env(dec.into(), &cx.env);
+  }
   match &ars.dec[dec] {
     sml_hir::Dec::Hole => st.err(dec, ErrorKind::DecHole),
     // sml_def(15)
@@ -74,8 +77,8 @@ pub(crate) fn get(
       for (val_bind, (pm_pat, mut want)) in val_binds[idx..].iter().zip(got_pats) {
         // sml_def(26)
         if let Some(exp) = val_bind.exp {
-          if !matches!(ars.exp[exp], sml_hir::Exp::Fn(_)) {
-            st.err(dec, ErrorKind::ValRecExpNotFn);
+          if !is_fn(ars, val_bind.exp) {
+            st.err(exp, ErrorKind::ValRecExpNotFn);
           }
         }
         let got = exp::get(st, &cx, ars, val_bind.exp);
@@ -95,9 +98,19 @@ pub(crate) fn get(
         let mv_g = st.meta_gen.generalizer();
         let g = generalize(mv_g, st.subst(), fixed.clone(), &mut val_info.ty_scheme);
         if expansive(&cx, ars, exp) && !val_info.ty_scheme.bound_vars.is_empty() {
+          // only suggest an annotation if this is the only name bound to the exp, and the
+          // suggestion would have more than just a hole.
+          let annotation = (src_exp.values().filter(|&&e| e == exp).count() == 1)
+            .then(|| {
+              let mut ty = val_info.ty_scheme.ty.clone();
+              bound_vars_to_holes(&mut ty);
+              ty
+            })
+            .filter(|ty| !matches!(ty, Ty::None))
+            .map(|ty| (name.clone(), ty));
           st.err(
             exp.map_or(sml_hir::Idx::Dec(dec), sml_hir::Idx::Exp),
-            ErrorKind::BindPolymorphicExpansiveExp,
+            ErrorKind::BindPolymorphicExpansiveExp(annotation),
           );
         }
         if let Err(HasRecordMetaVars) = g {
@@ -235,6 +248,7 @@ pub(crate) fn add_fixed_ty_vars(
   let mut ret = FixedTyVars::default();
   for ty_var in ty_vars.iter() {
     let fv = st.gen_fixed_var(ty_var.clone());
+    st.set_fixed_var_site(fv.clone(), idx);
     if cx.fixed.insert(ty_var.clone(), fv.clone()).is_some() {
       let e = ErrorKind::Duplicate(Item::TyVar, ty_var.as_name().clone());
       st.err(idx, e);
@@ -252,14 +266,31 @@ fn get_ty_binds(
   ty_binds: &[sml_hir::TyBind],
   idx: sml_hir::Idx,
 ) {
+  let recursive = recursive_ty_binds(cx, ars, ty_binds);
   for ty_bind in ty_binds {
     let fixed = add_fixed_ty_vars(st, cx, &ty_bind.ty_vars, idx);
-    let mut ty_scheme = TyScheme::zero(ty::get(st, cx, ars, ty_bind.ty));
+    let ty = if recursive.contains(&ty_bind.name) {
+      let is_self = is_self_ty_alias(ars, ty_bind);
+      st.err(
+        idx,
+        ErrorKind::RecursiveTyAlias(ty_bind.name.clone(), is_self),
+      );
+      Ty::None
+    } else {
+      ty::get(st, cx, ars, ty_bind.ty)
+    };
+    let mut ty_scheme = TyScheme::zero(ty);
     generalize_fixed(fixed, &mut ty_scheme);
+    let equality = if ty_scheme.ty.admits_equality(&|sym| st.syms.equality(&sym)) {
+      Equality::Args
+    } else {
+      Equality::Never
+    };
     let ty_info = TyInfo {
       ty_scheme,
       val_env: ValEnv::default(),
       def: st.def(idx),
+      equality,
     };
     if let Some(e) = ins_no_dupe(ty_env, ty_bind.name.clone(), ty_info, Item::Ty) {
       st.err(idx, e)
@@ -270,6 +301,94 @@ fn get_ty_binds(
   }
 }
 
+/// returns the names bound by the `ty_binds` that are (transitively) defined in terms of themselves.
+/// since type abbreviations are not recursive, the references would otherwise be reported as
+/// undefined.
+fn recursive_ty_binds(
+  cx: &Cx,
+  ars: &sml_hir::Arenas,
+  ty_binds: &[sml_hir::TyBind],
+) -> FxHashSet<sml_hir::Name> {
+  let names: FxHashSet<_> = ty_binds
+    .iter()
+    .map(|ty_bind| &ty_bind.name)
+    .filter(|name| cx.env.get_ty(name).is_none())
+    .collect();
+  let graph: FxHashMap<_, _> = ty_binds
+    .iter()
+    .map(|ty_bind| {
+      let mut refs = FxHashSet::<&sml_hir::Name>::default();
+      ty_refs(ars, &names, &mut refs, ty_bind.ty);
+      (&ty_bind.name, refs)
+    })
+    .collect();
+  names
+    .iter()
+    .filter(|&&name| {
+      let mut visited = FxHashSet::<&sml_hir::Name>::default();
+      let mut stack: Vec<_> = graph[name].iter().copied().collect();
+      while let Some(cur) = stack.pop() {
+        if cur == name {
+          return true;
+        }
+        if visited.insert(cur) {
+          stack.extend(graph.get(cur).into_iter().flatten().copied());
+        }
+      }
+      false
+    })
+    .map(|&name| name.clone())
+    .collect()
+}
+
+/// returns whether the type of `ty_bind` is exactly the type being defined, like `type t = t` or
+/// `type 'a t = 'a t`.
+fn is_self_ty_alias(ars: &sml_hir::Arenas, ty_bind: &sml_hir::TyBind) -> bool {
+  let (args, path) = match ty_bind.ty.map(|ty| &ars.ty[ty]) {
+    Some(sml_hir::Ty::Con(args, path)) => (args, path),
+    _ => return false,
+  };
+  path.structures().is_empty()
+    && *path.last() == ty_bind.name
+    && args.len() == ty_bind.ty_vars.len()
+    && args.iter().zip(ty_bind.ty_vars.iter()).all(|(&arg, ty_var)| {
+      matches!(arg.map(|arg| &ars.ty[arg]), Some(sml_hir::Ty::Var(v)) if v == ty_var)
+    })
+}
+
+/// adds the unqualified type names in `ty` which are in `names` to `ac`.
+fn ty_refs<'a>(
+  ars: &'a sml_hir::Arenas,
+  names: &FxHashSet<&sml_hir::Name>,
+  ac: &mut FxHashSet<&'a sml_hir::Name>,
+  ty: sml_hir::TyIdx,
+) {
+  let ty = match ty {
+    Some(x) => x,
+    None => return,
+  };
+  match &ars.ty[ty] {
+    sml_hir::Ty::Hole | sml_hir::Ty::Var(_) => {}
+    sml_hir::Ty::Record(rows) => {
+      for &(_, ty) in rows.iter() {
+        ty_refs(ars, names, ac, ty);
+      }
+    }
+    sml_hir::Ty::Con(args, path) => {
+      if path.structures().is_empty() && names.contains(path.last()) {
+        ac.insert(path.last());
+      }
+      for &ty in args.iter() {
+        ty_refs(ars, names, ac, ty);
+      }
+    }
+    sml_hir::Ty::Fn(param, res) => {
+      ty_refs(ars, names, ac, *param);
+      ty_refs(ars, names, ac, *res);
+    }
+  }
+}
+
 struct Datatype {
   started: StartedSym,
   fixed: FixedTyVars,
@@ -297,7 +416,9 @@ pub(crate) fn get_dat_binds(
     // just create the fixed ty vars, do not bring them into the scope of the cx yet.
     let mut fixed = FixedTyVars::default();
     for ty_var in dat_bind.ty_vars.iter() {
-      fixed.insert(st.gen_fixed_var(ty_var.clone()));
+      let fv = st.gen_fixed_var(ty_var.clone());
+      st.set_fixed_var_site(fv.clone(), idx);
+      fixed.insert(fv);
     }
     let out_ty = Ty::Con(
       fixed.iter().map(|x| Ty::FixedVar(x.clone())).collect(),
@@ -313,6 +434,7 @@ pub(crate) fn get_dat_binds(
       ty_scheme: ty_scheme.clone(),
       val_env: ValEnv::default(),
       def: st.def(idx),
+      equality: Equality::Args,
     };
     if let Some(e) = ins_no_dupe(&mut fake_ty_env, dat_bind.name.clone(), ty_info, Item::Ty) {
       st.err(idx, e);
@@ -350,6 +472,7 @@ pub(crate) fn get_dat_binds(
     datatypes.len(),
     "we created datatypes from a for loop over dat_binds"
   );
+  let mut finished = Vec::<(StartedSym, sml_hir::Name, TyInfo)>::with_capacity(datatypes.len());
   // sml_def(28), sml_def(81)
   for (dat_bind, datatype) in dat_binds.iter().zip(datatypes) {
     // bring the type variables for this datatype into scope.
@@ -385,16 +508,67 @@ pub(crate) fn get_dat_binds(
       ty_scheme: datatype.ty_scheme,
       val_env,
       def: st.def(idx),
+      equality: Equality::Args,
     };
-    st.syms.finish(datatype.started, ty_info.clone());
-    ty_env.insert(dat_bind.name.clone(), ty_info);
+    finished.push((datatype.started, dat_bind.name.clone(), ty_info));
     for ty_var in dat_bind.ty_vars.iter() {
       cx.fixed.remove(ty_var);
     }
   }
+  // a datatype admits equality (when its type arguments do) if the arguments of all of its
+  // constructors do. start by assuming every datatype here does, since they may refer to each
+  // other, then stop assuming that for those that don't until nothing changes.
+  let mut equality: FxHashMap<_, _> = finished
+    .iter()
+    .map(|(started, _, _)| (started.sym(), Equality::Args))
+    .collect();
+  loop {
+    let mut changed = false;
+    for (started, _, ty_info) in finished.iter() {
+      let sym = started.sym();
+      if equality[&sym] == Equality::Never {
+        continue;
+      }
+      let get = |sym| {
+        equality
+          .get(&sym)
+          .copied()
+          .unwrap_or_else(|| st.syms.equality(&sym))
+      };
+      let admits = ty_info.val_env.values().all(|vi| match &vi.ty_scheme.ty {
+        Ty::Fn(param, _) => param.admits_equality(&get),
+        _ => true,
+      });
+      if !admits {
+        equality.insert(sym, Equality::Never);
+        changed = true;
+      }
+    }
+    if !changed {
+      break;
+    }
+  }
+  for (started, name, mut ty_info) in finished {
+    ty_info.equality = equality[&started.sym()];
+    st.syms.finish(started, ty_info.clone());
+    ty_env.insert(name, ty_info);
+  }
   (ty_env, big_val_env)
 }
 
+/// returns whether `exp` is a `fn` expression, possibly wrapped in type annotations.
+fn is_fn(ars: &sml_hir::Arenas, exp: sml_hir::ExpIdx) -> bool {
+  let exp = match exp {
+    Some(x) => x,
+    None => return true,
+  };
+  match &ars.exp[exp] {
+    sml_hir::Exp::Fn(_) => true,
+    sml_hir::Exp::Typed(exp, _) => is_fn(ars, *exp),
+    _ => false,
+  }
+}
+
 fn expansive(cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::ExpIdx) -> bool {
   let exp = match exp {
     Some(x) => x,
@@ -406,6 +580,7 @@ fn expansive(cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::ExpIdx) -> bool {
     }
     sml_hir::Exp::Let(_, _) | sml_hir::Exp::Raise(_) | sml_hir::Exp::Handle(_, _) => true,
     sml_hir::Exp::Record(rows) => rows.iter().any(|&(_, exp)| expansive(cx, ars, exp)),
+    sml_hir::Exp::Vector(exps) => exps.iter().any(|&exp| expansive(cx, ars, exp)),
     sml_hir::Exp::App(func, arg) => !constructor(cx, ars, *func) || expansive(cx, ars, *arg),
     sml_hir::Exp::Typed(exp, _) => expansive(cx, ars, *exp),
   }
@@ -425,7 +600,8 @@ fn constructor(cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::ExpIdx) -> bool {
     | sml_hir::Exp::App(_, _)
     | sml_hir::Exp::Handle(_, _)
     | sml_hir::Exp::Raise(_)
-    | sml_hir::Exp::Fn(_) => false,
+    | sml_hir::Exp::Fn(_)
+    | sml_hir::Exp::Vector(_) => false,
     sml_hir::Exp::Record(rows) => rows.iter().any(|&(_, exp)| constructor(cx, ars, exp)),
     sml_hir::Exp::Typed(exp, _) => constructor(cx, ars, *exp),
     sml_hir::Exp::Path(path) => {
@@ -439,3 +615,18 @@ fn constructor(cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::ExpIdx) -> bool {
     }
   }
 }
+
+/// replaces every bound type variable in `ty` with a hole, to suggest a non-polymorphic
+/// annotation.
+fn bound_vars_to_holes(ty: &mut Ty) {
+  match ty {
+    Ty::BoundVar(_) => *ty = Ty::None,
+    Ty::None | Ty::MetaVar(_) | Ty::FixedVar(_) => {}
+    Ty::Record(rows) => rows.values_mut().for_each(bound_vars_to_holes),
+    Ty::Con(args, _) => args.iter_mut().for_each(bound_vars_to_holes),
+    Ty::Fn(param, res) => {
+      bound_vars_to_holes(param);
+      bound_vars_to_holes(res);
+    }
+  }
+}
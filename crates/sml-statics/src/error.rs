@@ -1,5 +1,5 @@
 use crate::pat_match::{Con, Pat, VariantName};
-use crate::types::{MetaTyVar, MetaVarInfo, MetaVarNames, Sym, Syms, Ty};
+use crate::types::{MetaTyVar, MetaVarInfo, MetaVarNames, Sym, Syms, Ty, TyScheme};
 use fmt_util::{comma_seq, sep_seq};
 use pattern_match::RawPat;
 use std::fmt;
@@ -32,6 +32,27 @@ impl Error {
     }
   }
 
+  /// Returns the severity for this.
+  pub fn severity(&self) -> config::Severity {
+    match self.kind {
+      ErrorKind::UnreachablePattern
+      | ErrorKind::NonExhaustiveCase(_)
+      | ErrorKind::NonExhaustiveBinding(_) => config::Severity::Warning,
+      _ => config::Severity::Error,
+    }
+  }
+
+  /// Returns another index relevant to this error, and a message for it.
+  pub fn related(&self) -> Option<(sml_hir::Idx, &'static str)> {
+    match self.kind {
+      ErrorKind::MismatchedTypes(_, _, Some(binding)) => Some((
+        binding,
+        "the type of this binding was not generalized, so every use must have the same type",
+      )),
+      _ => None,
+    }
+  }
+
   /// Return the code for this.
   pub fn to_code(&self) -> u16 {
     match self.kind {
@@ -40,7 +61,7 @@ impl Error {
       ErrorKind::Missing(_, _) => 5003,
       ErrorKind::Extra(_, _) => 5004,
       ErrorKind::Circularity(_, _) => 5005,
-      ErrorKind::MismatchedTypes(_, _) => 5006,
+      ErrorKind::MismatchedTypes(_, _, _) => 5006,
       ErrorKind::AppLhsNotFn(_) => 5007,
       ErrorKind::DuplicateLab(_) => 5008,
       ErrorKind::RealPat => 5009,
@@ -48,8 +69,8 @@ impl Error {
       ErrorKind::NonExhaustiveCase(_) => 5011,
       ErrorKind::NonExhaustiveBinding(_) => 5012,
       ErrorKind::PatValIdStatus => 5013,
-      ErrorKind::ConPatMustNotHaveArg => 5014,
-      ErrorKind::ConPatMustHaveArg => 5015,
+      ErrorKind::ConPatMustNotHaveArg(_) => 5014,
+      ErrorKind::ConPatMustHaveArg(_, _) => 5015,
       ErrorKind::InvalidAsPatName(_) => 5016,
       ErrorKind::TyNameEscape(_) => 5017,
       ErrorKind::ValRecExpNotFn => 5018,
@@ -63,12 +84,54 @@ impl Error {
       ErrorKind::ExpHole(_) => 5026,
       ErrorKind::TyHole => 5027,
       ErrorKind::DecHole => 5028,
-      ErrorKind::BindPolymorphicExpansiveExp => 5029,
+      ErrorKind::BindPolymorphicExpansiveExp(_) => 5029,
+      ErrorKind::RecursiveTyAlias(_, _) => 5031,
+      ErrorKind::SharingTyAlias => 5035,
+      ErrorKind::NotEqTy(_) => 5037,
+      ErrorKind::NotEqTyRealization(_, _) => 5039,
       ErrorKind::Unsupported(_) => 5999,
     }
   }
 }
 
+/// The codes returned by [`Error::to_code`], with short descriptions.
+pub const ERROR_CODES: &[(u16, &str)] = &[
+  (5001, "undefined name"),
+  (5002, "duplicate"),
+  (5003, "missing from structure, but in signature"),
+  (5004, "extra in structure, not in signature"),
+  (5005, "circular type"),
+  (5006, "mismatched types"),
+  (5007, "applying a non-function"),
+  (5008, "duplicate label"),
+  (5009, "real literal used as pattern"),
+  (5010, "unreachable pattern"),
+  (5011, "non-exhaustive case"),
+  (5012, "non-exhaustive binding"),
+  (5013, "value used as pattern"),
+  (5014, "constructor pattern with unexpected argument"),
+  (5015, "constructor pattern without argument"),
+  (5016, "invalid `as` pattern name"),
+  (5017, "type name escapes its scope"),
+  (5018, "`val rec` expression not `fn`"),
+  (5019, "wrong number of type arguments"),
+  (5020, "exception copy of non-exception"),
+  (5021, "rebinding a name that may not be rebound"),
+  (5022, "wrong identifier status"),
+  (5023, "unresolved record type"),
+  (5024, "or pattern alternatives bind different names"),
+  (5025, "declaration not allowed here"),
+  (5026, "expression hole"),
+  (5027, "type hole"),
+  (5028, "declaration hole"),
+  (5029, "polymorphic binding of expansive expression"),
+  (5031, "recursive type alias"),
+  (5035, "sharing constraint on type alias"),
+  (5037, "not an equality type"),
+  (5039, "equality type realized with non-equality type"),
+  (5999, "unsupported language construct"),
+];
+
 #[derive(Debug)]
 pub(crate) enum ErrorKind {
   Undefined(Item, sml_hir::Name),
@@ -76,7 +139,9 @@ pub(crate) enum ErrorKind {
   Missing(Item, sml_hir::Name),
   Extra(Item, sml_hir::Name),
   Circularity(MetaTyVar, Ty),
-  MismatchedTypes(Ty, Ty),
+  /// the last is the binding of a value whose type was not generalized, if the mismatch is from
+  /// using that value at a different type than another use.
+  MismatchedTypes(Ty, Ty, Option<sml_hir::Idx>),
   AppLhsNotFn(Ty),
   DuplicateLab(sml_hir::Lab),
   RealPat,
@@ -84,8 +149,9 @@ pub(crate) enum ErrorKind {
   NonExhaustiveCase(Vec<Pat>),
   NonExhaustiveBinding(Vec<Pat>),
   PatValIdStatus,
-  ConPatMustNotHaveArg,
-  ConPatMustHaveArg,
+  ConPatMustNotHaveArg(sml_hir::Name),
+  /// the constructor and the type of its argument.
+  ConPatMustHaveArg(sml_hir::Name, Ty),
   InvalidAsPatName(sml_hir::Name),
   TyNameEscape(Sym),
   ValRecExpNotFn,
@@ -99,7 +165,14 @@ pub(crate) enum ErrorKind {
   ExpHole(Ty),
   TyHole,
   DecHole,
-  BindPolymorphicExpansiveExp,
+  /// the name and type to suggest annotating the binding with, if any.
+  BindPolymorphicExpansiveExp(Option<(sml_hir::Name, Ty)>),
+  /// the bool is whether the abbreviation is directly of itself, like `type t = t`.
+  RecursiveTyAlias(sml_hir::Name, bool),
+  SharingTyAlias,
+  NotEqTy(Ty),
+  /// the `eqtype` and the type realizing it, which does not admit equality.
+  NotEqTyRealization(sml_hir::Path, TyScheme),
   /// must be last
   Unsupported(&'static str),
 }
@@ -149,7 +222,7 @@ impl fmt::Display for ErrorKindDisplay<'_> {
         write!(f, "attempted to a set a type variable {name} ")?;
         write!(f, "to a type containing that variable: {ty}")
       }
-      ErrorKind::MismatchedTypes(want, got) => {
+      ErrorKind::MismatchedTypes(want, got, _) => {
         let mut mvs = MetaVarNames::new(self.mv_info);
         mvs.extend_for(want);
         mvs.extend_for(got);
@@ -176,8 +249,21 @@ impl fmt::Display for ErrorKindDisplay<'_> {
       ErrorKind::NonExhaustiveCase(pats) => non_exhaustive(f, self.syms, pats, "case"),
       ErrorKind::NonExhaustiveBinding(pats) => non_exhaustive(f, self.syms, pats, "binding"),
       ErrorKind::PatValIdStatus => f.write_str("value binding used as a pattern"),
-      ErrorKind::ConPatMustNotHaveArg => f.write_str("unexpected argument for constructor pattern"),
-      ErrorKind::ConPatMustHaveArg => f.write_str("missing argument for constructor pattern"),
+      ErrorKind::ConPatMustNotHaveArg(name) => {
+        write!(
+          f,
+          "unexpected argument for constructor pattern: {name} takes no argument"
+        )
+      }
+      ErrorKind::ConPatMustHaveArg(name, ty) => {
+        let mut mvs = MetaVarNames::new(self.mv_info);
+        mvs.extend_for(ty);
+        let ty = ty.display(&mvs, self.syms);
+        write!(
+          f,
+          "missing argument for constructor pattern: {name} expects an argument of type {ty}"
+        )
+      }
       ErrorKind::InvalidAsPatName(name) => write!(f, "invalid `as` pat name: {name}"),
       ErrorKind::TyNameEscape(sym) => write!(
         f,
@@ -210,8 +296,34 @@ impl fmt::Display for ErrorKindDisplay<'_> {
       }
       ErrorKind::TyHole => f.write_str("type hole"),
       ErrorKind::DecHole => f.write_str("declaration hole"),
-      ErrorKind::BindPolymorphicExpansiveExp => {
-        f.write_str("cannot bind expansive polymorphic expression")
+      ErrorKind::BindPolymorphicExpansiveExp(annotation) => {
+        f.write_str("cannot bind expansive polymorphic expression")?;
+        if let Some((name, ty)) = annotation {
+          let mut mvs = MetaVarNames::new(self.mv_info);
+          mvs.extend_for(ty);
+          let ty = ty.display(&mvs, self.syms);
+          write!(f, "; annotate the binding, e.g. `val {name} : {ty} = ...`")?;
+        }
+        Ok(())
+      }
+      ErrorKind::RecursiveTyAlias(name, false) => write!(f, "recursive type abbreviation: {name}"),
+      ErrorKind::RecursiveTyAlias(name, true) => {
+        write!(f, "type abbreviation refers to itself: {name}")
+      }
+      ErrorKind::SharingTyAlias => f.write_str("cannot share between type abbreviations"),
+      ErrorKind::NotEqTy(ty) => {
+        let mut mvs = MetaVarNames::new(self.mv_info);
+        mvs.extend_for(ty);
+        let ty = ty.display(&mvs, self.syms);
+        write!(f, "operator = requires an equality type, found {ty}")
+      }
+      ErrorKind::NotEqTyRealization(path, ty_scheme) => {
+        let mvs = MetaVarNames::new(self.mv_info);
+        let ty_scheme = ty_scheme.display(&mvs, self.syms);
+        write!(
+          f,
+          "cannot realize equality type {path} with non-equality type {ty_scheme}"
+        )
       }
       ErrorKind::Unsupported(s) => write!(f, "unsupported language construct: {s}"),
     }
@@ -274,7 +386,9 @@ impl<'a> fmt::Display for PatDisplay<'a> {
           allows_other,
         } => {
           assert_eq!(labels.len(), args.len());
+          // a record with only the label `1` is not a tuple, but the empty record is `()`.
           let is_tuple = !*allows_other
+            && labels.len() != 1
             && labels
               .iter()
               .enumerate()
@@ -307,6 +421,18 @@ impl<'a> fmt::Display for PatDisplay<'a> {
             f.write_str("}")?;
           }
         }
+        Con::Vector(_) => {
+          f.write_str("#[")?;
+          comma_seq(
+            f,
+            args.iter().map(|pat| PatDisplay {
+              pat,
+              syms: self.syms,
+              prec: PatPrec::Min,
+            }),
+          )?;
+          f.write_str("]")?;
+        }
         Con::Variant(_, name) => {
           let name = match name {
             VariantName::Name(name) => name.as_str(),
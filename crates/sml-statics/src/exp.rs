@@ -1,12 +1,13 @@
 use crate::error::{ErrorKind, Item};
 use crate::get_env::get_val_info;
-use crate::info::TyEntry;
+use crate::info::{SymbolKind, TyEntry};
 use crate::pat_match::Pat;
 use crate::st::St;
 use crate::types::{
-  Cx, Def, Env, EnvLike as _, Generalizable, Sym, SymsMarker, Ty, TyScheme, ValEnv,
+  meta_vars, Cx, Def, Env, EnvLike as _, Generalizable, Subst, Sym, SymsMarker, Ty, TyScheme,
+  ValEnv,
 };
-use crate::unify::unify;
+use crate::unify::{unify, unify_not_generalized};
 use crate::util::{apply, get_scon, instantiate, record};
 use crate::{dec, pat, ty};
 
@@ -18,6 +19,7 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::Exp
   // NOTE: do not early return, since we add to the Info at the bottom.
   let mut ty_scheme = None::<TyScheme>;
   let mut def = None::<Def>;
+  let mut kind = None::<SymbolKind>;
   let ret = match &ars.exp[exp] {
     sml_hir::Exp::Hole => {
       let mv = st.meta_gen.gen(Generalizable::Always);
@@ -31,6 +33,7 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::Exp
       Ok(Some(val_info)) => {
         ty_scheme = Some(val_info.ty_scheme.clone());
         def = val_info.def;
+        kind = Some(val_info.id_status.symbol_kind());
         instantiate(st, val_info.ty_scheme.clone(), Generalizable::Always)
       }
       Ok(None) => {
@@ -47,6 +50,15 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::Exp
       let rows = record(st, rows, exp.into(), |st, _, exp| get(st, cx, ars, exp));
       Ty::Record(rows)
     }
+    sml_hir::Exp::Vector(exps) => {
+      let mut elem = Ty::MetaVar(st.meta_gen.gen(Generalizable::Always));
+      for &inner in exps {
+        let got = get(st, cx, ars, inner);
+        unify(st, elem.clone(), got, inner.unwrap_or(exp).into());
+        apply(st.subst(), &mut elem);
+      }
+      Ty::Con(vec![elem], Sym::VECTOR)
+    }
     // sml_def(4)
     sml_hir::Exp::Let(dec, inner) => {
       let mut let_env = Env::default();
@@ -54,6 +66,9 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::Exp
       dec::get(st, cx, ars, &mut let_env, *dec);
       let mut cx = cx.clone();
       cx.env.append(&mut let_env);
+      if let Some(inner) = *inner {
+        st.info().insert_env(inner.into(), &cx.env);
+      }
       let got = get(st, &cx, ars, *inner);
       if let Some(sym) = ty_name_escape(&marker, &got) {
         st.err(inner.unwrap_or(exp), ErrorKind::TyNameEscape(sym));
@@ -64,6 +79,7 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::Exp
     sml_hir::Exp::App(func, arg) => {
       let func_ty = get(st, cx, ars, *func);
       let arg_ty = get(st, cx, ars, *arg);
+      let binding = not_generalized_binding(st, cx, ars, *func);
       // we could choose to not `match` on `func_ty` and just use the `MetaVar` case always and it
       // would still be correct. however, matching on `func_ty` lets us emit slightly better error
       // messages sometimes.
@@ -73,7 +89,7 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::Exp
         Ty::MetaVar(_) => {
           let mut ret = Ty::MetaVar(st.meta_gen.gen(Generalizable::Always));
           let got = Ty::fun(arg_ty, ret.clone());
-          unify(st, func_ty, got, exp.into());
+          unify_not_generalized(st, func_ty, got, exp.into(), binding);
           apply(st.subst(), &mut ret);
           ret
         }
@@ -82,7 +98,24 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::Exp
           Ty::None
         }
         Ty::Fn(want_arg, mut want_res) => {
-          unify(st, *want_arg, arg_ty, arg.unwrap_or(exp).into());
+          let arg_idx = arg.unwrap_or(exp);
+          // for comparisons, unify the operands one at a time, so that a mismatch is reported at
+          // the operand that caused it.
+          match (comparison_operands(ars, *func, *arg), *want_arg, arg_ty) {
+            (Some(operands), Ty::Record(mut want), Ty::Record(mut got))
+              if want.keys().eq(got.keys()) =>
+            {
+              for (lab, operand) in operands {
+                if let (Some(want), Some(got)) = (want.remove(&lab), got.remove(&lab)) {
+                  let idx = operand.unwrap_or(arg_idx).into();
+                  unify_not_generalized(st, want, got, idx, binding);
+                }
+              }
+            }
+            (_, want_arg, arg_ty) => {
+              unify_not_generalized(st, want_arg, arg_ty, arg_idx.into(), binding);
+            }
+          }
           apply(st.subst(), want_res.as_mut());
           *want_res
         }
@@ -124,10 +157,70 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, exp: sml_hir::Exp
     ty: ret.clone(),
     ty_scheme,
   };
-  st.info().insert(exp.into(), Some(ty_entry), def);
+  st.info().insert(exp.into(), Some(ty_entry), def, kind);
   ret
 }
 
+/// returns the binding of the value `exp` is a path to, if that value's type was not generalized
+/// but has meta variables, so every use of it must have the same type.
+///
+/// such a binding is always in the same file as the use, since only bindings of the top-level
+/// declarations of a file are visible to other files, and those are always generalized.
+fn not_generalized_binding(
+  st: &St,
+  cx: &Cx,
+  ars: &sml_hir::Arenas,
+  exp: sml_hir::ExpIdx,
+) -> Option<sml_hir::Idx> {
+  let path = match &ars.exp[exp?] {
+    sml_hir::Exp::Path(path) => path,
+    _ => return None,
+  };
+  let val_info = get_val_info(&cx.env, path).ok()??;
+  if !val_info.ty_scheme.bound_vars.is_empty() {
+    return None;
+  }
+  let mut has_meta_vars = false;
+  meta_vars(
+    &Subst::default(),
+    &mut |_, _| has_meta_vars = true,
+    &val_info.ty_scheme.ty,
+  );
+  if !has_meta_vars {
+    return None;
+  }
+  let def = val_info.def?;
+  (st.def(def.idx) == Some(def)).then_some(def.idx)
+}
+
+/// the names of the comparison operators in the std basis.
+const COMPARISON_OPS: [&str; 6] = ["=", "<>", "<", ">", "<=", ">="];
+
+/// returns the labeled operands of `arg` if `func` is a comparison operator and `arg` is a pair, as
+/// in `lhs < rhs`.
+fn comparison_operands(
+  ars: &sml_hir::Arenas,
+  func: sml_hir::ExpIdx,
+  arg: sml_hir::ExpIdx,
+) -> Option<[(sml_hir::Lab, sml_hir::ExpIdx); 2]> {
+  let path = match &ars.exp[func?] {
+    sml_hir::Exp::Path(path) => path,
+    _ => return None,
+  };
+  if !path.structures().is_empty() || !COMPARISON_OPS.contains(&path.last().as_str()) {
+    return None;
+  }
+  match &ars.exp[arg?] {
+    sml_hir::Exp::Record(rows) => match rows.as_slice() {
+      [(sml_hir::Lab::Num(1), lhs), (sml_hir::Lab::Num(2), rhs)] => {
+        Some([(sml_hir::Lab::Num(1), *lhs), (sml_hir::Lab::Num(2), *rhs)])
+      }
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
 /// sml_def(13)
 fn get_matcher(
   st: &mut St,
@@ -149,6 +242,9 @@ fn get_matcher(
       val_env: ve,
       ..Default::default()
     });
+    if let Some(exp) = exp {
+      st.info().insert_env(exp.into(), &cx.env);
+    }
     let exp_ty = get(st, &cx, ars, exp);
     let pi = pat.map_or(idx, Into::into);
     unify(st, param_ty.clone(), pat_ty, pi);
@@ -5,7 +5,7 @@ use crate::types::{Env, EnvLike, TyInfo, ValInfo};
 /// final `env` or an error for the first name that was unbound.
 ///
 /// returns `Ok(None)` iff `names` was empty.
-fn get_env<'e, 'n, I, E>(env: &'e E, names: I) -> Result<Option<&'e Env>, ErrorKind>
+pub(crate) fn get_env<'e, 'n, I, E>(env: &'e E, names: I) -> Result<Option<&'e Env>, ErrorKind>
 where
   I: IntoIterator<Item = &'n sml_hir::Name>,
   E: EnvLike,
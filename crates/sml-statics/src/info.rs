@@ -1,6 +1,11 @@
-use crate::types::{Def, DefPath, MetaVarInfo, MetaVarNames, Syms, Ty, TyScheme};
+use crate::completions::CompletionItem;
+use crate::fmt_util::ty_var_name;
+use crate::types::{
+  meta_vars, Def, DefPath, EnvLike as _, EnvStack, IdStatus, MetaTyVar, MetaVarInfo, MetaVarNames,
+  Subst, Syms, Ty, TyScheme, TyVarKind,
+};
 use crate::util::ty_syms;
-use fast_hash::FxHashMap;
+use fast_hash::{FxHashMap, FxHashSet};
 use std::fmt::Write as _;
 
 /// Information about HIR indices.
@@ -8,7 +13,21 @@ use std::fmt::Write as _;
 pub struct Info {
   mode: Mode,
   store: FxHashMap<sml_hir::Idx, InfoEntry>,
+  envs: FxHashMap<sml_hir::Idx, EnvStack>,
+  end_env: Option<EnvStack>,
   pub(crate) meta_vars: MetaVarInfo,
+  pub(crate) meta_var_stats: MetaVarStats,
+  #[cfg(feature = "subst-info")]
+  pub(crate) subst: std::collections::BTreeMap<crate::types::MetaTyVar, Ty>,
+}
+
+/// Counts of meta type variables from type inference.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MetaVarStats {
+  /// How many were generated.
+  pub generated: usize,
+  /// How many were solved to a type.
+  pub solved: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -21,7 +40,23 @@ pub(crate) struct TyEntry {
 struct InfoEntry {
   ty_entry: Option<TyEntry>,
   def: Option<Def>,
+  kind: Option<SymbolKind>,
   doc: Option<String>,
+  ty_var_site: Option<sml_hir::Idx>,
+}
+
+/// The kind of symbol an index refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum SymbolKind {
+  Value,
+  Constructor,
+  Exception,
+  Type,
+  TyVar,
+  Structure,
+  Signature,
+  Functor,
 }
 
 impl Info {
@@ -29,11 +64,22 @@ impl Info {
     Self {
       mode,
       store: FxHashMap::default(),
+      envs: FxHashMap::default(),
+      end_env: None,
       meta_vars: MetaVarInfo::default(),
+      meta_var_stats: MetaVarStats::default(),
+      #[cfg(feature = "subst-info")]
+      subst: std::collections::BTreeMap::new(),
     }
   }
 
-  pub(crate) fn insert(&mut self, idx: sml_hir::Idx, ty_entry: Option<TyEntry>, def: Option<Def>) {
+  pub(crate) fn insert(
+    &mut self,
+    idx: sml_hir::Idx,
+    ty_entry: Option<TyEntry>,
+    def: Option<Def>,
+    kind: Option<SymbolKind>,
+  ) {
     // ignore ty schemes that bind no vars
     let entry = InfoEntry {
       ty_entry: ty_entry.map(|mut ty_entry| {
@@ -43,7 +89,9 @@ impl Info {
         ty_entry
       }),
       def,
+      kind,
       doc: None,
+      ty_var_site: None,
     };
     assert!(self.store.insert(idx, entry).is_none());
   }
@@ -53,6 +101,11 @@ impl Info {
     self.store.entry(idx).or_default().doc.replace(doc)
   }
 
+  /// Records that the type variable at `idx` is bound at `site`.
+  pub(crate) fn insert_ty_var_site(&mut self, idx: sml_hir::Idx, site: sml_hir::Idx) {
+    self.store.entry(idx).or_default().ty_var_site = Some(site);
+  }
+
   pub(crate) fn tys_mut(&mut self) -> impl Iterator<Item = &mut Ty> {
     self
       .store
@@ -60,6 +113,23 @@ impl Info {
       .filter_map(|entry| entry.ty_entry.as_mut().map(|x| &mut x.ty))
   }
 
+  /// records what is in scope at `idx`. this is only called where what is in scope may change, i.e.
+  /// at declarations and at the bodies of `let`s and matcher arms, not at every expression, since
+  /// that would clone an env for every node.
+  ///
+  /// only records envs for regular files, since we don't complete inside the std basis.
+  pub(crate) fn insert_env(&mut self, idx: sml_hir::Idx, env: &EnvStack) {
+    if self.mode.is_regular() {
+      self.envs.insert(idx, env.clone());
+    }
+  }
+
+  pub(crate) fn insert_end_env(&mut self, env: EnvStack) {
+    if self.mode.is_regular() {
+      self.end_env = Some(env);
+    }
+  }
+
   pub(crate) fn mode(&self) -> &Mode {
     &self.mode
   }
@@ -69,6 +139,80 @@ impl Info {
     &self.meta_vars
   }
 
+  /// Returns counts of the meta type variables generated and solved by type inference.
+  pub fn meta_var_stats(&self) -> MetaVarStats {
+    self.meta_var_stats
+  }
+
+  /// Returns the final substitution, as pairs of a meta type variable and the type it was solved
+  /// to, both rendered for display and ordered by when the meta variable was created.
+  ///
+  /// This exposes details of type inference which may change at any time, so it is only available
+  /// with the `subst-info` feature.
+  #[cfg(feature = "subst-info")]
+  pub fn subst(&self, syms: &Syms) -> Vec<(String, String)> {
+    let mut mvs = MetaVarNames::new(&self.meta_vars);
+    for (&mv, ty) in self.subst.iter() {
+      mvs.extend_for(&Ty::MetaVar(mv));
+      mvs.extend_for(ty);
+    }
+    self
+      .subst
+      .iter()
+      .map(|(&mv, ty)| {
+        let mv = Ty::MetaVar(mv).display(&mvs, syms).to_string();
+        let ty = ty.display(&mvs, syms).to_string();
+        (mv, ty)
+      })
+      .collect()
+  }
+
+  /// Returns the indices at which the names in scope were recorded.
+  pub fn env_indices(&self) -> impl Iterator<Item = sml_hir::Idx> + '_ {
+    self.envs.keys().copied()
+  }
+
+  /// Returns completions for the names in scope at `idx`, or at the end of the file if `idx` is
+  /// `None`. If `structures` is non-empty, returns only the members of that structure.
+  pub fn completions(
+    &self,
+    syms: &Syms,
+    idx: Option<sml_hir::Idx>,
+    structures: &[sml_hir::Name],
+  ) -> Vec<CompletionItem> {
+    let env = match idx {
+      Some(idx) => self.envs.get(&idx),
+      None => self.end_env.as_ref(),
+    };
+    match env {
+      Some(env) => crate::completions::get(syms, &self.meta_vars, env, structures),
+      None => Vec::new(),
+    }
+  }
+
+  /// Returns whether a value, type, or structure named `name` is in scope at this index, or `None`
+  /// if what is in scope at this index was not recorded.
+  pub fn is_in_scope(&self, idx: sml_hir::Idx, name: &str) -> Option<bool> {
+    let env = self.envs.get(&idx)?;
+    let name = sml_hir::Name::new(name);
+    Some(
+      env.get_val(&name).is_some() || env.get_ty(&name).is_some() || env.get_str(&name).is_some(),
+    )
+  }
+
+  /// Returns the most general type of the value `name` bound by a `val` or `fun` at the top level
+  /// of this file, if there is one.
+  pub fn get_top_level_ty_scheme(&self, syms: &Syms, name: &str) -> Option<String> {
+    let path = self.mode.path()?;
+    let val_info = self.end_env.as_ref()?.get_val(&sml_hir::Name::new(name))?;
+    if !matches!(val_info.id_status, IdStatus::Val) || val_info.def?.path != path {
+      return None;
+    }
+    let mut mvs = MetaVarNames::new(&self.meta_vars);
+    mvs.extend_for(&val_info.ty_scheme.ty);
+    Some(val_info.ty_scheme.display(&mvs, syms).to_string())
+  }
+
   /// Returns a Markdown string with type information associated with this index.
   pub fn get_ty_md(&self, syms: &Syms, idx: sml_hir::Idx) -> Option<String> {
     let mut ret = String::new();
@@ -94,6 +238,110 @@ impl Info {
     Some(())
   }
 
+  /// Returns the type of this index as plain text, naming meta variables the same way as
+  /// [`Self::get_ty_md`] does for this usage.
+  pub fn get_ty_display(&self, syms: &Syms, idx: sml_hir::Idx) -> Option<String> {
+    let ty_entry = self.store.get(&idx)?.ty_entry.as_ref()?;
+    if matches!(ty_entry.ty, Ty::None) {
+      return None;
+    }
+    let mut mvs = MetaVarNames::new(&self.meta_vars);
+    mvs.extend_for(&ty_entry.ty);
+    Some(ty_entry.ty.display(&mvs, syms).to_string())
+  }
+
+  /// Returns the names of the type variables that may be generalized in the type of this index.
+  ///
+  /// If this index is a use of a polymorphic value, these are the bound variables of its most
+  /// general type. Otherwise, these are the unsolved, non-overloaded meta variables in its type,
+  /// named like type variables in order of appearance, so that e.g. the definition and a use of the
+  /// identity function both have `'a`.
+  pub fn get_ty_vars(&self, idx: sml_hir::Idx) -> Option<Vec<String>> {
+    let ty_entry = self.store.get(&idx)?.ty_entry.as_ref()?;
+    if let Some(ty_scheme) = &ty_entry.ty_scheme {
+      let ret = ty_scheme
+        .bound_vars
+        .kinds()
+        .enumerate()
+        .map(|(idx, kind)| {
+          let equality = matches!(kind, Some(TyVarKind::Equality));
+          ty_var_name(equality, idx).to_string()
+        })
+        .collect();
+      return Some(ret);
+    }
+    let mut mvs = Vec::<MetaTyVar>::new();
+    meta_vars(
+      &Subst::default(),
+      &mut |mv, _| {
+        let overloaded = matches!(self.meta_vars.get(&mv), Some(TyVarKind::Overloaded(_)));
+        if !overloaded && !mvs.contains(&mv) {
+          mvs.push(mv);
+        }
+      },
+      &ty_entry.ty,
+    );
+    let ret = mvs
+      .iter()
+      .enumerate()
+      .map(|(idx, mv)| {
+        let equality = matches!(self.meta_vars.get(mv), Some(TyVarKind::Equality));
+        ty_var_name(equality, idx).to_string()
+      })
+      .collect();
+    Some(ret)
+  }
+
+  /// Returns the declarations in scope at `scope` of the type aliases equal to, and datatype
+  /// constructors taking, exactly the record type that the function at `idx` takes. This is for
+  /// record selectors like `#foo`.
+  ///
+  /// `scope` should be one of the [`Self::env_indices`] enclosing `idx`.
+  ///
+  /// Each is the definition site of the declaration and the name of the alias or constructor.
+  /// Inner declarations come first.
+  pub fn get_record_ty_defs(
+    &self,
+    idx: sml_hir::Idx,
+    scope: sml_hir::Idx,
+  ) -> Option<Vec<(Def, sml_hir::Name)>> {
+    let ty_entry = self.store.get(&idx)?.ty_entry.as_ref()?;
+    let param = match &ty_entry.ty {
+      Ty::Fn(param, _) => param.as_ref(),
+      _ => return None,
+    };
+    if !matches!(param, Ty::Record(_)) {
+      return None;
+    }
+    let mut seen = FxHashSet::<&sml_hir::Name>::default();
+    let mut ret = Vec::<(Def, sml_hir::Name)>::new();
+    for env in self.envs.get(&scope)?.iter_rev() {
+      let mut found = Vec::<(Def, sml_hir::Name)>::new();
+      for (name, ty_info) in env.ty_env.iter() {
+        // inner types shadow outer ones.
+        if !seen.insert(name) {
+          continue;
+        }
+        let def = match ty_info.def {
+          Some(x) => x,
+          None => continue,
+        };
+        if ty_info.ty_scheme.bound_vars.is_empty() && ty_info.ty_scheme.ty == *param {
+          found.push((def, name.clone()));
+        }
+        for (con_name, val_info) in ty_info.val_env.iter() {
+          if matches!(&val_info.ty_scheme.ty, Ty::Fn(arg, _) if arg.as_ref() == param) {
+            found.push((def, con_name.clone()));
+          }
+        }
+      }
+      // the envs are hash maps, so sort for determinism.
+      found.sort_unstable_by(|a, b| a.1.cmp(&b.1));
+      ret.extend(found);
+    }
+    Some(ret)
+  }
+
   /// Returns documentation for this index.
   pub fn get_doc(&self, idx: sml_hir::Idx) -> Option<&str> {
     self.store.get(&idx)?.doc.as_deref()
@@ -104,6 +352,25 @@ impl Info {
     self.store.get(&idx)?.def
   }
 
+  /// Returns the kind of symbol the idx refers to.
+  pub fn get_symbol_kind(&self, idx: sml_hir::Idx) -> Option<SymbolKind> {
+    self.store.get(&idx)?.kind
+  }
+
+  /// Returns the index of the declaration that binds the type variable at the idx, like a `val` or
+  /// `datatype`. Returns `None` if the idx is not a type variable, or if it is free.
+  pub fn get_ty_var_site(&self, idx: sml_hir::Idx) -> Option<sml_hir::Idx> {
+    self.store.get(&idx)?.ty_var_site
+  }
+
+  /// Returns the indices whose definition site is `def`.
+  pub fn get_with_def(&self, def: Def) -> impl Iterator<Item = sml_hir::Idx> + '_ {
+    self
+      .store
+      .iter()
+      .filter_map(move |(&idx, entry)| (entry.def == Some(def)).then_some(idx))
+  }
+
   /// Returns the definition site of the type for the idx.
   pub fn get_ty_defs(&self, syms: &Syms, idx: sml_hir::Idx) -> Option<Vec<Def>> {
     let ty_entry = self.store.get(&idx)?.ty_entry.as_ref()?;
@@ -121,6 +388,33 @@ impl Info {
     Some(ret)
   }
 
+  /// Returns the definition site of the outermost type constructor in the type of the idx that was
+  /// defined in a regular file, i.e. not in the std basis.
+  pub fn get_ty_def(&self, syms: &Syms, idx: sml_hir::Idx) -> Option<Def> {
+    let ty_entry = self.store.get(&idx)?.ty_entry.as_ref()?;
+    let mut queue = std::collections::VecDeque::from([&ty_entry.ty]);
+    while let Some(ty) = queue.pop_front() {
+      match ty {
+        Ty::None | Ty::BoundVar(_) | Ty::MetaVar(_) | Ty::FixedVar(_) => {}
+        Ty::Record(rows) => queue.extend(rows.values()),
+        Ty::Con(args, sym) => {
+          let def = syms.get(sym).and_then(|(_, ty_info)| ty_info.def);
+          if let Some(def) = def {
+            if matches!(def.path, DefPath::Regular(_)) {
+              return Some(def);
+            }
+          }
+          queue.extend(args.iter());
+        }
+        Ty::Fn(param, res) => {
+          queue.push_back(param);
+          queue.push_back(res);
+        }
+      }
+    }
+    None
+  }
+
   /// Gets the variants for the type of the index. The bool is whether the name has an argument.
   pub fn get_variants(&self, syms: &Syms, idx: sml_hir::Idx) -> Option<Vec<(sml_hir::Name, bool)>> {
     let ty_entry = self.store.get(&idx)?.ty_entry.as_ref()?;
@@ -6,6 +6,7 @@
 
 #![deny(missing_debug_implementations, missing_docs, rust_2018_idioms)]
 
+mod completions;
 mod dec;
 mod error;
 mod exp;
@@ -24,9 +25,10 @@ mod util;
 
 pub mod basis;
 
-pub use error::Error;
-pub use info::{Info, Mode};
-pub use types::{Def, DefPath, MetaVarInfo, Syms};
+pub use completions::CompletionItem;
+pub use error::{Error, ERROR_CODES};
+pub use info::{Info, MetaVarStats, Mode, SymbolKind};
+pub use types::{Def, DefPath, MetaVarInfo, Syms, SymsCheckpoint};
 
 /// The result of statics.
 #[derive(Debug)]
@@ -49,6 +51,9 @@ pub fn get(
 ) -> Statics {
   let mut st = st::St::new(mode, std::mem::take(syms));
   let inner = top_dec::get(&mut st, &basis.inner, arenas, root);
+  let mut end_env = basis.inner.env.clone();
+  end_env.extend(&inner.env);
+  st.info().insert_end_env(end_env);
   let (new_syms, errors, info) = st.finish();
   *syms = new_syms;
   Statics {
@@ -1,12 +1,12 @@
 use crate::error::{ErrorKind, Item};
 use crate::generalizes::eq_ty_scheme;
 use crate::get_env::get_val_info;
-use crate::info::TyEntry;
+use crate::info::{SymbolKind, TyEntry};
 use crate::pat_match::{Con, Pat, VariantName};
 use crate::st::St;
 use crate::ty;
 use crate::types::{
-  Cx, Def, EnvLike as _, Generalizable, IdStatus, SubstEntry, Ty, TyScheme, TyVarKind, ValEnv,
+  Cx, Def, EnvLike as _, Generalizable, IdStatus, SubstEntry, Sym, Ty, TyScheme, TyVarKind, ValEnv,
   ValInfo,
 };
 use crate::unify::unify;
@@ -30,7 +30,15 @@ pub(crate) fn get(
     ty: ty.clone(),
     ty_scheme,
   };
-  st.info().insert(pat_.into(), Some(ty_entry), def);
+  let kind = match &ars.pat[pat_] {
+    sml_hir::Pat::Con(path, _) => Some(match get_val_info(&cx.env, path) {
+      Ok(Some(val_info)) => val_info.id_status.symbol_kind(),
+      Ok(None) | Err(_) => SymbolKind::Value,
+    }),
+    sml_hir::Pat::As(_, _) => Some(SymbolKind::Value),
+    _ => None,
+  };
+  st.info().insert(pat_.into(), Some(ty_entry), def, kind);
   (pat, ty)
 }
 
@@ -101,7 +109,7 @@ fn get_(
           ty_scheme = Some(val_info.ty_scheme.clone());
           def = val_info.def;
           if arg.is_some() {
-            st.err(pat_, ErrorKind::ConPatMustNotHaveArg)
+            st.err(pat_, ErrorKind::ConPatMustNotHaveArg(path.last().clone()))
           }
           (sym, Vec::new(), ty)
         }
@@ -120,7 +128,8 @@ fn get_(
           };
           let arg_pat = match arg {
             None => {
-              st.err(pat_, ErrorKind::ConPatMustHaveArg);
+              let e = ErrorKind::ConPatMustHaveArg(path.last().clone(), param_ty.as_ref().clone());
+              st.err(pat_, e);
               Pat::zero(Con::Any, pat)
             }
             Some((arg_pat, arg_ty)) => {
@@ -162,6 +171,18 @@ fn get_(
       };
       (Pat::con(con, pats, pat), ty)
     }
+    sml_hir::Pat::Vector(pats) => {
+      let mut elem = Ty::MetaVar(st.meta_gen.gen(g));
+      let mut pm_pats = Vec::<Pat>::with_capacity(pats.len());
+      for &inner in pats {
+        let (pm_pat, got) = get(st, cx, ars, ve, inner, g);
+        unify(st, elem.clone(), got, inner.unwrap_or(pat_).into());
+        apply(st.subst(), &mut elem);
+        pm_pats.push(pm_pat);
+      }
+      let ty = Ty::Con(vec![elem], Sym::VECTOR);
+      (Pat::con(Con::Vector(pats.len()), pm_pats, pat), ty)
+    }
     // sml_def(42)
     sml_hir::Pat::Typed(inner, want) => {
       let (pm_pat, got) = get(st, cx, ars, ve, *inner, g);
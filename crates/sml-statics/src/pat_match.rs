@@ -59,6 +59,7 @@ impl pattern_match::Lang for Lang {
       | Con::Char(_)
       | Con::String(_)
       | Con::Record { .. }
+      | Con::Vector(_)
       | Con::Variant(_, _) => {
         vec![con.clone()]
       }
@@ -95,6 +96,10 @@ impl pattern_match::Lang for Lang {
             _ => return Err(CheckError),
           }
         }
+        Con::Vector(len) => match args.first() {
+          Some(arg) => vec![arg.clone(); *len],
+          None => return Err(CheckError),
+        },
         Con::Record { .. } => return Err(CheckError),
       },
     };
@@ -126,6 +131,8 @@ pub(crate) enum Con {
     labels: BTreeSet<sml_hir::Lab>,
     allows_other: bool,
   },
+  /// A vector with the given number of elements.
+  Vector(usize),
   Variant(Sym, VariantName),
 }
 
@@ -1,8 +1,9 @@
 use crate::error::{Error, ErrorKind};
-use crate::info::{Info, Mode};
+use crate::info::{Info, MetaVarStats, Mode};
 use crate::pat_match::{Lang, Pat};
 use crate::types::{Def, FixedTyVar, FixedTyVarGen, MetaTyVar, MetaTyVarGen, Subst, Syms, Ty};
 use crate::util::apply;
+use fast_hash::FxHashMap;
 
 /// The state.
 ///
@@ -15,6 +16,8 @@ pub(crate) struct St {
   errors: Vec<Error>,
   pub(crate) meta_gen: MetaTyVarGen,
   fixed_gen: FixedTyVarGen,
+  /// where each fixed var was bound.
+  fixed_sites: FxHashMap<FixedTyVar, sml_hir::Idx>,
   info: Info,
   matches: Vec<Match>,
   holes: Vec<(MetaTyVar, sml_hir::Idx)>,
@@ -28,6 +31,7 @@ impl St {
       errors: Vec::new(),
       meta_gen: MetaTyVarGen::default(),
       fixed_gen: FixedTyVarGen::default(),
+      fixed_sites: FxHashMap::default(),
       info: Info::new(mode),
       matches: Vec::new(),
       holes: Vec::new(),
@@ -61,6 +65,14 @@ impl St {
     self.fixed_gen.gen(ty_var)
   }
 
+  pub(crate) fn set_fixed_var_site(&mut self, fv: FixedTyVar, idx: sml_hir::Idx) {
+    self.fixed_sites.insert(fv, idx);
+  }
+
+  pub(crate) fn fixed_var_site(&self, fv: &FixedTyVar) -> Option<sml_hir::Idx> {
+    self.fixed_sites.get(fv).copied()
+  }
+
   pub(crate) fn info(&mut self) -> &mut Info {
     &mut self.info
   }
@@ -133,6 +145,22 @@ impl St {
     for ty in self.info.tys_mut() {
       apply(&self.subst, ty);
     }
+    #[cfg(feature = "subst-info")]
+    {
+      self.info.subst = self
+        .subst
+        .solved()
+        .map(|(mv, ty)| {
+          let mut ty = ty.clone();
+          apply(&self.subst, &mut ty);
+          (mv, ty)
+        })
+        .collect();
+    }
+    self.info.meta_var_stats = MetaVarStats {
+      generated: self.meta_gen.num_generated(),
+      solved: self.subst.num_solved(),
+    };
     self.info.meta_vars = self.subst.into_meta_var_info();
     (lang.syms, errors, self.info)
   }
@@ -1,11 +1,12 @@
 use crate::error::{ErrorKind, Item};
 use crate::generalizes::{eq_ty_scheme, eq_ty_scheme_no_emit, generalizes};
 use crate::get_env::{get_env_from_str_path, get_ty_info, get_ty_info_raw};
+use crate::info::SymbolKind;
 use crate::st::St;
 use crate::types::{
-  generalize, generalize_fixed, BasicOverload, Bs, Env, EnvLike, EnvStack, FunEnv, FunSig,
-  HasRecordMetaVars, IdStatus, Sig, SigEnv, StartedSym, StrEnv, Sym, Ty, TyEnv, TyInfo, TyNameSet,
-  TyScheme, TyVarKind, ValEnv, ValInfo,
+  generalize, generalize_fixed, BasicOverload, Bs, Env, EnvLike, EnvStack, Equality, FunEnv,
+  FunSig, HasRecordMetaVars, IdStatus, Sig, SigEnv, StartedSym, StrEnv, Sym, Ty, TyEnv, TyInfo,
+  TyNameSet, TyScheme, TyVarKind, ValEnv, ValInfo,
 };
 use crate::util::{apply_bv, ignore, ins_check_name, ins_no_dupe, ty_syms};
 use crate::{dec, ty};
@@ -46,6 +47,9 @@ fn get_str_dec(
     Some(x) => x,
     None => return,
   };
+  if !matches!(ars.str_dec[str_dec], sml_hir::StrDec::Seq(_)) {
+    st.info().insert_env(str_dec.into(), &bs.env);
+  }
   match &ars.str_dec[str_dec] {
     // sml_def(56)
     sml_hir::StrDec::Dec(dec) => dec::get(st, &bs.as_cx(), ars, ac.as_mut_env(), *dec),
@@ -173,7 +177,12 @@ fn get_str_exp(
     // sml_def(51)
     sml_hir::StrExp::Path(path) => match get_env_from_str_path(&bs.env, path) {
       Ok(got_env) => {
-        st.info().insert(str_exp.into(), None, got_env.def);
+        st.info().insert(
+          str_exp.into(),
+          None,
+          got_env.def,
+          Some(SymbolKind::Structure),
+        );
         ac.append(&mut got_env.clone());
       }
       Err(e) => st.err(str_exp, e),
@@ -234,7 +243,12 @@ fn get_str_exp(
         for val_info in to_add.val_env.values_mut() {
           val_info.def = def;
         }
-        st.info().insert(str_exp.into(), None, fun_sig.body_env.def);
+        st.info().insert(
+          str_exp.into(),
+          None,
+          fun_sig.body_env.def,
+          Some(SymbolKind::Functor),
+        );
         ac.append(&mut to_add);
       }
       None => st.err(
@@ -277,7 +291,12 @@ fn get_sig_exp(
         gen_fresh_syms(st, &mut subst, &sig.ty_names);
         let mut sig_env = sig.env.clone();
         env_realize(&subst, &mut sig_env);
-        st.info().insert(sig_exp.into(), None, sig.env.def);
+        st.info().insert(
+          sig_exp.into(),
+          None,
+          sig.env.def,
+          Some(SymbolKind::Signature),
+        );
         ac.append(&mut sig_env);
         if st.mode().is_std_basis() {
           match name.as_str() {
@@ -302,7 +321,10 @@ fn get_sig_exp(
       let fixed = dec::add_fixed_ty_vars(st, &mut cx, ty_vars, sig_exp.into());
       let mut ty_scheme = TyScheme::zero(ty::get(st, &cx, ars, *ty));
       generalize_fixed(fixed, &mut ty_scheme);
-      get_where_type(st, &mut inner_env, ty_vars, path, ty_scheme, sig_exp.into());
+      let arity = ty_vars.len();
+      // report errors at the realizing type, not the whole signature expression.
+      let idx = ty.map_or_else(|| sig_exp.into(), sml_hir::Idx::from);
+      get_where_type(st, &mut inner_env, arity, path, ty_scheme, idx);
       ac.append(&mut inner_env);
       ov
     }
@@ -333,7 +355,8 @@ fn get_sig_exp(
         match get_ty_info(&bs.env, &rhs) {
           Ok(ty_info) => {
             let ty_scheme = ty_info.ty_scheme.clone();
-            get_where_type(st, &mut inner_env, &[], &lhs, ty_scheme, sig_exp.into());
+            let arity = ty_scheme.bound_vars.len();
+            get_where_type(st, &mut inner_env, arity, &lhs, ty_scheme, sig_exp.into());
           }
           Err(e) => st.err(sig_exp, e),
         }
@@ -344,10 +367,11 @@ fn get_sig_exp(
   }
 }
 
+/// `arity` is the number of type variables bound by `ty_scheme`.
 fn get_where_type(
   st: &mut St,
   inner_env: &mut Env,
-  ty_vars: &[sml_hir::TyVar],
+  arity: usize,
   path: &sml_hir::Path,
   ty_scheme: TyScheme,
   idx: sml_hir::Idx,
@@ -355,15 +379,25 @@ fn get_where_type(
   match get_ty_info(inner_env, path) {
     Ok(ty_info) => {
       let want_len = ty_info.ty_scheme.bound_vars.len();
-      if want_len == ty_vars.len() {
+      if want_len == arity {
         match &ty_info.ty_scheme.ty {
           Ty::None => {}
           // TODO side condition for sym not in T of B?
-          Ty::Con(_, sym) => env_realize(&map([(*sym, ty_scheme)]), inner_env),
+          Ty::Con(_, sym) => {
+            let sym = *sym;
+            let eqtype = st.syms.equality(&sym) != Equality::Never;
+            if eqtype && !ty_scheme.ty.admits_equality(&|s| st.syms.equality(&s)) {
+              st.err(
+                idx,
+                ErrorKind::NotEqTyRealization(path.clone(), ty_scheme.clone()),
+              );
+            }
+            env_realize(&map([(sym, ty_scheme)]), inner_env);
+          }
           t => unreachable!("bad `where`: {t:?}"),
         }
       } else {
-        st.err(idx, ErrorKind::WrongNumTyArgs(want_len, ty_vars.len()));
+        st.err(idx, ErrorKind::WrongNumTyArgs(want_len, arity));
       }
     }
     Err(e) => st.err(idx, e),
@@ -426,11 +460,22 @@ fn get_spec(st: &mut St, bs: &Bs, ars: &sml_hir::Arenas, ac: &mut Env, spec: sml
     // sml_def(69)
     //
     // TODO check does not admit equality
-    sml_hir::Spec::Ty(ty_descs) => get_ty_desc(st, &mut ac.ty_env, ty_descs, spec.into()),
+    sml_hir::Spec::Ty(ty_descs) => {
+      get_ty_desc(st, &mut ac.ty_env, ty_descs, Equality::Never, spec.into());
+    }
     // sml_def(70)
     //
     // TODO check does admit equality
-    sml_hir::Spec::EqTy(ty_descs) => get_ty_desc(st, &mut ac.ty_env, ty_descs, spec.into()),
+    sml_hir::Spec::EqTy(ty_descs) => {
+      // arrays admit equality no matter their contents, like refs, but that can't be written in
+      // SML, so the std basis just says they're `eqtype`s.
+      let equality = if st.mode().is_std_basis() && ty_descs.name.as_str() == "array" {
+        Equality::Always
+      } else {
+        Equality::Args
+      };
+      get_ty_desc(st, &mut ac.ty_env, ty_descs, equality, spec.into());
+    }
     // sml_def(71)
     sml_hir::Spec::Datatype(dat_desc) => {
       let dat_descs = std::slice::from_ref(dat_desc);
@@ -500,7 +545,9 @@ fn get_spec(st: &mut St, bs: &Bs, ars: &sml_hir::Arenas, ac: &mut Env, spec: sml
       let mut inner_env = Env::default();
       get_spec(st, bs, ars, &mut inner_env, *inner);
       match kind {
-        sml_hir::SharingKind::Regular => get_sharing_type(st, &mut inner_env, paths, spec.into()),
+        sml_hir::SharingKind::Regular => {
+          get_sharing_type(st, bs, &mut inner_env, paths, spec.into());
+        }
         sml_hir::SharingKind::Derived => {
           let mut all: Vec<_> = paths
             .iter()
@@ -520,7 +567,7 @@ fn get_spec(st: &mut St, bs: &Bs, ars: &sml_hir::Arenas, ac: &mut Env, spec: sml
                 }
                 let path_1 = join_paths(struct_1, &ty_con);
                 let path_2 = join_paths(*struct_2, &ty_con);
-                get_sharing_type(st, &mut inner_env, &[path_1, path_2], spec.into());
+                get_sharing_type(st, bs, &mut inner_env, &[path_1, path_2], spec.into());
               }
             }
           }
@@ -561,25 +608,43 @@ fn append_no_dupe(st: &mut St, ac: &mut Env, other: &mut Env, idx: sml_hir::Idx)
 }
 
 /// `sharing type` directly uses this, and the `sharing` derived form eventually uses this.
-fn get_sharing_type(st: &mut St, inner_env: &mut Env, paths: &[sml_hir::Path], idx: sml_hir::Idx) {
+fn get_sharing_type(
+  st: &mut St,
+  bs: &Bs,
+  inner_env: &mut Env,
+  paths: &[sml_hir::Path],
+  idx: sml_hir::Idx,
+) {
   let mut ty_scheme = None::<TyScheme>;
   let mut syms = Vec::<Sym>::with_capacity(paths.len());
+  // computed lazily, since it's the whole basis.
+  let mut bs_ty_names = None::<TyNameSet>;
   for path in paths {
     match get_ty_info(inner_env, path) {
       Ok(ty_info) => {
         // TODO assert exists c s.t. for all ty schemes, arity of ty scheme = c? and all other
         // things about the bound ty vars are 'compatible'? (the bit about admitting equality?)
-        match &ty_info.ty_scheme.ty {
-          Ty::None => {}
-          // TODO side condition for sym not in T of B?
-          Ty::Con(_, sym) => {
+        if matches!(ty_info.ty_scheme.ty, Ty::None) {
+          continue;
+        }
+        let bs_ty_names = bs_ty_names.get_or_insert_with(|| {
+          let mut ac = TyNameSet::default();
+          bs_syms(&mut |x| ignore(ac.insert(x)), bs);
+          ac
+        });
+        // the type must be a type name not in T of B. if it's not, it was a type abbreviation,
+        // like `type t = int * int`, or `type t = int` (`int` is in T of B).
+        match ty_info.ty_scheme.ty_name() {
+          Some(sym) if !bs_ty_names.contains(&sym) => {
             if ty_scheme.is_none() {
               ty_scheme = Some(ty_info.ty_scheme.clone());
             }
-            syms.push(*sym);
+            syms.push(sym);
+          }
+          _ => {
+            st.err(idx, ErrorKind::SharingTyAlias);
+            return;
           }
-          // TODO this is reachable, but doesn't make sense.
-          _ => {}
         }
       }
       Err(e) => st.err(idx, e),
@@ -630,7 +695,13 @@ fn get_ty_cons(structures: &mut Vec<sml_hir::Name>, ac: &mut FxHashSet<sml_hir::
 }
 
 // sml_def(80). TODO equality checks
-fn get_ty_desc(st: &mut St, ty_env: &mut TyEnv, ty_desc: &sml_hir::TyDesc, idx: sml_hir::Idx) {
+fn get_ty_desc(
+  st: &mut St,
+  ty_env: &mut TyEnv,
+  ty_desc: &sml_hir::TyDesc,
+  equality: Equality,
+  idx: sml_hir::Idx,
+) {
   let mut ty_vars = FxHashSet::<&sml_hir::TyVar>::default();
   let started = st.syms.start(ty_desc.name.clone());
   for ty_var in ty_desc.ty_vars.iter() {
@@ -649,6 +720,7 @@ fn get_ty_desc(st: &mut St, ty_env: &mut TyEnv, ty_desc: &sml_hir::TyDesc, idx:
     ),
     val_env: ValEnv::default(),
     def: st.def(idx),
+    equality,
   };
   st.syms.finish(started, ty_info.clone());
   if let Some(e) = ins_no_dupe(ty_env, ty_desc.name.clone(), ty_info, Item::Ty) {
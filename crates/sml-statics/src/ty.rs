@@ -1,6 +1,6 @@
 use crate::error::{ErrorKind, Item};
 use crate::get_env::get_ty_info;
-use crate::info::TyEntry;
+use crate::info::{SymbolKind, TyEntry};
 use crate::st::St;
 use crate::types::{Cx, Def, Ty, TyScheme};
 use crate::util::{apply_bv, record};
@@ -13,19 +13,27 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, ty: sml_hir::TyId
   // NOTE: do not early return, since we add to the Info at the bottom.
   let mut ty_scheme = None::<TyScheme>;
   let mut def = None::<Def>;
+  let mut kind = None::<SymbolKind>;
+  let mut ty_var_site = None::<sml_hir::Idx>;
   let ret = match &ars.ty[ty] {
     sml_hir::Ty::Hole => {
       st.err(ty, ErrorKind::TyHole);
       Ty::None
     }
     // sml_def(44)
-    sml_hir::Ty::Var(v) => match cx.fixed.get(v) {
-      None => {
-        st.err(ty, ErrorKind::Undefined(Item::TyVar, v.as_name().clone()));
-        Ty::None
+    sml_hir::Ty::Var(v) => {
+      kind = Some(SymbolKind::TyVar);
+      match cx.fixed.get(v) {
+        None => {
+          st.err(ty, ErrorKind::Undefined(Item::TyVar, v.as_name().clone()));
+          Ty::None
+        }
+        Some(fv) => {
+          ty_var_site = st.fixed_var_site(fv);
+          Ty::FixedVar(fv.clone())
+        }
       }
-      Some(fv) => Ty::FixedVar(fv.clone()),
-    },
+    }
     // sml_def(45)
     sml_hir::Ty::Record(rows) => {
       let rows = record(st, rows, ty.into(), |st, _, ty| get(st, cx, ars, ty));
@@ -36,6 +44,7 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, ty: sml_hir::TyId
       Ok(ty_info) => {
         ty_scheme = Some(ty_info.ty_scheme.clone());
         def = ty_info.def;
+        kind = Some(SymbolKind::Type);
         let want_len = ty_info.ty_scheme.bound_vars.len();
         let mut ret = Ty::None;
         if want_len == args.len() {
@@ -66,6 +75,9 @@ pub(crate) fn get(st: &mut St, cx: &Cx, ars: &sml_hir::Arenas, ty: sml_hir::TyId
     ty: ret.clone(),
     ty_scheme,
   };
-  st.info().insert(ty.into(), Some(ty_entry), def);
+  st.info().insert(ty.into(), Some(ty_entry), def, kind);
+  if let Some(site) = ty_var_site {
+    st.info().insert_ty_var_site(ty.into(), site);
+  }
   ret
 }
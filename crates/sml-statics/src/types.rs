@@ -4,6 +4,7 @@
 //! this crate are defined here.
 
 use crate::fmt_util::{idx_to_name, ty_var_name};
+use crate::info::SymbolKind;
 use drop_bomb::DropBomb;
 use fast_hash::{FxHashMap, FxHashSet};
 use fmt_util::comma_seq;
@@ -13,7 +14,7 @@ use std::sync::Arc;
 use uniq::{Uniq, UniqGen};
 
 /// Definition: Type
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum Ty {
   None,
   /// Can only appear when this Ty is wrapped in a TyScheme.
@@ -43,6 +44,28 @@ impl Ty {
     Self::Fn(param.into(), res.into())
   }
 
+  /// Returns whether this admits equality, given the equality attributes of type constructors.
+  ///
+  /// Bound type variables are assumed to admit equality, since they are the parameters of a type
+  /// function. Unlike unifying with an equality type variable, this never solves meta variables.
+  pub(crate) fn admits_equality<F>(&self, equality: &F) -> bool
+  where
+    F: Fn(Sym) -> Equality,
+  {
+    match self {
+      // unsolved meta vars may yet be solved to equality types.
+      Ty::None | Ty::BoundVar(_) | Ty::MetaVar(_) => true,
+      Ty::FixedVar(fv) => fv.ty_var().is_equality(),
+      Ty::Record(rows) => rows.values().all(|ty| ty.admits_equality(equality)),
+      Ty::Con(args, sym) => match equality(*sym) {
+        Equality::Never => false,
+        Equality::Args => args.iter().all(|ty| ty.admits_equality(equality)),
+        Equality::Always => true,
+      },
+      Ty::Fn(_, _) => false,
+    }
+  }
+
   pub(crate) fn display<'a>(
     &'a self,
     meta_vars: &'a MetaVarNames<'a>,
@@ -307,6 +330,22 @@ impl TyScheme {
     Self { bound_vars, ty }
   }
 
+  /// Returns `sym` if this is equivalent to the type name `sym`, i.e. it is the type scheme
+  /// returned by `n_ary` for `sym`.
+  pub(crate) fn ty_name(&self) -> Option<Sym> {
+    match &self.ty {
+      Ty::Con(args, sym) => {
+        let all_bound_in_order = args.len() == self.bound_vars.len()
+          && args
+            .iter()
+            .enumerate()
+            .all(|(i, arg)| matches!(arg, Ty::BoundVar(bv) if bv.0 == i));
+        all_bound_in_order.then_some(*sym)
+      }
+      _ => None,
+    }
+  }
+
   pub(crate) fn display<'a>(
     &'a self,
     meta_vars: &'a MetaVarNames<'a>,
@@ -573,6 +612,11 @@ impl MetaTyVarGen {
     ret
   }
 
+  /// Returns how many meta vars this has generated.
+  pub(crate) fn num_generated(&self) -> usize {
+    self.id as usize
+  }
+
   pub(crate) fn inc_rank(&mut self) {
     self.rank += 1;
   }
@@ -700,6 +744,7 @@ mk_special_syms![
   (6, y, BOOL, "bool"),
   (7, n, LIST, "list"),
   (8, n, REF, "ref"),
+  (9, n, VECTOR, "vector"),
 ];
 
 impl Sym {
@@ -725,17 +770,28 @@ impl Sym {
 #[derive(Debug, Default, Clone)]
 pub struct Syms {
   /// remember: always use Sym::idx to index
-  store: Vec<(sml_hir::Name, TyInfo)>,
-  exns: Vec<(sml_hir::Name, Option<Ty>)>,
+  store: SharedVec<(sml_hir::Name, TyInfo)>,
+  exns: SharedVec<(sml_hir::Name, Option<Ty>)>,
   overloads: Overloads,
 }
 
 impl Syms {
+  /// Makes all the current symbols shared between this and its clones, so that cloning this is
+  /// cheap. Symbols generated after this are not shared.
+  ///
+  /// Useful for a `Syms` that is cloned often but rarely changed, like the one for the std basis.
+  pub fn freeze(&mut self) {
+    self.store.freeze();
+    self.exns.freeze();
+  }
+
   pub(crate) fn start(&mut self, name: sml_hir::Name) -> StartedSym {
     let ty_info = TyInfo {
       ty_scheme: TyScheme::zero(Ty::None),
       val_env: ValEnv::default(),
       def: None,
+      // assume a type being defined admits equality, for recursive datatypes. see `get_dat_binds`.
+      equality: Equality::Args,
     };
     self.store.push((name, ty_info));
     StartedSym {
@@ -747,7 +803,7 @@ impl Syms {
 
   pub(crate) fn finish(&mut self, mut started: StartedSym, ty_info: TyInfo) {
     started.bomb.defuse();
-    self.store[started.sym.idx()].1 = ty_info;
+    self.store.get_mut(started.sym.idx()).unwrap().1 = ty_info;
   }
 
   /// Returns `None` iff passed `&Sym::EXN`.
@@ -759,6 +815,12 @@ impl Syms {
     Some((name, info))
   }
 
+  /// Returns the equality attribute of the type constructor `sym`.
+  pub(crate) fn equality(&self, sym: &Sym) -> Equality {
+    // only exn has no info, and it never admits equality.
+    self.get(sym).map_or(Equality::Never, |(_, ti)| ti.equality)
+  }
+
   pub(crate) fn insert_exn(&mut self, name: sml_hir::Name, param: Option<Ty>) -> Exn {
     let ret = Exn(self.exns.len());
     self.exns.push((name, param));
@@ -774,6 +836,24 @@ impl Syms {
     SymsMarker(self.store.len())
   }
 
+  /// Returns a checkpoint to later pass to [`Self::rollback`].
+  pub fn checkpoint(&self) -> SymsCheckpoint {
+    SymsCheckpoint {
+      store: self.store.len(),
+      exns: self.exns.len(),
+      overloads: self.overloads.clone(),
+    }
+  }
+
+  /// Forgets every symbol generated since `checkpoint` was taken from this.
+  ///
+  /// Only items that were created before `checkpoint` was taken may be used with this afterward.
+  pub fn rollback(&mut self, checkpoint: &SymsCheckpoint) {
+    self.store.truncate(checkpoint.store);
+    self.exns.truncate(checkpoint.exns);
+    self.overloads = checkpoint.overloads.clone();
+  }
+
   pub(crate) fn iter(&self) -> impl Iterator<Item = (&sml_hir::Name, &TyInfo)> {
     self.store.iter().map(|&(ref a, ref b)| (a, b))
   }
@@ -783,9 +863,80 @@ impl Syms {
   }
 }
 
+/// A sequence whose items before the last call to [`SharedVec::freeze`] are shared between clones.
+#[derive(Debug, Clone)]
+struct SharedVec<T> {
+  frozen: Arc<Vec<T>>,
+  own: Vec<T>,
+}
+
+impl<T> Default for SharedVec<T> {
+  fn default() -> Self {
+    Self {
+      frozen: Arc::default(),
+      own: Vec::new(),
+    }
+  }
+}
+
+impl<T: Clone> SharedVec<T> {
+  fn len(&self) -> usize {
+    self.frozen.len() + self.own.len()
+  }
+
+  fn push(&mut self, val: T) {
+    self.own.push(val);
+  }
+
+  fn get(&self, idx: usize) -> Option<&T> {
+    match idx.checked_sub(self.frozen.len()) {
+      None => self.frozen.get(idx),
+      Some(idx) => self.own.get(idx),
+    }
+  }
+
+  /// if `idx` is for a frozen item, this un-shares all frozen items, which is expensive.
+  fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+    match idx.checked_sub(self.frozen.len()) {
+      None => Arc::make_mut(&mut self.frozen).get_mut(idx),
+      Some(idx) => self.own.get_mut(idx),
+    }
+  }
+
+  fn iter(&self) -> impl Iterator<Item = &T> {
+    self.frozen.iter().chain(self.own.iter())
+  }
+
+  /// if `len` is less than the number of frozen items, this un-shares all frozen items, which is
+  /// expensive.
+  fn truncate(&mut self, len: usize) {
+    match len.checked_sub(self.frozen.len()) {
+      None => {
+        Arc::make_mut(&mut self.frozen).truncate(len);
+        self.own.clear();
+      }
+      Some(len) => self.own.truncate(len),
+    }
+  }
+
+  fn freeze(&mut self) {
+    if !self.own.is_empty() {
+      Arc::make_mut(&mut self.frozen).append(&mut self.own);
+    }
+  }
+}
+
 /// A marker to determine when a `Sym` was generated.
 pub(crate) struct SymsMarker(usize);
 
+/// A record of which symbols a [`Syms`] had generated at some point. See [`Syms::checkpoint`].
+#[derive(Debug, Clone)]
+pub struct SymsCheckpoint {
+  store: usize,
+  exns: usize,
+  overloads: Overloads,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) struct Exn(usize);
 
@@ -807,6 +958,19 @@ pub(crate) struct TyInfo {
   pub(crate) ty_scheme: TyScheme,
   pub(crate) val_env: ValEnv,
   pub(crate) def: Option<Def>,
+  pub(crate) equality: Equality,
+}
+
+/// The equality attribute of a type constructor, i.e. when it admits equality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Equality {
+  /// Never, like `real`, or an abstract type from a `type` specification.
+  Never,
+  /// When all of its type arguments do, like `list`, or an abstract type from an `eqtype`
+  /// specification.
+  Args,
+  /// Always, no matter its type arguments, like `ref`.
+  Always,
 }
 
 /// Definition: StrEnv
@@ -834,6 +998,14 @@ pub(crate) enum IdStatus {
 }
 
 impl IdStatus {
+  pub(crate) fn symbol_kind(&self) -> SymbolKind {
+    match self {
+      Self::Con => SymbolKind::Constructor,
+      Self::Exn(_) => SymbolKind::Exception,
+      Self::Val => SymbolKind::Value,
+    }
+  }
+
   pub(crate) fn same_kind_as(&self, other: &Self) -> bool {
     matches!(
       (self, other),
@@ -921,6 +1093,15 @@ impl EnvStack {
   pub(crate) fn push(&mut self, other: Env) {
     self.0.push(Arc::new(other));
   }
+
+  pub(crate) fn extend(&mut self, other: &Self) {
+    self.0.extend(other.0.iter().cloned());
+  }
+
+  /// from innermost to outermost.
+  pub(crate) fn iter_rev(&self) -> impl Iterator<Item = &Env> + '_ {
+    self.0.iter().rev().map(AsRef::as_ref)
+  }
 }
 
 impl EnvLike for EnvStack {
@@ -1055,6 +1236,12 @@ impl<E: EnvLike> Bs<E> {
 #[derive(Debug, Default, Clone)]
 pub struct MetaVarInfo(FxHashMap<MetaTyVar, TyVarKind>);
 
+impl MetaVarInfo {
+  pub(crate) fn get(&self, mv: &MetaTyVar) -> Option<&TyVarKind> {
+    self.0.get(mv)
+  }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct Subst {
   mv_info: MetaVarInfo,
@@ -1076,9 +1263,27 @@ impl Subst {
     self.entries.get(mv)
   }
 
+  /// returns how many meta vars this solved to a type.
+  pub(crate) fn num_solved(&self) -> usize {
+    self
+      .entries
+      .values()
+      .filter(|entry| matches!(entry, SubstEntry::Solved(_)))
+      .count()
+  }
+
   pub(crate) fn into_meta_var_info(self) -> MetaVarInfo {
     self.mv_info
   }
+
+  /// returns the meta vars solved by this and what they were solved to, not yet applied.
+  #[cfg(feature = "subst-info")]
+  pub(crate) fn solved(&self) -> impl Iterator<Item = (MetaTyVar, &Ty)> + '_ {
+    self.entries.iter().filter_map(|(&mv, entry)| match entry {
+      SubstEntry::Solved(ty) => Some((mv, ty)),
+      SubstEntry::Kind(_) => None,
+    })
+  }
 }
 
 #[derive(Debug)]
@@ -1314,7 +1519,7 @@ fn handle_bv(
 }
 
 /// A definition site.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Def {
   /// The path.
   pub path: DefPath,
@@ -2,23 +2,39 @@ use fast_hash::FxHashMap;
 
 use crate::error::ErrorKind;
 use crate::st::St;
-use crate::types::{meta_vars, MetaTyVar, SubstEntry, Ty, TyVarKind};
+use crate::types::{
+  meta_vars, BasicOverload, Equality, MetaTyVar, Overload, SubstEntry, Ty, TyVarKind,
+};
 use crate::util::apply;
 
 #[derive(Debug)]
 pub(crate) enum UnifyError {
   OccursCheck(MetaTyVar, Ty),
   HeadMismatch,
+  NotEqTy(Ty),
 }
 
 pub(crate) type Result<T = (), E = UnifyError> = std::result::Result<T, E>;
 
 pub(crate) fn unify(st: &mut St, want: Ty, got: Ty, idx: sml_hir::Idx) {
+  unify_not_generalized(st, want, got, idx, None);
+}
+
+/// like [`unify`], but if the types mismatch, also reports `binding` as the binding of a value
+/// whose type was not generalized, if any.
+pub(crate) fn unify_not_generalized(
+  st: &mut St,
+  want: Ty,
+  got: Ty,
+  idx: sml_hir::Idx,
+  binding: Option<sml_hir::Idx>,
+) {
   let e = match unify_(st, want.clone(), got.clone()) {
     Ok(()) => return,
     Err(e) => match e {
       UnifyError::OccursCheck(mv, ty) => ErrorKind::Circularity(mv, ty),
-      UnifyError::HeadMismatch => ErrorKind::MismatchedTypes(want, got),
+      UnifyError::HeadMismatch => ErrorKind::MismatchedTypes(want, got, binding),
+      UnifyError::NotEqTy(ty) => ErrorKind::NotEqTy(ty),
     },
   };
   st.err(idx, e);
@@ -105,8 +121,8 @@ fn unify_mv(st: &mut St, mv: MetaTyVar, mut ty: Ty) -> Result {
     // unreachable because we applied upon entry.
     Some(SubstEntry::Solved(ty)) => unreachable!("meta var already solved to {ty:?}"),
     Some(SubstEntry::Kind(kind)) => match kind {
-      // TODO check ty is do more for equality checks
-      TyVarKind::Equality => {}
+      // mv was an equality ty var. ty must admit equality.
+      TyVarKind::Equality => eq_ty(st, &ty)?,
       // mv was an overloaded ty var. ty must conform to that overload.
       TyVarKind::Overloaded(ov) => match ty {
         // don't emit more errors for None.
@@ -207,6 +223,54 @@ fn unify_mv(st: &mut St, mv: MetaTyVar, mut ty: Ty) -> Result {
   Ok(())
 }
 
+/// checks `ty` admits equality, making the meta vars in it equality ty vars as needed.
+///
+/// this is conservative for type constructors: it only knows that some types from the std basis
+/// never admit equality, and otherwise assumes a type admits equality if its arguments do.
+fn eq_ty(st: &mut St, ty: &Ty) -> Result {
+  match ty {
+    Ty::None | Ty::BoundVar(_) => Ok(()),
+    Ty::MetaVar(mv) => {
+      let kind = match st.subst().get(mv) {
+        None => None,
+        // types in the kinds of other meta vars may not have been applied.
+        Some(SubstEntry::Solved(ty)) => {
+          let ty = ty.clone();
+          return eq_ty(st, &ty);
+        }
+        Some(SubstEntry::Kind(kind)) => Some(kind.clone()),
+      };
+      match kind {
+        None => {
+          st.subst()
+            .insert(*mv, SubstEntry::Kind(TyVarKind::Equality));
+          Ok(())
+        }
+        Some(TyVarKind::Equality) => Ok(()),
+        Some(TyVarKind::Overloaded(ov)) => match ov {
+          Overload::Basic(BasicOverload::Real) => Err(UnifyError::NotEqTy(ty.clone())),
+          Overload::Basic(_) | Overload::Composite(_) => Ok(()),
+        },
+        Some(TyVarKind::Record(rows)) => rows.values().try_for_each(|ty| eq_ty(st, ty)),
+      }
+    }
+    Ty::FixedVar(fv) => {
+      if fv.ty_var().is_equality() {
+        Ok(())
+      } else {
+        Err(UnifyError::NotEqTy(ty.clone()))
+      }
+    }
+    Ty::Record(rows) => rows.values().try_for_each(|ty| eq_ty(st, ty)),
+    Ty::Con(args, sym) => match st.syms.equality(sym) {
+      Equality::Never => Err(UnifyError::NotEqTy(ty.clone())),
+      Equality::Args => args.iter().try_for_each(|ty| eq_ty(st, ty)),
+      Equality::Always => Ok(()),
+    },
+    Ty::Fn(_, _) => Err(UnifyError::NotEqTy(ty.clone())),
+  }
+}
+
 fn head_match(b: bool) -> Result {
   if b {
     Ok(())
@@ -42,7 +42,7 @@ fn main() -> std::io::Result<()> {
   gen(
     std::path::Path::new(out_dir.as_str()),
     "SML",
-    &["Whitespace", "BlockComment", "Invalid"],
+    &["Whitespace", "BlockComment", "Shebang", "Invalid"],
     include_str!("syntax.ungram").parse().expect("ungram parse"),
     |s| {
       let kind: TokenKind;
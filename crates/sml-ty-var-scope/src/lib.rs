@@ -275,6 +275,11 @@ fn get_exp(
         get_exp(cx, ars, scope, mode, exp);
       }
     }
+    sml_hir::Exp::Vector(exps) => {
+      for &exp in exps {
+        get_exp(cx, ars, scope, mode, exp);
+      }
+    }
     sml_hir::Exp::Let(dec, exp) => {
       get_dec(cx, ars, scope, mode, *dec);
       get_exp(cx, ars, scope, mode, *exp);
@@ -326,6 +331,11 @@ fn get_pat(cx: &mut Cx, ars: &sml_hir::Arenas, ac: &mut TyVarSet, pat: sml_hir::
         get_pat(cx, ars, ac, pat)
       }
     }
+    sml_hir::Pat::Vector(pats) => {
+      for &pat in pats {
+        get_pat(cx, ars, ac, pat)
+      }
+    }
     sml_hir::Pat::Typed(pat, ty) => {
       get_pat(cx, ars, ac, *pat);
       get_ty(cx, ars, ac, *ty);
@@ -0,0 +1,62 @@
+//! Counting allocations, to check that repeated work stays cheap.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// counts allocations per thread, since tests run in parallel.
+struct Counting;
+
+thread_local! {
+  static ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+// SAFETY: defers to `System`, only counting calls to `alloc` (which the default `realloc` uses).
+unsafe impl GlobalAlloc for Counting {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    // the thread-local may already be gone if the thread is exiting.
+    let _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// returns how many allocations `f` made on this thread.
+fn count<T, F>(f: F) -> usize
+where
+  F: FnOnce() -> T,
+{
+  let before = ALLOCS.with(Cell::get);
+  let ret = f();
+  let after = ALLOCS.with(Cell::get);
+  drop(ret);
+  after - before
+}
+
+/// Checking one file, as an editor might on every keystroke, should cost about the same no matter
+/// how big the std basis is, since the std basis is shared instead of cloned.
+#[test]
+fn get_one_std_basis_size() {
+  let s = "val x = 3 + 4";
+  let minimal = analysis::Analysis::new(analysis::StdBasis::minimal(), config::ErrorLines::One);
+  let full = analysis::Analysis::new(analysis::StdBasis::full(), config::ErrorLines::One);
+  // do it once first to initialize any lazy statics.
+  assert!(minimal.get_one(s).is_empty());
+  assert!(full.get_one(s).is_empty());
+  let minimal_allocs = count(|| minimal.get_one(s));
+  let full_allocs = count(|| full.get_one(s));
+  assert!(
+    full_allocs <= 2 * minimal_allocs,
+    "minimal: {minimal_allocs} allocations, full: {full_allocs} allocations"
+  );
+  let again = count(|| full.get_one(s));
+  assert!(
+    again <= full_allocs,
+    "first: {full_allocs} allocations, again: {again} allocations"
+  );
+}
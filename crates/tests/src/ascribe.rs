@@ -1,4 +1,4 @@
-use crate::check::check;
+use crate::check::{check, check_with_std_basis};
 
 #[test]
 fn ok_smoke() {
@@ -478,6 +478,64 @@ val _ = S.x : int
   );
 }
 
+#[test]
+fn where_type_wrong_arity() {
+  check(
+    r#"
+signature SIG = sig type 'a t end where type t = int
+(**                                              ^^^ expected 1 type argument, found 0 *)
+"#,
+  );
+}
+
+#[test]
+fn where_type_not_eq() {
+  check(
+    r#"
+signature SIG = sig eqtype t end where type t = real
+(**                                             ^^^^ cannot realize equality type t with non-equality type real *)
+"#,
+  );
+}
+
+#[test]
+fn where_type_not_eq_fn() {
+  check(
+    r#"
+signature SIG = sig eqtype 'a t end where type 'a t = 'a -> int
+(**                                                   ^^^^^^^^^ cannot realize equality type t with non-equality type 'a -> int *)
+"#,
+  );
+}
+
+#[test]
+fn where_type_eq_array() {
+  check_with_std_basis(
+    r#"
+signature SIG = sig eqtype t end where type t = real array
+"#,
+  );
+}
+
+#[test]
+fn where_type_eq_ty_var() {
+  check(
+    r#"
+signature SIG = sig eqtype 'a t end where type 'a t = 'a list
+"#,
+  );
+}
+
+#[test]
+fn eqtype_spec_not_eq() {
+  check(
+    r#"
+signature SIG = sig eqtype t = int -> int end
+(**                            ^^^^^^^^^^ cannot realize equality type t with non-equality type int -> int *)
+"#,
+  );
+}
+
 #[test]
 fn exn() {
   check(
@@ -549,3 +607,228 @@ end
 "#,
   );
 }
+
+#[test]
+fn mk_transparent_leaks() {
+  check(
+    r#"
+structure S : sig
+  type t
+  val mk : int -> t
+end = struct
+  type t = int
+  fun mk x = x
+end
+
+fun inc (x: int) = x + 1
+val _ = inc (S.mk 3)
+"#,
+  );
+}
+
+#[test]
+fn mk_opaque_hides() {
+  check(
+    r#"
+structure S :> sig
+  type t
+  val mk : int -> t
+end = struct
+  type t = int
+  fun mk x = x
+end
+
+fun inc (x: int) = x + 1
+val _ = inc (S.mk 3)
+(**          ^^^^^^ expected int, found t *)
+"#,
+  );
+}
+
+#[test]
+fn where_type_and_chain() {
+  check(
+    r#"
+signature S = sig
+  type t
+  type u
+  type v
+end
+  where type t = int
+  and type u = bool
+  and type v = string
+
+structure X : S = struct
+  type t = int
+  type u = bool
+  type v = string
+end
+
+val _ = (1 : X.t, true : X.u, "hi" : X.v)
+"#,
+  );
+}
+
+#[test]
+fn sharing_type_and_chain() {
+  check(
+    r#"
+functor F (A : sig
+  type t
+  type u
+  type v
+  val x : t
+  sharing type t = u and type u = v
+end) = struct
+  val y : A.v = A.x
+end
+"#,
+  );
+}
+
+#[test]
+fn where_structure_opaque() {
+  check(
+    r#"
+signature FOO = sig
+  type t
+  val x : t
+end
+
+signature BAR = sig
+  structure Foo : FOO
+end
+
+signature QUZ = sig
+  structure Foo : FOO
+  type u
+  val z : u
+end
+
+functor F (Bar : BAR) :> QUZ where Foo = Bar.Foo = struct
+  structure Foo = Bar.Foo
+  type u = int
+  val z = 3
+end
+
+structure B = struct
+  structure Foo = struct
+    type t = int
+    val x = 1
+  end
+end
+
+structure R = F (B)
+val _ = R.Foo.x + 1
+val _ = R.z : int
+(**     ^^^^^^^ expected int, found u *)
+"#,
+  );
+}
+
+#[test]
+fn where_structure_ty_args() {
+  check(
+    r#"
+structure A = struct
+  type 'a t = 'a list
+end
+
+signature SIG = sig
+  structure T : sig
+    type 'a t
+    val x : int t
+  end
+end where T = A
+
+structure B : SIG = struct
+  structure T = struct
+    type 'a t = 'a list
+    val x = [1]
+  end
+end
+
+val _ = B.T.x : int list
+"#,
+  );
+}
+
+#[test]
+fn hover_opaque_counter() {
+  check(
+    r#"
+signature COUNTER = sig
+  type t
+  val zero : t
+  val inc : t -> t
+end
+
+structure C :> COUNTER = struct
+  type t = int
+  val zero = 0
+  fun inc x = x + 1
+end
+
+val _ = C.inc C.zero
+(**       ^^^ hover: t -> t *)
+"#,
+  );
+}
+
+#[test]
+fn hover_transparent_counter() {
+  check(
+    r#"
+signature COUNTER = sig
+  type t
+  val zero : t
+  val inc : t -> t
+end
+
+structure C : COUNTER = struct
+  type t = int
+  val zero = 0
+  fun inc x = x + 1
+end
+
+val _ = C.inc C.zero
+(**       ^^^ hover: int -> int *)
+"#,
+  );
+}
+
+#[test]
+fn hover_opaque_inside() {
+  check(
+    r#"
+signature COUNTER = sig
+  type t
+  val zero : t
+end
+
+structure C :> COUNTER = struct
+  type t = int
+  val zero = 0
+(**   ^^^^ hover: int *)
+end
+"#,
+  );
+}
+
+#[test]
+fn hover_opaque_con() {
+  check(
+    r#"
+signature SIG = sig
+  datatype t = A | B
+end
+
+structure S :> SIG = struct
+  datatype t = A | B
+end
+
+val _ = S.A
+(**       ^ hover: t *)
+"#,
+  );
+}
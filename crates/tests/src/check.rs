@@ -18,6 +18,8 @@ use std::fmt::{self, Write as _};
 /// The expectation messages have a certain format:
 ///
 /// - Error expects have no special prefix, and must match exactly.
+/// - Error code expects begin with `code: `, and the actual error must merely have that code. This
+///   is for when the exact wording of the message is not what is being tested.
 /// - Hover expects begin with `hover: `, and the actual hover must merely contain the message.
 ///
 /// To construct the string to pass without worrying about Rust string escape sequences, use the raw
@@ -34,7 +36,7 @@ use std::fmt::{self, Write as _};
 /// Note that this also sets up logging.
 #[track_caller]
 pub(crate) fn check(s: &str) {
-  go(&[s], StdBasis::Minimal, Outcome::Pass)
+  go(&[s], StdBasis::Minimal, None, Outcome::Pass)
 }
 
 /// Like [`check`], but the expectation comments should be not satisfied.
@@ -61,35 +63,41 @@ pub(crate) fn check(s: &str) {
 #[allow(dead_code)]
 #[track_caller]
 pub(crate) fn fail(s: &str) {
-  go(&[s], StdBasis::Minimal, Outcome::Fail)
+  go(&[s], StdBasis::Minimal, None, Outcome::Fail)
 }
 
 /// Like [`check`], but includes the full std basis.
 #[track_caller]
 pub(crate) fn check_with_std_basis(s: &str) {
-  go(&[s], StdBasis::Full, Outcome::Pass)
+  go(&[s], StdBasis::Full, None, Outcome::Pass)
 }
 
 /// Like [`fail`], but includes the full std basis.
 #[allow(dead_code)]
 #[track_caller]
 pub(crate) fn fail_with_std_basis(s: &str) {
-  go(&[s], StdBasis::Full, Outcome::Fail)
+  go(&[s], StdBasis::Full, None, Outcome::Fail)
 }
 
 /// Like [`check`], but checks multiple files in sequence.
 #[track_caller]
 pub(crate) fn check_multi(ss: &[&str]) {
-  go(ss, StdBasis::Minimal, Outcome::Pass)
+  go(ss, StdBasis::Minimal, None, Outcome::Pass)
+}
+
+/// Like [`check`], but includes the full std basis and uses the given contents of the config file.
+#[track_caller]
+pub(crate) fn check_with_config(config: &str, s: &str) {
+  go(&[s], StdBasis::Full, Some(config), Outcome::Pass)
 }
 
 /// ignores the Err if we already initialized logging, since that's fine.
-fn go(ss: &[&str], std_basis: StdBasis, want: Outcome) {
+fn go(ss: &[&str], std_basis: StdBasis, config: Option<&str>, want: Outcome) {
   let _ = env_logger::builder().is_test(true).try_init();
   if matches!(std_basis, StdBasis::Full) && env_var_eq_1("TEST_MINIMAL") {
     return;
   }
-  let c = Check::new(ss, std_basis.to_analysis());
+  let c = Check::new(ss, std_basis.to_analysis(), config);
   match (want, c.reasons.is_empty()) {
     (Outcome::Pass, true) | (Outcome::Fail, false) => {}
     (Outcome::Pass, false) => panic!("UNEXPECTED FAIL: {c}"),
@@ -123,6 +131,74 @@ pub(crate) static ROOT: Lazy<paths::CanonicalPathBuf> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Returns the analysis of the one file `s` with the minimal std basis, and the id of the file's
+/// path.
+pub(crate) fn analyze(s: &str) -> (analysis::Analysis, paths::PathId) {
+  let (_, input) = get_input(&[s], None);
+  let path = input.iter_sources().next().expect("no source").path;
+  let mut an = analysis::Analysis::new(MINIMAL.clone(), config::ErrorLines::One);
+  an.get_many(&input);
+  (an, path)
+}
+
+/// Like [`analyze`], but analyzes multiple files in sequence. Returns the ids of the files' paths in
+/// the same order.
+pub(crate) fn analyze_multi(ss: &[&str]) -> (analysis::Analysis, Vec<paths::PathId>) {
+  let (_, input) = get_input(ss, None);
+  let paths = input.ordered_sources();
+  let mut an = analysis::Analysis::new(MINIMAL.clone(), config::ErrorLines::One);
+  an.get_many(&input);
+  (an, paths)
+}
+
+/// Returns the errors for the one file `s` in a workspace with the given config, with the minimal
+/// std basis.
+pub(crate) fn get_many_with_config(s: &str, config: Option<&str>) -> Vec<analysis::Error> {
+  let (_, input) = get_input(&[s], config);
+  let path = input.iter_sources().next().expect("no source").path;
+  let mut an = analysis::Analysis::new(MINIMAL.clone(), config::ErrorLines::One);
+  an.get_many(&input).remove(&path).unwrap_or_default()
+}
+
+/// Returns the errors for every path other than the one empty source file in a workspace with the
+/// given config, with the full std basis.
+pub(crate) fn get_config_errors(config: &str) -> Vec<analysis::Error> {
+  let (_, input) = get_input(&[""], Some(config));
+  let path = input.iter_sources().next().expect("no source").path;
+  let mut an = analysis::Analysis::new(FULL.clone(), config::ErrorLines::One);
+  let mut errors = an.get_many(&input);
+  errors.remove(&path);
+  errors.into_values().flatten().collect()
+}
+
+/// Returns the errors for the one isolated file `s` with the minimal std basis.
+pub(crate) fn get_one(s: &str) -> Vec<analysis::Error> {
+  analysis::Analysis::new(MINIMAL.clone(), config::ErrorLines::One).get_one(s)
+}
+
+/// the files are named `f{idx}.sml`, in order, in a root `sources.mlb`.
+pub(crate) fn get_input(
+  ss: &[&str],
+  config: Option<&str>,
+) -> (analysis::input::Root, analysis::input::Input) {
+  let mut m = FxHashMap::<std::path::PathBuf, String>::default();
+  let mut mlb_file = String::new();
+  for (idx, &s) in ss.iter().enumerate() {
+    let file_name = format!("f{idx}.sml");
+    writeln!(mlb_file, "{file_name}").unwrap();
+    let file_name = std::path::PathBuf::from(file_name);
+    m.insert(ROOT.as_path().join(file_name), s.to_owned());
+  }
+  m.insert(ROOT.as_path().join("sources.mlb"), mlb_file);
+  if let Some(config) = config {
+    m.insert(ROOT.as_path().join(config::FILE_NAME), config.to_owned());
+  }
+  let fs = paths::MemoryFileSystem::new(m);
+  let mut root = analysis::input::get_root_dir(ROOT.to_owned());
+  let input = analysis::input::get(&fs, &mut root).expect("in memory fs was not set up correctly");
+  (root, input)
+}
+
 struct Check {
   root: analysis::input::Root,
   files: paths::PathMap<ExpectFile>,
@@ -130,20 +206,8 @@ struct Check {
 }
 
 impl Check {
-  fn new(ss: &[&str], std_basis: analysis::StdBasis) -> Self {
-    let mut m = FxHashMap::<std::path::PathBuf, String>::default();
-    let mut mlb_file = String::new();
-    for (idx, &s) in ss.iter().enumerate() {
-      let file_name = format!("f{idx}.sml");
-      writeln!(mlb_file, "{file_name}").unwrap();
-      let file_name = std::path::PathBuf::from(file_name);
-      m.insert(ROOT.as_path().join(file_name), s.to_owned());
-    }
-    m.insert(ROOT.as_path().join("sources.mlb"), mlb_file);
-    let fs = paths::MemoryFileSystem::new(m);
-    let mut root = analysis::input::get_root_dir(ROOT.to_owned());
-    let input =
-      analysis::input::get(&fs, &mut root).expect("in memory fs was not set up correctly");
+  fn new(ss: &[&str], std_basis: analysis::StdBasis, config: Option<&str>) -> Self {
+    let (root, input) = get_input(ss, config);
     let mut ret = Self {
       root,
       files: input
@@ -168,7 +232,7 @@ impl Check {
       .map(|x| {
         x.want
           .iter()
-          .filter(|(_, e)| matches!(e.kind, ExpectKind::Error))
+          .filter(|(_, e)| matches!(e.kind, ExpectKind::Error | ExpectKind::Code))
           .count()
       })
       .sum();
@@ -176,11 +240,12 @@ impl Check {
       ret.reasons.push(Reason::WantWrongNumError(want_err_len));
     }
     let mut an = analysis::Analysis::new(std_basis, config::ErrorLines::One);
-    let err = an
-      .get_many(&input)
-      .into_iter()
-      .flat_map(|(id, errors)| errors.into_iter().map(move |e| (id, e)))
-      .next();
+    // the errors for a file are sorted by position, but we check the first error from the earliest
+    // pass (lex, parse, etc), since errors in later passes may be caused by errors in earlier ones.
+    let err = an.get_many(&input).into_iter().find_map(|(id, errors)| {
+      let e = errors.into_iter().min_by_key(|e| e.code / 1000)?;
+      Some((id, e))
+    });
     for (&path, file) in ret.files.iter() {
       for (&region, expect) in file.want.iter() {
         if matches!(expect.kind, ExpectKind::Hover) {
@@ -212,7 +277,7 @@ impl Check {
     }
     let had_error = match err {
       Some((id, e)) => {
-        match ret.get_err_reason(id, e.range, e.message) {
+        match ret.get_err_reason(id, e.range, e.code, e.message) {
           Ok(()) => {}
           Err(r) => ret.reasons.push(r),
         }
@@ -230,6 +295,7 @@ impl Check {
     &mut self,
     path: paths::PathId,
     range: analysis::Range,
+    code: u16,
     got: String,
   ) -> Result<(), Reason> {
     let file = &self.files[&path];
@@ -243,17 +309,31 @@ impl Check {
       Region::Line(range.start.line)
     };
     let path_region = path.wrap(region);
-    let want = match file.want.get(&region) {
+    let exp = match file.want.get(&region) {
       None => return Err(Reason::GotButNotWanted(path_region, got)),
-      Some(exp) => match exp.kind {
-        ExpectKind::Error => exp.msg.to_owned(),
-        ExpectKind::Hover => return Err(Reason::GotButNotWanted(path_region, got)),
-      },
+      Some(exp) => exp,
     };
-    if want == got {
-      Ok(())
-    } else {
-      Err(Reason::Mismatched(path_region, want, got))
+    match exp.kind {
+      ExpectKind::Error => {
+        if exp.msg == got {
+          Ok(())
+        } else {
+          Err(Reason::Mismatched(path_region, exp.msg.clone(), got))
+        }
+      }
+      ExpectKind::Code => {
+        if exp.msg == code.to_string() {
+          Ok(())
+        } else {
+          let want = format!("code {}", exp.msg);
+          Err(Reason::Mismatched(
+            path_region,
+            want,
+            format!("code {code}: {got}"),
+          ))
+        }
+      }
+      ExpectKind::Hover => Err(Reason::GotButNotWanted(path_region, got)),
     }
   }
 }
@@ -331,6 +411,7 @@ impl fmt::Display for Expect {
 
 enum ExpectKind {
   Error,
+  Code,
   Hover,
 }
 
@@ -338,6 +419,7 @@ impl fmt::Display for ExpectKind {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
       ExpectKind::Error => f.write_str("error"),
+      ExpectKind::Code => f.write_str("code"),
       ExpectKind::Hover => f.write_str("hover"),
     }
   }
@@ -403,15 +485,21 @@ fn get_expect_comment(line_n: usize, line_s: &str) -> Option<(Region, Expect)> {
   let inner = &inner[non_space_idx..];
   let (col_range, msg) = inner.split_once(' ')?;
   let msg = msg.trim_end_matches(' ');
-  let expect = match msg.strip_prefix("hover: ") {
-    Some(msg) => Expect {
+  let expect = if let Some(msg) = msg.strip_prefix("hover: ") {
+    Expect {
       msg: msg.to_owned(),
       kind: ExpectKind::Hover,
-    },
-    None => Expect {
+    }
+  } else if let Some(msg) = msg.strip_prefix("code: ") {
+    Expect {
+      msg: msg.to_owned(),
+      kind: ExpectKind::Code,
+    }
+  } else {
+    Expect {
       msg: msg.to_owned(),
       kind: ExpectKind::Error,
-    },
+    }
   };
   let (line, exact) = match col_range.chars().next()? {
     '^' => (line_n - 1, true),
@@ -0,0 +1,38 @@
+//! Checking only syntax, without statics.
+
+fn codes(s: &str) -> Vec<u16> {
+  analysis::check_syntax(s)
+    .into_iter()
+    .map(|e| e.code)
+    .collect()
+}
+
+#[test]
+fn ok() {
+  assert!(codes("val x = 1 + 2").is_empty());
+}
+
+#[test]
+fn unmatched_paren() {
+  assert!(codes("val x = (1").contains(&3006));
+}
+
+#[test]
+fn unclosed_string() {
+  assert!(codes("val s = \"hi").contains(&2004));
+}
+
+#[test]
+fn unsupported() {
+  assert_eq!(codes("do print \"hi\""), vec![4999]);
+}
+
+#[test]
+fn vector() {
+  assert_eq!(codes("val _ = #[1]"), vec![4999]);
+}
+
+#[test]
+fn no_statics() {
+  assert!(codes("val _ : string = nope 1").is_empty());
+}
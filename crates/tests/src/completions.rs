@@ -0,0 +1,90 @@
+//! Completions for the names in scope.
+
+use crate::check::analyze;
+use analysis::Assoc;
+
+/// returns the completions at `(line, character)`.
+fn get(s: &str, (line, character): (u32, u32)) -> Vec<analysis::CompletionItem> {
+  let (an, path) = analyze(s);
+  let pos = analysis::Position { line, character };
+  an.completions(path.wrap(pos))
+}
+
+/// asserts the completion named `label` has the given fixity.
+#[track_caller]
+fn check_infix(items: &[analysis::CompletionItem], label: &str, want: Option<(u16, Assoc)>) {
+  let item = items
+    .iter()
+    .find(|x| x.label == label)
+    .expect("no completion with that label");
+  let got = item.infix.map(|x| (x.prec, x.assoc));
+  assert_eq!(got, want);
+}
+
+#[test]
+fn infix() {
+  let items = get(
+    r#"
+infix 5 <+>
+fun a <+> b = a + b
+val x = 3
+"#,
+    (4, 0),
+  );
+  check_infix(&items, "<+>", Some((5, Assoc::Left)));
+  check_infix(&items, "::", Some((5, Assoc::Right)));
+  check_infix(&items, "+", Some((6, Assoc::Left)));
+  check_infix(&items, "x", None);
+}
+
+#[test]
+fn nonfix() {
+  let items = get(
+    r#"
+infixr 2 <+>
+fun a <+> b = a + b
+nonfix <+>
+"#,
+    (4, 0),
+  );
+  check_infix(&items, "<+>", None);
+}
+
+/// fixity is not looked up for qualified names, so an unqualified `<+>` being infix has no effect
+/// on `S.<+>`.
+#[test]
+fn qualified_no_fixity() {
+  let items = get(
+    r#"
+structure S = struct
+  fun <+> (a, b) = a + b
+end
+infix <+>
+val _ = S.
+"#,
+    (5, 10),
+  );
+  check_infix(&items, "<+>", None);
+}
+
+#[test]
+fn fn_param() {
+  let items = get(
+    r#"
+val _ = fn apple => apple
+"#,
+    (1, 20),
+  );
+  assert!(items.iter().any(|x| x.label == "apple"));
+}
+
+#[test]
+fn let_body() {
+  let items = get(
+    r#"
+val _ = let val banana = 1 in banana end
+"#,
+    (1, 30),
+  );
+  assert!(items.iter().any(|x| x.label == "banana"));
+}
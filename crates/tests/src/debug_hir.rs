@@ -0,0 +1,64 @@
+//! Dumping the lowered HIR of a file.
+
+use crate::check::analyze;
+
+fn get(s: &str) -> String {
+  let (an, path) = analyze(s);
+  an.debug_hir(path).expect("no HIR for path")
+}
+
+/// asserts the trimmed lines of `got` contain `want` as a contiguous sequence.
+#[track_caller]
+fn check_lines(got: &str, want: &[&str]) {
+  let lines: Vec<_> = got.lines().map(str::trim).collect();
+  assert!(
+    lines.windows(want.len()).any(|w| w == want),
+    "did not find {want:?} in:\n{got}"
+  );
+}
+
+#[test]
+fn val() {
+  let got = get("val x = 3");
+  check_lines(&got, &["Dec::Val", "ValBind", "Pat::Con x", "Exp::SCon 3"]);
+}
+
+#[test]
+fn nested() {
+  let got = get(
+    r#"
+structure S = struct
+  datatype 'a t = A | B of 'a
+  fun f (B x) = x
+end
+"#,
+  );
+  check_lines(&got, &["StrBind S", "StrExp::Struct"]);
+  check_lines(
+    &got,
+    &["DatBind 'a t", "ConBind A", "ConBind B", "Ty::Var 'a"],
+  );
+  check_lines(&got, &["Pat::Con B", "Pat::Con x"]);
+  let struct_line = got.lines().find(|x| x.trim() == "StrExp::Struct").unwrap();
+  let dat_line = got.lines().find(|x| x.trim() == "DatBind 'a t").unwrap();
+  let indent = |s: &str| s.len() - s.trim_start().len();
+  assert!(indent(struct_line) < indent(dat_line));
+}
+
+#[test]
+fn missing() {
+  let (an, path) = analyze("val x = ");
+  let got = an.debug_hir(path).unwrap();
+  check_lines(&got, &["Pat::Con x", "<none>"]);
+}
+
+#[test]
+fn deterministic() {
+  let s = r#"
+val _ = fn (x, y) => if x then y else "no"
+val _ = {a = 1, b = #"c"}
+"#;
+  let got = get(s);
+  check_lines(&got, &["b =", "Exp::SCon #\"c\""]);
+  assert_eq!(got, get(s));
+}
@@ -0,0 +1,75 @@
+//! Reporting uses of deprecated std basis items.
+
+use crate::check::{check_with_config, get_config_errors};
+
+const CONFIG: &str = r#"
+version = 1
+[language]
+deprecated = ["List.nth"]
+"#;
+
+#[test]
+fn use_qualified() {
+  check_with_config(
+    CONFIG,
+    r#"
+val _ = List.nth ([1, 2], 0)
+(**     ^^^^^^^^ deprecated: List.nth *)
+"#,
+  );
+}
+
+#[test]
+fn use_opened() {
+  check_with_config(
+    CONFIG,
+    r#"
+local
+  open List
+in
+  val _ = nth ([1, 2], 0)
+(**       ^^^ deprecated: List.nth *)
+end
+"#,
+  );
+}
+
+#[test]
+fn shadowed() {
+  check_with_config(
+    CONFIG,
+    r#"
+structure List = struct
+  fun nth (xs, _) = xs
+end
+val _ = List.nth ([1, 2], 0)
+"#,
+  );
+}
+
+#[test]
+fn other_not_deprecated() {
+  check_with_config(
+    CONFIG,
+    r#"
+val _ = List.length [1, 2]
+"#,
+  );
+}
+
+#[test]
+fn undefined() {
+  let config = r#"
+version = 1
+[language]
+deprecated = ["List.nth", "List.nht"]
+"#;
+  let errors = get_config_errors(config);
+  assert_eq!(errors.len(), 1);
+  let error = &errors[0];
+  assert_eq!(error.code, 1013);
+  assert_eq!(error.message, "undefined deprecated value: List.nht");
+  assert_eq!(error.range.start.line, 3);
+  assert_eq!(error.range.start.character, 26);
+  assert_eq!(error.range.end.character, 36);
+}
@@ -35,7 +35,7 @@ fn functor_re_typecheck_or_not_1() {
 fun id x = x
 functor F (X: sig type t end) = struct
   val f = id id
-(**       ^^^^^ cannot bind expansive polymorphic expression *)
+(**       ^^^^^ cannot bind expansive polymorphic expression; annotate the binding, e.g. `val f : _ -> _ = ...` *)
 end
 structure A = F (struct type t = int end)
 structure B = F (struct type t = bool end)
@@ -52,7 +52,7 @@ fn functor_re_typecheck_or_not_2() {
 fun id x = x
 functor F (X: sig type t end) = struct
   val f = id id
-(**       ^^^^^ cannot bind expansive polymorphic expression *)
+(**       ^^^^^ cannot bind expansive polymorphic expression; annotate the binding, e.g. `val f : _ -> _ = ...` *)
 end
 structure A = F (struct type t = int end)
 structure B = F (struct type t = bool end)
@@ -3,7 +3,7 @@
 //!
 //! [1]: http://www.mlton.org/SMLNJDeviations
 
-use crate::check::{check, fail};
+use crate::check::check;
 
 #[test]
 fn op_in_val() {
@@ -17,6 +17,30 @@ end
   );
 }
 
+#[test]
+fn op_in_val_and() {
+  check(
+    r#"
+signature FOO = sig
+  val x : int and op f : int -> int
+(**               ^^ expected a name *)
+end
+"#,
+  );
+}
+
+#[test]
+fn op_in_val_alpha() {
+  check(
+    r#"
+signature FOO = sig
+  val op f : int -> int
+(**   ^^ expected a name *)
+end
+"#,
+  );
+}
+
 #[test]
 fn op_star() {
   check(
@@ -72,7 +96,7 @@ fn rebind_cons() {
   check(
     r#"
 fun op :: () = ()
-(** ^^^^^^^^^^^^^ missing argument for constructor pattern *)
+(** ^^^^^^^^^^^^^ missing argument for constructor pattern: :: expects an argument of type ?a * ?a list *)
 "#,
   );
 }
@@ -82,7 +106,7 @@ fn rebind_ref() {
   check(
     r#"
 fun ref () = ()
-(** ^^^^^^^^^^^ missing argument for constructor pattern *)
+(** ^^^^^^^^^^^ missing argument for constructor pattern: ref expects an argument of type ?a *)
 "#,
   );
 }
@@ -204,7 +228,7 @@ end
 
 #[test]
 fn sharing_via_abbreviation_short() {
-  fail(
+  check(
     r#"
 signature SIG = sig type t = int type u = int sharing type t = u end
 (**                 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ cannot share between type abbreviations *)
@@ -214,7 +238,7 @@ signature SIG = sig type t = int type u = int sharing type t = u end
 
 #[test]
 fn sharing_via_abbreviation_long() {
-  fail(
+  check(
     r#"
 signature SIG = sig type t = int * int type u = int * int sharing type t = u end
 (**                 ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ cannot share between type abbreviations *)
@@ -243,7 +267,6 @@ signature S = sig
   type u
   type v
   sharing type t = u and type u = v
-(**                  ^^^ expected `end` *)
 end
 "#,
   );
@@ -316,7 +339,7 @@ fn type_inf_context_1() {
     r#"
 structure S = struct
   val z = (fn x => x) []
-(**       ^^^^^^^^^^^^^^ cannot bind expansive polymorphic expression *)
+(**       ^^^^^^^^^^^^^^ cannot bind expansive polymorphic expression; annotate the binding, e.g. `val z : _ list = ...` *)
   val y = z :: [true] :: nil
 end
 "#,
@@ -329,7 +352,7 @@ fn type_inf_context_2() {
     r#"
 structure S : sig val z : bool list end = struct
   val z = (fn x => x) []
-(**       ^^^^^^^^^^^^^^ cannot bind expansive polymorphic expression *)
+(**       ^^^^^^^^^^^^^^ cannot bind expansive polymorphic expression; annotate the binding, e.g. `val z : _ list = ...` *)
 end
 "#,
   );
@@ -0,0 +1,74 @@
+//! Reporting integer division or modulo by a literal zero.
+
+use crate::check::{check, check_with_config};
+
+const CONFIG: &str = r#"
+version = 1
+[language]
+div-by-zero = true
+"#;
+
+#[test]
+fn div_literal() {
+  check_with_config(
+    CONFIG,
+    r#"
+val _ = 5 div 0
+(**     ^^^^^^^ division by zero *)
+"#,
+  );
+}
+
+#[test]
+fn mod_literal() {
+  check_with_config(
+    CONFIG,
+    r#"
+val _ = 5 mod 0
+(**     ^^^^^^^ division by zero *)
+"#,
+  );
+}
+
+#[test]
+fn qualified() {
+  check_with_config(
+    CONFIG,
+    r#"
+val _ = Int.div (5, 0)
+(**     ^^^^^^^^^^^^^^ division by zero *)
+"#,
+  );
+}
+
+#[test]
+fn not_literal() {
+  check_with_config(
+    CONFIG,
+    r#"
+fun f (n : int) = 5 div n
+"#,
+  );
+}
+
+#[test]
+fn shadowed() {
+  check_with_config(
+    CONFIG,
+    r#"
+fun f (x : int, y : int) = x + y
+infix 7 div
+val op div = f
+val _ = 5 div 0
+"#,
+  );
+}
+
+#[test]
+fn disabled() {
+  check(
+    r#"
+val _ = 5 div 0
+"#,
+  );
+}
@@ -0,0 +1,88 @@
+//! Outlines of the symbols declared in a file.
+
+use crate::check::analyze;
+use std::fmt::Write as _;
+
+/// returns an outline of the symbols, one per line, with their kind, name, and start and end lines.
+/// children are indented under their parent.
+fn outline(s: &str) -> String {
+  let (an, path) = analyze(s);
+  let mut ret = String::new();
+  go(&mut ret, 0, &an.document_symbols(path));
+  ret
+}
+
+fn go(ac: &mut String, indent: usize, symbols: &[analysis::Symbol]) {
+  for symbol in symbols {
+    let start = symbol.range.start.line;
+    let end = symbol.range.end.line;
+    let pad = "  ".repeat(indent);
+    writeln!(ac, "{pad}{:?} {} {start}-{end}", symbol.kind, symbol.name).unwrap();
+    go(ac, indent + 1, &symbol.children);
+  }
+}
+
+#[test]
+fn nested_structures() {
+  let got = outline(
+    r#"
+structure A = struct
+  structure B = struct
+    val x = 1
+  end
+  val y = 2
+end
+val z = 3
+"#,
+  );
+  let want = r#"Structure A 1-6
+  Structure B 2-4
+    Value x 3-3
+  Value y 5-5
+Value z 7-7
+"#;
+  assert_eq!(got, want);
+}
+
+#[test]
+fn fun_cases_collapse() {
+  let got = outline(
+    r#"
+fun f 0 = 1
+  | f n = n * f (n - 1)
+fun g x = x
+and h y = y
+"#,
+  );
+  let want = r#"Value f 1-2
+Value g 3-3
+Value h 4-4
+"#;
+  assert_eq!(got, want);
+}
+
+#[test]
+fn signature_and_datatype() {
+  let got = outline(
+    r#"
+signature SIG = sig
+  type t
+  val x : t
+end
+datatype d = A | B of int
+functor F (X : SIG) = struct
+  exception E
+end
+"#,
+  );
+  let want = r#"Signature SIG 1-4
+  Type t 2-2
+  Value x 3-3
+Type d 5-5
+  Constructor A 5-5
+  Constructor B 5-5
+Functor F 6-8
+  Exception E 7-7
+"#;
+  assert_eq!(got, want);
+}
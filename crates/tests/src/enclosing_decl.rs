@@ -0,0 +1,103 @@
+//! Getting the declarations enclosing a position.
+
+use crate::check::analyze;
+
+/// asserts the start and end lines (0-indexed) of the declarations enclosing `(line, character)`
+/// are `want`, innermost first.
+#[track_caller]
+fn check_lines(s: &str, (line, character): (u32, u32), want: &[(u32, u32)]) {
+  let (an, path) = analyze(s);
+  let pos = analysis::Position { line, character };
+  let got: Vec<_> = an
+    .enclosing_decls(path.wrap(pos))
+    .into_iter()
+    .map(|range| (range.start.line, range.end.line))
+    .collect();
+  assert_eq!(got, want);
+}
+
+#[test]
+fn val_in_structure() {
+  check_lines(
+    r#"
+structure S = struct
+  val x = 1
+  val y = 2
+end
+"#,
+    (2, 6),
+    &[(2, 2), (1, 4)],
+  );
+}
+
+#[test]
+fn nested_let() {
+  check_lines(
+    r#"
+fun f x =
+  let
+    val y =
+      x
+  in
+    y
+  end
+"#,
+    (4, 6),
+    &[(3, 4), (1, 7)],
+  );
+}
+
+#[test]
+fn spec_in_signature() {
+  check_lines(
+    r#"
+signature SIG = sig
+  type t
+  val x : t
+end
+"#,
+    (3, 10),
+    &[(3, 3), (1, 4)],
+  );
+}
+
+#[test]
+fn local() {
+  check_lines(
+    r#"
+local
+  val a = 1
+in
+  val b = a
+end
+"#,
+    (4, 10),
+    &[(4, 4), (1, 5)],
+  );
+}
+
+#[test]
+fn statics_error() {
+  check_lines(
+    r#"
+structure S = struct
+  val x : string = 1 + nope
+end
+"#,
+    (2, 23),
+    &[(2, 2), (1, 3)],
+  );
+}
+
+#[test]
+fn top_level() {
+  check_lines(
+    r#"
+val x = 1
+
+val y = 2
+"#,
+    (3, 4),
+    &[(3, 3)],
+  );
+}
@@ -0,0 +1,144 @@
+//! Equality types and equality type variables, like `''a`.
+
+use crate::check::{check, check_with_std_basis};
+
+#[test]
+fn equality_ty_var() {
+  check(
+    r#"
+val ''a f : ''a -> bool = fn x => x = x
+"#,
+  );
+}
+
+#[test]
+fn ordinary_ty_var() {
+  check(
+    r#"
+fun 'a same (x : 'a) (y : 'a) = x = y
+(**                             ^ operator = requires an equality type, found 'a *)
+"#,
+  );
+}
+
+#[test]
+fn fn_ty() {
+  check(
+    r#"
+val _ = (fn x => x = x) (fn y => y)
+(**                      ^^^^^^^^^ operator = requires an equality type, found ?a -> ?a *)
+"#,
+  );
+}
+
+#[test]
+fn fn_ty_operand() {
+  check(
+    r#"
+fun f (g : int -> int) = g = g
+(**                      ^ operator = requires an equality type, found int -> int *)
+"#,
+  );
+}
+
+#[test]
+fn equality_tys() {
+  check(
+    r#"
+datatype d = A | B of int list
+val _ = A = B [1, 2] andalso ref A <> ref (B [])
+val _ = (1, "a") = (2, "b") andalso {x = #"c"} <> {x = #"d"}
+"#,
+  );
+}
+
+#[test]
+fn inferred_equality() {
+  check(
+    r#"
+fun eq x y = x = y
+val _ = eq 1 2
+val _ = eq (fn x => x) (fn y => y)
+(**         ^^^^^^^^^ operator = requires an equality type, found ?a -> ?a *)
+"#,
+  );
+}
+
+#[test]
+fn real_array() {
+  check_with_std_basis(
+    r#"
+val a = Array.fromList [1.0]
+val _ = a = a
+"#,
+  );
+}
+
+#[test]
+fn datatype_real_field() {
+  check(
+    r#"
+datatype t = T of real
+val x = T 1.0
+val _ = x = x
+(**     ^ operator = requires an equality type, found t *)
+"#,
+  );
+}
+
+#[test]
+fn datatype_fn_field() {
+  check(
+    r#"
+datatype t = T of int -> int
+val x = T (fn y => y)
+val _ = x = x
+(**     ^ operator = requires an equality type, found t *)
+"#,
+  );
+}
+
+#[test]
+fn datatype_recursive() {
+  check(
+    r#"
+datatype t = L | N of t * int * t
+val x = N (L, 1, L)
+val _ = x = x
+"#,
+  );
+}
+
+#[test]
+fn datatype_mutually_recursive() {
+  check(
+    r#"
+datatype a = A of b | Z
+and b = B of a | R of real
+val x = A (R 1.0)
+val _ = x = x
+(**     ^ operator = requires an equality type, found a *)
+"#,
+  );
+}
+
+#[test]
+fn abstract_ty() {
+  check(
+    r#"
+structure S :> sig type t val x : t end = struct type t = int val x = 1 end
+val _ = S.x = S.x
+(**     ^^^ operator = requires an equality type, found t *)
+"#,
+  );
+}
+
+#[test]
+fn abstract_eqtype() {
+  check(
+    r#"
+structure S :> sig eqtype t val x : t end = struct type t = int val x = 1 end
+val _ = S.x = S.x
+"#,
+  );
+}
@@ -0,0 +1,46 @@
+//! The catalog of error codes.
+
+use crate::check::get_one;
+use std::path::Path;
+
+#[test]
+fn unique() {
+  let codes = analysis::error_codes();
+  assert!(!codes.is_empty());
+  for pair in codes.windows(2) {
+    assert_ne!(pair[0].0, pair[1].0, "duplicate code");
+  }
+}
+
+#[test]
+fn same_as_docs() {
+  let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../docs/errors.md");
+  let contents = std::fs::read_to_string(path).unwrap();
+  let documented: Vec<u16> = contents
+    .lines()
+    .filter_map(|line| line.strip_prefix("## ")?.parse().ok())
+    .collect();
+  let codes: Vec<_> = analysis::error_codes()
+    .into_iter()
+    .map(|(code, _)| code)
+    .collect();
+  assert_eq!(codes, documented);
+}
+
+#[test]
+fn reported_codes_listed() {
+  let codes = analysis::error_codes();
+  for s in [
+    "val _ = nope",
+    "val _ = 1 + \"hi\"",
+    "fun f 0 = 1 | g _ = 2",
+    "val s = \"oops",
+  ] {
+    let e = get_one(s).into_iter().next().expect("no error");
+    assert!(
+      codes.iter().any(|&(code, _)| code == e.code),
+      "unlisted code {}",
+      e.code
+    );
+  }
+}
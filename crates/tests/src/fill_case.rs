@@ -0,0 +1,170 @@
+//! Filling a `case` with arms for the variants of the head's type.
+
+use crate::check::analyze;
+
+/// asserts the variants for the `case` at `(line, character)` are `want`.
+#[track_caller]
+fn check_variants(s: &str, (line, character): (u32, u32), want: &[(&str, bool)]) {
+  let (an, path) = analyze(s);
+  let pos = analysis::Position { line, character };
+  let got = an
+    .missing_case_variants(path.wrap(pos))
+    .expect("no variants");
+  let got: Vec<_> = got
+    .iter()
+    .map(|(name, has_arg)| (name.as_str(), *has_arg))
+    .collect();
+  assert_eq!(got, want);
+}
+
+/// asserts filling the `case` at `(line, character)` gives `want`.
+#[track_caller]
+fn check_fill(s: &str, (line, character): (u32, u32), want: &str) {
+  let (an, path) = analyze(s);
+  let pos = analysis::Position { line, character };
+  let (_, got) = an.fill_case(path.wrap(pos)).expect("no fill");
+  assert_eq!(got, want);
+}
+
+#[test]
+fn variants() {
+  check_variants(
+    r#"
+datatype d = A | B of int | C
+fun f (x : d) = case x of
+"#,
+    (2, 16),
+    &[("A", false), ("B", true), ("C", false)],
+  );
+}
+
+#[test]
+fn variants_infix() {
+  check_variants(
+    r#"
+infixr 5 :::
+datatype d = E | ::: of int * d
+fun f (x : d) = case x of
+"#,
+    (3, 16),
+    &[("E", false), ("op :::", true)],
+  );
+}
+
+#[test]
+fn variants_not_case() {
+  let (an, path) = analyze(
+    r#"
+datatype d = A | B
+val _ = A
+"#,
+  );
+  let pos = analysis::Position {
+    line: 2,
+    character: 8,
+  };
+  assert!(an.missing_case_variants(path.wrap(pos)).is_none());
+}
+
+#[test]
+fn fill() {
+  check_fill(
+    r#"
+datatype d = A | B of int
+fun f (x : d) = case x of
+"#,
+    (2, 16),
+    "    A => _\n  | B _ => _",
+  );
+}
+
+#[test]
+fn fill_infix() {
+  check_fill(
+    r#"
+infixr 5 :::
+datatype d = E | ::: of int * d
+fun f (x : d) = case x of
+"#,
+    (3, 16),
+    "    E => _\n  | op ::: _ => _",
+  );
+}
+
+#[test]
+fn variants_some_covered() {
+  check_variants(
+    r#"
+datatype d = A | B of int | C
+fun f (x : d) = case x of B _ => 1
+"#,
+    (2, 16),
+    &[("A", false), ("C", false)],
+  );
+}
+
+#[test]
+fn variants_nested_covered() {
+  check_variants(
+    r#"
+datatype d = A | B of d | C
+fun f (x : d) = case x of B A => 1 | (C : d) => 2
+"#,
+    (2, 16),
+    &[("A", false)],
+  );
+}
+
+#[test]
+fn variants_or_covered() {
+  check_variants(
+    r#"
+datatype d = A | B of int | C
+fun f (x : d) = case x of A | C => 1
+"#,
+    (2, 16),
+    &[("B", true)],
+  );
+}
+
+#[test]
+fn variants_wildcard() {
+  let (an, path) = analyze(
+    r#"
+datatype d = A | B of int
+fun f (x : d) = case x of A => 1 | _ => 2
+"#,
+  );
+  let pos = analysis::Position {
+    line: 2,
+    character: 16,
+  };
+  assert!(an.missing_case_variants(path.wrap(pos)).is_none());
+}
+
+#[test]
+fn variants_variable() {
+  let (an, path) = analyze(
+    r#"
+datatype d = A | B of int
+fun f (x : d) = case x of y => 1
+"#,
+  );
+  let pos = analysis::Position {
+    line: 2,
+    character: 16,
+  };
+  assert!(an.missing_case_variants(path.wrap(pos)).is_none());
+}
+
+#[test]
+fn fill_some_covered() {
+  check_fill(
+    r#"
+datatype d = A | B of int
+fun f (x : d) = case x of B _ => 1
+"#,
+    (2, 16),
+    "  | A => _",
+  );
+}
@@ -1,4 +1,6 @@
-use crate::check::check;
+use crate::check::{analyze, analyze_multi, check, check_with_std_basis};
+use analysis::Assoc;
+use sml_syntax::ast::AstNode as _;
 
 #[test]
 fn smoke() {
@@ -82,7 +84,132 @@ fn same_fixity_diff_assoc() {
 infix <<
 infixr >>
 val _ = 1 << 2 >> 3
-(**            ^^ consecutive infix names with same fixity but different associativity *)
+(**       ^^^^^^^ consecutive infix names `<<` and `>>` with same fixity but different associativity *)
+"#,
+  );
+}
+
+#[test]
+fn same_fixity_diff_assoc_std_basis_op() {
+  check(
+    r#"
+infixr 4 >
+val _ = 1 < 2 > 3
+(**       ^^^^^ consecutive infix names `<` and `>` with same fixity but different associativity *)
+"#,
+  );
+}
+
+#[test]
+fn same_fixity_diff_assoc_pat() {
+  check(
+    r#"
+infix <<
+infixr >>
+datatype t = op << of int * int | op >> of int * int
+fun f (a << b >> c) = a
+(**      ^^^^^^^ consecutive infix names `<<` and `>>` with same fixity but different associativity *)
+"#,
+  );
+}
+
+#[test]
+fn api_std_basis() {
+  let (an, path) = analyze("val _ = 1 + 2");
+  let got = an.fixity(path, "::").expect("should be infix");
+  assert_eq!((got.prec, got.assoc), (5, Assoc::Right));
+  assert!(an.fixity(path, "nope").is_none());
+}
+
+#[test]
+fn api_declared() {
+  let (an, path) = analyze("infixr 4 <+>");
+  let got = an.fixity(path, "<+>").expect("should be infix");
+  assert_eq!((got.prec, got.assoc), (4, Assoc::Right));
+}
+
+#[test]
+fn api_nonfix() {
+  let (an, path) = analyze("nonfix +");
+  assert!(an.fixity(path, "+").is_none());
+}
+
+#[test]
+fn api_across_files() {
+  let (an, paths) = analyze_multi(&["infix 2 <+>", "val _ = 3", "nonfix <+>"]);
+  assert_eq!(paths.len(), 3);
+  assert!(an.fixity(paths[0], "<+>").is_some());
+  assert!(an.fixity(paths[1], "<+>").is_some());
+  assert!(an.fixity(paths[2], "<+>").is_none());
+}
+
+/// returns the expression `s` with every infix application parenthesized, using the std basis
+/// fixities.
+fn shape(s: &str) -> String {
+  let mut fix_env = mlb_statics::STD_BASIS_FIX_ENV.clone();
+  let (_, parsed, _) = mlb_statics::start_source_file(
+    sml_lower::Config::default(),
+    &format!("val _ = {s}"),
+    &mut fix_env,
+  );
+  assert!(
+    parsed.errors.is_empty(),
+    "parse errors: {:?}",
+    parsed.errors
+  );
+  let exp = parsed
+    .root
+    .syntax()
+    .descendants()
+    .find_map(sml_syntax::ast::InfixExp::cast)
+    .expect("no infix exp");
+  shape_exp(&sml_syntax::ast::Exp::InfixExp(exp))
+}
+
+fn shape_exp(exp: &sml_syntax::ast::Exp) -> String {
+  match exp {
+    sml_syntax::ast::Exp::InfixExp(exp) => {
+      let lhs = exp.lhs().map(|x| shape_exp(&x)).unwrap_or_default();
+      let op = exp.name_star_eq().map(|x| x.token.text().to_owned());
+      let rhs = exp.rhs().map(|x| shape_exp(&x)).unwrap_or_default();
+      format!("({lhs} {} {rhs})", op.unwrap_or_default())
+    }
+    _ => exp.syntax().to_string().trim().to_owned(),
+  }
+}
+
+#[test]
+fn prec_0_left_assoc() {
+  assert_eq!(shape("a before b before c"), "((a before b) before c)");
+}
+
+#[test]
+fn prec_0_mixed() {
+  assert_eq!(shape("a + b before c * d"), "((a + b) before (c * d))");
+  assert_eq!(
+    shape("a before b + c before d"),
+    "((a before (b + c)) before d)"
+  );
+}
+
+#[test]
+fn prec_0_user() {
+  check(
+    r#"
+infix 0 <<
+fun a << b = a - b
+val _ = 10 << 3 << 2 : int
+"#,
+  );
+  assert_eq!(shape("a := b before c"), "((a := b) before c)");
+}
+
+#[test]
+fn prec_0_std_basis() {
+  check_with_std_basis(
+    r#"
+val r = ref 0
+val _ : int = 1 + 2 before r := 3 before ignore 4
 "#,
   );
 }
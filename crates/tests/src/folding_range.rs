@@ -0,0 +1,79 @@
+//! Folding ranges, computed from the syntax tree.
+
+use crate::check::analyze;
+
+/// returns the start and end lines of the folding ranges, which are 0-indexed.
+fn lines(s: &str) -> Vec<(u32, u32)> {
+  let (an, path) = analyze(s);
+  an.folding_ranges(path)
+    .into_iter()
+    .map(|range| (range.start.line, range.end.line))
+    .collect()
+}
+
+#[test]
+fn single_line() {
+  assert!(lines("structure S = struct val x = let val y = 1 in y end end").is_empty());
+}
+
+#[test]
+fn nested() {
+  let got = lines(
+    r#"
+structure S = struct
+  val x =
+    let
+      val y = 1
+    in
+      y
+    end
+end
+"#,
+  );
+  assert_eq!(got, vec![(1, 8), (3, 7)]);
+}
+
+#[test]
+fn sig_and_local() {
+  let got = lines(
+    r#"
+signature SIG = sig
+  val x : int
+end
+local
+  val y = 1
+in
+  val z = y
+end
+"#,
+  );
+  assert_eq!(got, vec![(1, 3), (4, 8)]);
+}
+
+#[test]
+fn comment() {
+  let got = lines(
+    r#"
+(* a comment
+   on two lines *)
+val x = 3
+(* not this one *)
+"#,
+  );
+  assert_eq!(got, vec![(1, 2)]);
+}
+
+#[test]
+fn statics_error() {
+  let got = lines(
+    r#"
+val x =
+  let
+    val y = nope
+  in
+    y
+  end
+"#,
+  );
+  assert_eq!(got, vec![(2, 6)]);
+}
@@ -0,0 +1,198 @@
+//! Formatting source files.
+
+use crate::check::analyze;
+
+#[track_caller]
+fn check(s: &str, want: &str) {
+  let (an, path) = analyze(s);
+  let got = an.format(path).expect("no formatted text");
+  assert_eq!(want, got);
+  let (an, path) = analyze(want);
+  let again = an.format(path).expect("no formatted text");
+  assert_eq!(want, again, "not idempotent");
+}
+
+#[test]
+fn already_formatted() {
+  let s = r#"
+structure S = struct
+  val x = 1
+  fun f (x :: xs) = x + f xs
+    | f [] = 0
+end
+"#;
+  check(s, s);
+}
+
+#[test]
+fn struct_body() {
+  check(
+    r#"
+structure S = struct
+val x = 1
+    val y =
+      x+2
+end
+"#,
+    r#"
+structure S = struct
+  val x = 1
+  val y =
+    x + 2
+end
+"#,
+  );
+}
+
+#[test]
+fn nested() {
+  check(
+    r#"
+structure S =
+struct
+structure T = struct
+      val x = 1
+      end
+end
+"#,
+    r#"
+structure S =
+struct
+  structure T = struct
+    val x = 1
+  end
+end
+"#,
+  );
+}
+
+#[test]
+fn sig_body() {
+  check(
+    r#"
+signature SIG =
+  sig
+  val x : int
+       type t
+  end
+"#,
+    r#"
+signature SIG =
+  sig
+    val x : int
+    type t
+  end
+"#,
+  );
+}
+
+#[test]
+fn let_and_local() {
+  check(
+    r#"
+local
+val a = 1
+in
+fun f x =
+  let
+      val y = x+a
+  in y end
+end
+"#,
+    r#"
+local
+  val a = 1
+in
+  fun f x =
+    let
+      val y = x + a
+    in y end
+end
+"#,
+  );
+}
+
+#[test]
+fn case_arms_keep_relative_indent() {
+  check(
+    r#"
+structure S = struct
+    fun f x =
+      case x of
+        1 => 2
+      | _ => 3
+end
+"#,
+    r#"
+structure S = struct
+  fun f x =
+    case x of
+      1 => 2
+    | _ => 3
+end
+"#,
+  );
+}
+
+#[test]
+fn infix_spacing() {
+  check(
+    r#"
+infix 6 ++
+fun a++b = a  +  b
+val x = 1+2*3 :: [op+ (1, 2)]
+val y = x=x
+fun g (x::_) = x
+  | g [] = 0
+"#,
+    r#"
+infix 6 ++
+fun a ++ b = a + b
+val x = 1 + 2 * 3 :: [op+ (1, 2)]
+val y = x = x
+fun g (x :: _) = x
+  | g [] = 0
+"#,
+  );
+}
+
+#[test]
+fn comments() {
+  check(
+    r#"
+structure S = struct
+(* on x *)
+val   x = 1   (* trailing *)
+      (* before end *)
+end
+(* after *)
+"#,
+    r#"
+structure S = struct
+  (* on x *)
+  val x = 1 (* trailing *)
+  (* before end *)
+end
+(* after *)
+"#,
+  );
+}
+
+#[test]
+fn multi_line_comment() {
+  let s = r#"
+structure S = struct
+  (* a comment
+       spanning lines
+   *)
+  val x = 1
+end
+"#;
+  check(s, s);
+}
+
+#[test]
+fn parse_error() {
+  let (an, path) = analyze("val x = (1");
+  assert!(an.format(path).is_none());
+}
@@ -1,4 +1,4 @@
-use crate::check::check;
+use crate::check::{check, get_many_with_config};
 
 #[test]
 fn no_over_generalize_infer_val() {
@@ -77,3 +77,55 @@ fun go n =
 "#,
   );
 }
+
+/// asserts the one error in `s` has this message, range, and related ranges, as 0-indexed `(line,
+/// start character, end character)`.
+#[track_caller]
+fn check_related(s: &str, message: &str, range: (u32, u32, u32), related: &[(u32, u32, u32)]) {
+  let errors = get_many_with_config(s, None);
+  assert_eq!(errors.len(), 1, "{errors:?}");
+  let e = &errors[0];
+  assert_eq!(e.message, message);
+  let to_tuple = |r: &analysis::Range| (r.start.line, r.start.character, r.end.character);
+  assert_eq!(to_tuple(&e.range), range);
+  let got: Vec<_> = e.related.iter().map(|(r, _)| to_tuple(r)).collect();
+  assert_eq!(got, related);
+}
+
+#[test]
+fn not_generalized_fn_param() {
+  check_related(
+    r#"
+fun f g = (g 1, g "hi")
+"#,
+    "expected int, found string",
+    (1, 18, 22),
+    &[(1, 6, 7)],
+  );
+}
+
+#[test]
+fn not_generalized_let() {
+  check_related(
+    r#"
+fun h x =
+  let val k = x in (k 1, k "hi") end
+"#,
+    "expected int, found string",
+    (2, 27, 31),
+    &[(2, 10, 11)],
+  );
+}
+
+#[test]
+fn generalized_no_related() {
+  check_related(
+    r#"
+fun f (x : int) = x
+val _ = f "hi"
+"#,
+    "expected int, found string",
+    (2, 10, 14),
+    &[],
+  );
+}
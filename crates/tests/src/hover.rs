@@ -20,3 +20,136 @@ val _ = (   3, "hi", Uh false)
 "#,
   );
 }
+
+#[test]
+fn ty_var_names() {
+  check(
+    r#"
+fun id x = x
+val _ = id
+(**     ^ hover: 'a -> 'a *)
+"#,
+  );
+}
+
+#[test]
+fn ty_var_names_in_order() {
+  check(
+    r#"
+fun swap (x, y) = (y, x)
+val _ = swap
+(**     ^ hover: 'a * 'b -> 'b * 'a *)
+"#,
+  );
+}
+
+#[test]
+fn ty_var_names_equality() {
+  check(
+    r#"
+fun eq x y = x = y
+val _ = eq
+(**     ^ hover: ''a -> ''a -> bool *)
+"#,
+  );
+}
+
+#[test]
+fn meta_var_names() {
+  check(
+    r#"
+fun pair x y = (x, y)
+val _ = pair
+(**     ^ hover: ?a -> ?b -> ?a * ?b *)
+"#,
+  );
+}
+
+/// returns the hover for the smallest expression containing the selection from `start` to `end` on
+/// `line`, and the text of that expression.
+fn selection(s: &str, line: u32, start: u32, end: u32) -> (String, String) {
+  let (an, path) = crate::check::analyze(s);
+  let range = analysis::Range {
+    start: analysis::Position {
+      line,
+      character: start,
+    },
+    end: analysis::Position {
+      line,
+      character: end,
+    },
+  };
+  let (md, range) = an.get_selection_ty_md(path.wrap(range)).expect("no hover");
+  assert_eq!(range.start.line, range.end.line);
+  let text = s.lines().nth(range.start.line as usize).unwrap();
+  let text = &text[range.start.character as usize..range.end.character as usize];
+  (md, text.to_owned())
+}
+
+const NESTED_APP: &str = r#"
+fun f (x : int) (y : bool) = x
+fun g (x : string) = 3
+val _ = f (g "hi") true
+"#;
+
+#[test]
+fn selection_inner_app() {
+  let (md, text) = selection(NESTED_APP, 3, 11, 17);
+  assert_eq!(text, r#"g "hi""#);
+  assert!(md.contains("int"), "{md}");
+}
+
+#[test]
+fn selection_parens() {
+  let (md, text) = selection(NESTED_APP, 3, 10, 18);
+  assert_eq!(text, r#"(g "hi")"#);
+  assert!(md.contains("int"), "{md}");
+}
+
+#[test]
+fn selection_partial() {
+  let (md, text) = selection(NESTED_APP, 3, 9, 15);
+  assert_eq!(text, r#"f (g "hi")"#);
+  assert!(md.contains("bool -> int"), "{md}");
+}
+
+#[test]
+fn selection_whole() {
+  let (md, text) = selection(NESTED_APP, 3, 8, 23);
+  assert_eq!(text, r#"f (g "hi") true"#);
+  assert!(md.contains("int") && !md.contains("->"), "{md}");
+}
+
+#[test]
+fn string_start_after_name() {
+  check(
+    r#"
+fun f _ = 1
+val _ = f"hi"
+(**      ^ hover: string *)
+"#,
+  );
+}
+
+#[test]
+fn string_end_before_name() {
+  check(
+    r#"
+fun f _ _ = 1
+val x = 2
+val _ = f "hi"x
+(**           ^ hover: string *)
+"#,
+  );
+}
+
+#[test]
+fn name_before_space_then_string() {
+  check(
+    r#"
+fun f _ = 1
+val _ = f "hi"
+(**     ^ hover: 'a -> int *)
+"#,
+  );
+}
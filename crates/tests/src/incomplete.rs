@@ -19,3 +19,103 @@ datatype ' guh = no
 "#,
   );
 }
+
+#[test]
+fn val_eq_eof() {
+  check(
+    r#"
+val x =
+(**   ^ expected an expression *)
+"#,
+  );
+}
+
+#[test]
+fn val_eq_then_more() {
+  check(
+    r#"
+val _ =
+  let
+    val x =
+    val y = 3
+(** ^^^ expected an expression *)
+  in
+    x + y
+  end
+"#,
+  );
+}
+
+#[test]
+fn trailing_comma_record() {
+  check(
+    r#"
+val _ = {a = 1, b = 2, }
+(**                  ^ trailing comma *)
+"#,
+  );
+}
+
+#[test]
+fn trailing_comma_tuple() {
+  check(
+    r#"
+val _ = (1, 2, )
+(**          ^ trailing comma *)
+"#,
+  );
+}
+
+#[test]
+fn trailing_comma_tuple_pat() {
+  check(
+    r#"
+fun f (x, y, ) = x + y
+(**        ^ trailing comma *)
+"#,
+  );
+}
+
+#[test]
+fn trailing_comma_then_more() {
+  let s = r#"
+val x = (1, "a", )
+val _ : int = #1 x
+val _ : bool = #2 x
+"#;
+  assert_eq!(
+    messages(s),
+    vec!["trailing comma", "expected bool, found string"]
+  );
+}
+
+#[test]
+fn record_bad_sep_then_more() {
+  let s = r#"
+val _ = {a = 1; b = 2}
+val _ : int = "no"
+"#;
+  assert_eq!(
+    messages(s),
+    vec!["expected `}`", "expected int, found string"]
+  );
+}
+
+#[test]
+fn tuple_bad_elem_then_more() {
+  let s = r#"
+val _ = (1, 2 =>, 4)
+val _ : int = "no"
+"#;
+  assert_eq!(
+    messages(s),
+    vec!["expected `)`", "expected int, found string"]
+  );
+}
+
+fn messages(s: &str) -> Vec<String> {
+  crate::check::get_many_with_config(s, None)
+    .into_iter()
+    .map(|e| e.message)
+    .collect()
+}
@@ -0,0 +1,50 @@
+//! Inferred type hints for bindings without type annotations.
+
+use crate::check::analyze;
+
+/// returns the hints as the 0-indexed line and character of their position, and their text.
+fn hints(s: &str) -> Vec<(u32, u32, String)> {
+  let (an, path) = analyze(s);
+  an.inlay_hints(path)
+    .into_iter()
+    .map(|(pos, text)| (pos.line, pos.character, text))
+    .collect()
+}
+
+#[test]
+fn annotated_suppressed() {
+  let mut got = hints(
+    r#"val a = 1
+val b : int = 2
+val c = 3 : int
+fun f x : int = x
+fun g x = x + 1
+val _ = 4
+"#,
+  );
+  got.sort_unstable();
+  assert_eq!(
+    got,
+    vec![
+      (0, 5, ": int".to_owned()),
+      (4, 5, ": int -> int".to_owned())
+    ]
+  );
+}
+
+#[test]
+fn polymorphic_same_as_hover() {
+  let s = "val swap = fn (x, y) => (y, x)";
+  let (an, path) = analyze(s);
+  let hints = an.inlay_hints(path);
+  assert_eq!(hints.len(), 1);
+  let (pos, text) = &hints[0];
+  assert_eq!((pos.line, pos.character), (0, 8));
+  let ty = text.strip_prefix(": ").expect("no `: ` prefix");
+  let hover_pos = analysis::Position {
+    line: 0,
+    character: 4,
+  };
+  let (md, _) = an.get_md(path.wrap(hover_pos)).expect("no hover");
+  assert!(md.contains(&format!("\n{ty}\n")), "{ty} not in {md}");
+}
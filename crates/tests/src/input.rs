@@ -57,6 +57,17 @@ fn config_parse_err() {
   assert!(e.to_string().contains("couldn't parse config"));
 }
 
+#[test]
+fn config_invalid_error_code() {
+  let config = r#"
+version = 1
+[workspace.diagnostics]
+unused = "ignore"
+"#;
+  let e = check_empty_cm(&["foo.cm"], Some(config)).unwrap_err();
+  assert!(e.to_string().contains("invalid error code: unused"));
+}
+
 #[test]
 fn cycle_1() {
   let e = check_input([("foo.cm", "Group is foo.cm")], None).unwrap_err();
@@ -117,6 +128,314 @@ root = "foo.mlb"
   check_input([("foo.mlb", ""), ("foo.cm", "Group is")], Some(config)).unwrap();
 }
 
+#[test]
+fn ordered_sources() {
+  let inp = [
+    ("sources.cm", "Group is lib.cm a.sml main.sml"),
+    ("lib.cm", "Group is c.sml b.sml"),
+    ("a.sml", ""),
+    ("b.sml", ""),
+    ("c.sml", ""),
+    ("main.sml", ""),
+  ];
+  let config = r#"
+version = 1
+[workspace]
+root = "sources.cm"
+"#;
+  check_ordered_sources(inp, Some(config), &["c.sml", "b.sml", "a.sml", "main.sml"]);
+}
+
+#[test]
+fn root_groups_list() {
+  let config = r#"
+version = 1
+[workspace]
+root = ["foo.cm", "bar.cm"]
+"#;
+  check_empty_cm(&["foo.cm", "bar.cm"], Some(config)).unwrap();
+}
+
+#[test]
+fn root_groups_list_shared_source() {
+  let inp = [
+    ("foo.mlb", "a.sml foo.sml"),
+    ("bar.mlb", "a.sml bar.sml"),
+    ("a.sml", ""),
+    ("foo.sml", ""),
+    ("bar.sml", ""),
+  ];
+  let config = r#"
+version = 1
+[workspace]
+root = ["foo.mlb", "bar.mlb"]
+"#;
+  check_ordered_sources(inp, Some(config), &["a.sml", "foo.sml", "bar.sml"]);
+}
+
+#[test]
+fn root_groups_list_cycle() {
+  let inp = [("foo.cm", "Group is bar.cm"), ("bar.cm", "Group is foo.cm")];
+  let config = r#"
+version = 1
+[workspace]
+root = ["foo.cm", "bar.cm"]
+"#;
+  let e = check_input(inp, Some(config)).unwrap_err();
+  assert!(e.to_string().contains("there is a cycle"));
+}
+
+#[test]
+fn mlb_include() {
+  let inp = [
+    ("sources.mlb", "lib.mlb main.sml"),
+    ("lib.mlb", "a.sml b.sml"),
+    ("a.sml", ""),
+    ("b.sml", ""),
+    ("main.sml", ""),
+  ];
+  check_ordered_sources(inp, None, &["a.sml", "b.sml", "main.sml"]);
+}
+
+#[test]
+fn mlb_bas_local_open() {
+  let inp = [
+    (
+      "sources.mlb",
+      r#"
+local
+  a.sml
+in
+  basis B = bas b.sml end
+end
+open B
+main.sml
+"#,
+    ),
+    ("a.sml", ""),
+    ("b.sml", ""),
+    ("main.sml", ""),
+  ];
+  check_ordered_sources(inp, None, &["a.sml", "b.sml", "main.sml"]);
+}
+
+#[test]
+fn mlb_path_var() {
+  let inp = [
+    ("sources.mlb", "$(LIB)/a.sml main.sml"),
+    ("lib/a.sml", ""),
+    ("main.sml", ""),
+  ];
+  let config = r#"
+version = 1
+[workspace.path-vars]
+LIB = { value = "lib" }
+"#;
+  check_ordered_sources(inp, Some(config), &["lib/a.sml", "main.sml"]);
+}
+
+#[test]
+fn mlb_path_var_undefined() {
+  let e = check_input([("sources.mlb", "$(NOPE)/a.sml")], None).unwrap_err();
+  assert!(e.to_string().contains("undefined path variable: NOPE"));
+}
+
+#[test]
+fn mlb_sml_lib_unset() {
+  let inp = [
+    ("sources.mlb", "$(SML_LIB)/basis/basis.mlb main.sml"),
+    ("main.sml", ""),
+  ];
+  check_ordered_sources(inp, None, &["main.sml"]);
+}
+
+#[test]
+fn mlb_sml_lib_set() {
+  let inp = [
+    ("sources.mlb", "$(SML_LIB)/basis/basis.mlb main.sml"),
+    ("sml-lib/basis/basis.mlb", "a.sml"),
+    ("sml-lib/basis/a.sml", ""),
+    ("main.sml", ""),
+  ];
+  let config = r#"
+version = 1
+[workspace]
+root = "sources.mlb"
+[workspace.path-vars]
+SML_LIB = { path = "sml-lib" }
+"#;
+  check_ordered_sources(inp, Some(config), &["sml-lib/basis/a.sml", "main.sml"]);
+}
+
+#[test]
+fn mlb_duplicate_basis_and() {
+  let e = check_input([("sources.mlb", "basis A = bas end and A = bas end")], None).unwrap_err();
+  assert!(e.to_string().contains("duplicate name: A"));
+}
+
+#[test]
+fn mlb_duplicate_basis_seq() {
+  let inp = [(
+    "sources.mlb",
+    r#"
+basis A = bas end
+basis B = bas end
+basis A = bas end
+"#,
+  )];
+  let e = check_input(inp, None).unwrap_err();
+  assert!(e.to_string().contains("duplicate name: A"));
+}
+
+#[test]
+fn mlb_basis_local_not_duplicate() {
+  let inp = [(
+    "sources.mlb",
+    r#"
+local
+  basis A = bas end
+in
+  basis A = bas end
+end
+"#,
+  )];
+  check_input(inp, None).unwrap();
+}
+
+#[test]
+fn mlb_open_undefined() {
+  let inp = [
+    (
+      "sources.mlb",
+      r#"
+basis A = bas a.sml end
+open B
+"#,
+    ),
+    ("a.sml", ""),
+  ];
+  assert_eq!(error_messages(inp), vec!["undefined basis: B".to_owned()]);
+}
+
+#[test]
+fn mlb_export_namespaces() {
+  let inp = [
+    (
+      "sources.mlb",
+      r#"
+local
+  a.sml
+in
+  structure A
+  signature A
+  functor A
+end
+main.sml
+"#,
+    ),
+    (
+      "a.sml",
+      r#"
+signature A = sig val x : int end
+structure A : A = struct val x = 1 end
+functor A (X : A) = struct val y = X.x end
+"#,
+    ),
+    (
+      "main.sml",
+      r#"
+structure B : A = A
+structure C = A (A)
+val _ : int = B.x + C.y
+"#,
+    ),
+  ];
+  assert!(error_messages(inp).is_empty());
+}
+
+#[test]
+fn mlb_export_one_namespace() {
+  let inp = [
+    (
+      "sources.mlb",
+      r#"
+local
+  a.sml
+in
+  structure A
+end
+main.sml
+"#,
+    ),
+    (
+      "a.sml",
+      r#"
+signature A = sig val x : int end
+structure A : A = struct val x = 1 end
+"#,
+    ),
+    ("main.sml", "structure B : A = A"),
+  ];
+  assert_eq!(
+    error_messages(inp),
+    vec!["undefined signature: A".to_owned()]
+  );
+}
+
+#[test]
+fn mlb_unmatched_close_comment() {
+  let inp = [
+    ("sources.mlb", "a.sml *) main.sml"),
+    ("a.sml", "val x = 1"),
+    ("main.sml", "val y = x"),
+  ];
+  let input = check_input(inp, None).unwrap();
+  let mut an = analysis::Analysis::new(analysis::StdBasis::minimal(), config::ErrorLines::One);
+  let got: Vec<_> = an
+    .get_many(&input)
+    .into_values()
+    .flatten()
+    .map(|e| e.message)
+    .collect();
+  assert_eq!(
+    got,
+    vec!["couldn't process ML Basis file: unmatched close comment".to_owned()]
+  );
+}
+
+#[track_caller]
+fn check_ordered_sources<'a, I>(files: I, config: Option<&str>, want: &[&str])
+where
+  I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+  let fs = memory_fs(files, config);
+  let mut root = analysis::input::get_root_dir(ROOT.to_owned());
+  let input = analysis::input::get(&fs, &mut root).unwrap();
+  let got: Vec<_> = input
+    .ordered_sources()
+    .into_iter()
+    .map(|path| root.as_paths().get_rel_path(path).to_owned())
+    .collect();
+  let want: Vec<_> = want.iter().map(std::path::PathBuf::from).collect();
+  assert_eq!(got, want);
+}
+
+/// returns the messages of all the errors from analyzing the files with the minimal std basis.
+fn error_messages<'a, I>(files: I) -> Vec<String>
+where
+  I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+  let fs = memory_fs(files, None);
+  let mut root = analysis::input::get_root_dir(ROOT.to_owned());
+  let input = analysis::input::get(&fs, &mut root).unwrap();
+  let mut an = analysis::Analysis::new(analysis::StdBasis::minimal(), config::ErrorLines::One);
+  an.get_many(&input)
+    .into_values()
+    .flatten()
+    .map(|e| e.message)
+    .collect()
+}
+
 fn check_empty_cm(
   names: &[&str],
   config: Option<&str>,
@@ -131,13 +450,20 @@ fn check_input<'a, I>(
 where
   I: IntoIterator<Item = (&'a str, &'a str)>,
 {
-  let fs = paths::MemoryFileSystem::new(
-    groups
+  let fs = memory_fs(groups, config);
+  let mut root = analysis::input::get_root_dir(ROOT.to_owned());
+  analysis::input::get(&fs, &mut root)
+}
+
+fn memory_fs<'a, I>(files: I, config: Option<&str>) -> paths::MemoryFileSystem
+where
+  I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+  paths::MemoryFileSystem::new(
+    files
       .into_iter()
       .map(|(name, contents)| (ROOT.as_path().join(name), contents.to_owned()))
       .chain(config.map(|x| (ROOT.as_path().join(config::FILE_NAME), x.to_owned())))
       .collect(),
-  );
-  let mut root = analysis::input::get_root_dir(ROOT.to_owned());
-  analysis::input::get(&fs, &mut root)
+  )
 }
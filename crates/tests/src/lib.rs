@@ -6,31 +6,65 @@
 #![cfg(test)]
 #![deny(rust_2018_idioms)]
 
+mod allocs;
 mod ascribe;
 mod check;
+mod check_syntax;
+mod completions;
 mod datatype_copy;
+mod debug_hir;
+mod deprecated;
 mod deviations;
+mod div_by_zero;
 mod docs;
+mod document_symbol;
 mod dupe;
+mod enclosing_decl;
+mod equality;
+mod error_codes;
 mod exn;
+mod fill_case;
 mod fixity;
+mod folding_range;
+mod format;
 mod functor;
 mod generalize;
 mod hover;
 mod incomplete;
 mod infix_without_op;
+mod inlay_hint;
 mod input;
 mod literal;
 mod local;
 mod matching;
+mod max_errors;
 mod misc;
+mod moniker;
+mod narrow_handle;
+mod no_std_basis;
 mod num_record;
 mod overload;
 mod pat;
+mod quick_fix;
+mod recheck;
+mod rename;
 mod rest_pat;
 mod rust;
+mod selector;
+mod semantic_token;
+mod severity;
 mod shadow;
+mod shadow_infix;
 mod smoke;
+mod stats;
 mod std_basis;
+mod subst;
+mod symbol_kind;
+mod token;
+mod top_level_type;
+mod tuple_record;
+mod ty_def;
 mod ty_name_escape;
 mod ty_var;
+mod val_rec;
+mod vector;
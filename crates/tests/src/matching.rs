@@ -476,3 +476,39 @@ fun f x =
 "#,
   );
 }
+
+#[test]
+fn bool_missing_false() {
+  check(
+    r#"
+fun f b =
+  case b of
+(**    + non-exhaustive case: missing false *)
+    true => 1
+"#,
+  );
+}
+
+#[test]
+fn bool_missing_true() {
+  check(
+    r#"
+fun f b =
+  case b of
+(**    + non-exhaustive case: missing true *)
+    false => 1
+"#,
+  );
+}
+
+#[test]
+fn bool_exhaustive() {
+  check(
+    r#"
+fun f b =
+  case b of
+    true => 1
+  | false => 0
+"#,
+  );
+}
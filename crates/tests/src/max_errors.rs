@@ -0,0 +1,63 @@
+//! Limiting the number of errors reported per file.
+
+use crate::check::{get_many_with_config, get_one};
+
+/// returns `n` lines, each of which has one error.
+fn many_errors(n: usize) -> String {
+  (0..n).map(|i| format!("val _ = x{i}\n")).collect()
+}
+
+#[test]
+fn default_max() {
+  let errors = get_many_with_config(&many_errors(25), None);
+  assert_eq!(errors.len(), 21);
+  let last = errors.last().unwrap();
+  assert_eq!(last.message, "5 more errors hidden");
+  assert_eq!(last.code, 6004);
+  assert_eq!(last.range.start.line, 20);
+}
+
+#[test]
+fn default_max_get_one() {
+  let errors = get_one(&many_errors(25));
+  assert_eq!(errors.len(), 21);
+  assert_eq!(errors.last().unwrap().message, "5 more errors hidden");
+}
+
+#[test]
+fn at_max() {
+  let errors = get_many_with_config(&many_errors(20), None);
+  assert_eq!(errors.len(), 20);
+  assert!(errors.iter().all(|e| e.code != 6004));
+}
+
+#[test]
+fn one_hidden() {
+  let errors = get_many_with_config(&many_errors(21), None);
+  assert_eq!(errors.len(), 21);
+  assert_eq!(errors.last().unwrap().message, "1 more error hidden");
+}
+
+#[test]
+fn config_max() {
+  let config = r#"
+version = 1
+[workspace]
+max-errors-per-file = 3
+"#;
+  let errors = get_many_with_config(&many_errors(5), Some(config));
+  assert_eq!(errors.len(), 4);
+  assert_eq!(errors.last().unwrap().message, "2 more errors hidden");
+}
+
+#[test]
+fn config_unlimited() {
+  let config = r#"
+version = 1
+[workspace]
+max-errors-per-file = 0
+"#;
+  let errors = get_many_with_config(&many_errors(25), Some(config));
+  assert_eq!(errors.len(), 25);
+  assert!(errors.iter().all(|e| e.code != 6004));
+}
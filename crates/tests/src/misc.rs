@@ -1,4 +1,4 @@
-use crate::check::{check, fail};
+use crate::check::{check, fail, get_one};
 
 #[test]
 fn apply() {
@@ -112,6 +112,16 @@ fn forbidden_binding() {
   );
 }
 
+#[test]
+fn forbidden_binding_eq() {
+  check(
+    r#"
+val op = = 13
+(** ^^^^ cannot re-bind name: = *)
+"#,
+  );
+}
+
 #[test]
 fn list_fns() {
   check(
@@ -153,6 +163,29 @@ fun f 1 = 2
   );
 }
 
+#[test]
+fn fun_dec_wrong_num_pats_second_case() {
+  check(
+    r#"
+fun f x = x | f x y = y
+(**           ^^^^^^^^^ expected 1 patterns, found 2 *)
+"#,
+  );
+}
+
+#[test]
+fn fun_dec_wrong_num_pats_first_case_odd() {
+  let s = r#"
+fun f 1 2 = 3
+  | f 4 = 5
+  | f 6 = 7
+"#;
+  let errors: Vec<_> = get_one(s).into_iter().filter(|e| e.code == 4002).collect();
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].message, "expected 2 patterns, found 1");
+  assert_eq!(errors[0].range.start.line, 2);
+}
+
 #[test]
 fn match_record_non_record_ty() {
   check(
@@ -232,10 +265,10 @@ val _ = "foo" 3
 
 #[test]
 fn not_equality() {
-  fail(
+  check(
     r#"
 val _ = 2.2 = 3.3
-(**     ^^^^^^^^^ not an equality type: real *)
+(**     ^^^ operator = requires an equality type, found real *)
 "#,
   );
 }
@@ -288,7 +321,27 @@ fn value_restriction() {
   check(
     r#"
 val id = (fn x => x) (fn x => x)
-(**      ^^^^^^^^^^^^^^^^^^^^^^^ cannot bind expansive polymorphic expression *)
+(**      ^^^^^^^^^^^^^^^^^^^^^^^ cannot bind expansive polymorphic expression; annotate the binding, e.g. `val id : _ -> _ = ...` *)
+"#,
+  );
+}
+
+#[test]
+fn value_restriction_suggest_list() {
+  check(
+    r#"
+val r = ref []
+(**     ^^^^^^ cannot bind expansive polymorphic expression; annotate the binding, e.g. `val r : _ list ref = ...` *)
+"#,
+  );
+}
+
+#[test]
+fn value_restriction_no_suggest_many_names() {
+  check(
+    r#"
+val (a, b) = (ref [], ref [])
+(**          ^^^^^^^^^^^^^^^^ cannot bind expansive polymorphic expression *)
 "#,
   );
 }
@@ -351,6 +404,56 @@ val _ = 1 = 2 andalso 3 > 4 orelse 5 < 6
   );
 }
 
+#[test]
+fn andalso_rhs() {
+  check(
+    r#"
+val _ = true andalso "x"
+(**                  ^^^ expected bool, found string *)
+"#,
+  );
+}
+
+#[test]
+fn orelse_rhs() {
+  check(
+    r#"
+val _ = false orelse 3
+(**                  ^ expected bool, found int *)
+"#,
+  );
+}
+
+/// returns the start column and message of each error, sorted.
+fn cols_and_messages(s: &str) -> Vec<(u32, String)> {
+  let mut ret: Vec<_> = get_one(s)
+    .into_iter()
+    .map(|e| (e.range.start.character, e.message))
+    .collect();
+  ret.sort_unstable();
+  ret
+}
+
+#[test]
+fn andalso_both_sides() {
+  let got = cols_and_messages(r#"val _ = 1 andalso "x""#);
+  let want = vec![
+    (8, "expected bool, found int".to_owned()),
+    (18, "expected bool, found string".to_owned()),
+  ];
+  assert_eq!(got, want);
+}
+
+#[test]
+fn orelse_both_sides() {
+  let got = cols_and_messages(r#"val _ = "x" orelse 1"#);
+  let want = vec![
+    (8, "expected bool, found string".to_owned()),
+    (19, "expected bool, found int".to_owned()),
+  ];
+  assert_eq!(got, want);
+}
+
 #[test]
 fn phantom_datatype() {
   check(
@@ -487,6 +590,47 @@ end"#,
   );
 }
 
+#[test]
+fn wrong_num_ty_args_too_few() {
+  check(
+    r#"
+val _: list = []
+(**    ^^^^ expected 1 type argument, found 0 *)
+"#,
+  );
+}
+
+#[test]
+fn wrong_num_ty_args_too_many_user_defined() {
+  check(
+    r#"
+type ('a, 'b) pair = 'a * 'b
+val _: (int, bool, string) pair = (1, true)
+(**    ^^^^^^^^^^^^^^^^^^^^^^^^ expected 2 type arguments, found 3 *)
+"#,
+  );
+}
+
+#[test]
+fn wrong_num_ty_args_too_few_user_defined() {
+  check(
+    r#"
+datatype ('a, 'b) either = L of 'a | R of 'b
+val _: int either = L 1
+(**    ^^^^^^^^^^ expected 2 type arguments, found 1 *)
+"#,
+  );
+}
+
+#[test]
+fn nested_ty_args_ok() {
+  check(
+    r#"
+val _: int list list = [[1]]
+"#,
+  );
+}
+
 #[test]
 fn sig_ty_eq_wrong_num_ty_args() {
   check(
@@ -601,7 +745,7 @@ fn empty_fun() {
   check(
     r#"
     fun f = 3
-(** ^^^^^^^^^ the expression for a `val rec` was not a `fn` *)
+(**     ^^^^^ the expression for a `val rec` was not a `fn` *)
 "#,
   )
 }
@@ -618,6 +762,19 @@ end
   )
 }
 
+#[test]
+fn empty_include_before_spec() {
+  check(
+    r#"
+signature S = sig
+    include
+(** ^^^^^^^ requires at least 1 operand *)
+  val x : int
+end
+"#,
+  )
+}
+
 #[test]
 fn empty_open() {
   check(
@@ -652,3 +809,217 @@ val _ = inc
 "#,
   );
 }
+
+/// asserts the hover at `(line, character)` contains `want`.
+#[track_caller]
+fn check_doc_hover(s: &str, (line, character): (u32, u32), want: &str) {
+  let (an, path) = crate::check::analyze(s);
+  let pos = analysis::Position { line, character };
+  let (got, _) = an.get_md(path.wrap(pos)).expect("no hover");
+  assert!(got.contains(want), "{got} does not contain {want}");
+}
+
+#[test]
+fn doc_comment_nested() {
+  check_doc_hover(
+    r#"
+(*!
+ * Returns the number (* an int *) incremented.
+ *)
+fun inc x = x + 1
+val _ = inc
+"#,
+    (5, 8),
+    "Returns the number (* an int *) incremented.",
+  );
+}
+
+#[test]
+fn doc_comment_nested_multi_line() {
+  check_doc_hover(
+    r#"
+(*!
+ * Returns the number incremented.
+ * (* TODO handle
+      overflow *)
+ *)
+fun inc x = x + 1
+val _ = inc
+"#,
+    (7, 8),
+    "Returns the number incremented.\n(* TODO handle\noverflow *)",
+  );
+}
+
+#[test]
+fn recursive_ty_alias() {
+  check(
+    r#"
+    type t = t list
+(** ^^^^^^^^^^^^^^^ recursive type abbreviation: t *)
+"#,
+  );
+}
+
+#[test]
+fn mutually_recursive_ty_alias() {
+  check(
+    r#"
+    type a = b and b = a
+(** ^^^^^^^^^^^^^^^^^^^^ recursive type abbreviation: a *)
+"#,
+  );
+}
+
+#[test]
+fn self_ty_alias() {
+  check(
+    r#"
+    type t = t
+(** ^^^^^^^^^^ type abbreviation refers to itself: t *)
+"#,
+  );
+}
+
+#[test]
+fn self_ty_alias_ty_vars() {
+  check(
+    r#"
+    type 'a t = 'a t
+(** ^^^^^^^^^^^^^^^^ type abbreviation refers to itself: t *)
+"#,
+  );
+}
+
+#[test]
+fn self_ty_alias_and() {
+  check(
+    r#"
+    type a = int and b = b
+(** ^^^^^^^^^^^^^^^^^^^^^^ type abbreviation refers to itself: b *)
+"#,
+  );
+}
+
+#[test]
+fn ty_alias_shadow_not_recursive() {
+  check(
+    r#"
+type t = int
+type t = t list
+val _ : t = [1, 2]
+"#,
+  );
+}
+
+#[test]
+fn empty_seq_exp() {
+  for s in [
+    "val _ = let in end",
+    "val _ = (3; )",
+    "val _ = let val x = 3 in ; end",
+  ] {
+    assert!(!get_one(s).is_empty(), "no errors for {s}");
+  }
+}
+
+#[test]
+fn empty_let_body_one_error() {
+  for s in [
+    "val _ = let in end",
+    "val _ = let val x = 3 in end",
+    "fun f x = let val y = x in end",
+  ] {
+    let errors = get_one(s);
+    assert_eq!(
+      errors.len(),
+      1,
+      "wrong number of errors for {s}: {errors:?}"
+    );
+    assert_eq!(errors[0].message, "expected an expression");
+  }
+}
+
+#[test]
+fn shebang() {
+  check(
+    r#"#!/usr/bin/env sml
+val x = 3
+val _ = x + y
+(**         ^ undefined value: y *)
+"#,
+  );
+}
+
+#[test]
+fn errors_sorted_by_position() {
+  let s = r#"
+val _ = x
+val _ = "a\qb"
+"#;
+  let got: Vec<_> = get_one(s)
+    .iter()
+    .map(|e| (e.range.start.line, e.code))
+    .collect();
+  assert_eq!(got, [(1, 5001), (2, 2008)]);
+}
+
+#[test]
+fn severity() {
+  let s = r#"
+val _ : int = "a"
+val _ = fn 1 => 2 | 1 => 3 | _ => 4
+"#;
+  let got: Vec<_> = get_one(s)
+    .iter()
+    .map(|e| (e.range.start.line, e.severity))
+    .collect();
+  assert_eq!(
+    got,
+    [(1, config::Severity::Error), (2, config::Severity::Warning)]
+  );
+}
+
+#[test]
+fn error_code() {
+  check(
+    r#"
+val _ = nope
+(**     ^^^^ code: 5001 *)
+"#,
+  );
+}
+
+#[test]
+fn error_code_wrong() {
+  fail(
+    r#"
+val _ = nope
+(**     ^^^^ code: 5006 *)
+"#,
+  );
+}
+
+#[test]
+fn error_code_parse() {
+  check(
+    r#"
+infix 1.1 bad
+(**   ^^^ code: 3006 *)
+"#,
+  );
+}
+
+#[test]
+fn error_code_unreachable() {
+  check(
+    r#"
+val _ =
+  case 3 of
+    4 => 1
+  | 4 => 2
+(** ^ code: 5010 *)
+  | _ => 3
+"#,
+  );
+}
@@ -0,0 +1,68 @@
+//! Stable identifiers for symbols.
+
+use crate::check::analyze;
+
+#[test]
+fn def_and_use() {
+  let (an, path) = analyze(
+    r#"
+structure S = struct
+  val x = 3
+end
+val _ = S.x
+"#,
+  );
+  let def = an.moniker(path.wrap(analysis::Position {
+    line: 2,
+    character: 6,
+  }));
+  let use_ = an.moniker(path.wrap(analysis::Position {
+    line: 4,
+    character: 10,
+  }));
+  assert_eq!(def.as_deref(), Some("f0.sml#S.x"));
+  assert_eq!(def, use_);
+}
+
+#[test]
+fn std_basis() {
+  let (an, path) = analyze(
+    r#"
+val _ : int = 3
+"#,
+  );
+  let got = an.moniker(path.wrap(analysis::Position {
+    line: 1,
+    character: 8,
+  }));
+  assert_eq!(got, None);
+}
+
+#[test]
+fn same_name_locals() {
+  let (an, path) = analyze(
+    r#"
+val a = let val x = 1 in x end
+val b = let val x = 2 in x end
+"#,
+  );
+  let moniker = |line, character| an.moniker(path.wrap(analysis::Position { line, character }));
+  assert_eq!(moniker(1, 16).as_deref(), Some("f0.sml#x@2:17"));
+  assert_eq!(moniker(1, 16), moniker(1, 25));
+  assert_eq!(moniker(2, 16).as_deref(), Some("f0.sml#x@3:17"));
+  assert_eq!(moniker(2, 16), moniker(2, 25));
+}
+
+#[test]
+fn same_name_params() {
+  let (an, path) = analyze(
+    r#"
+val f = fn x => x
+val g = fn x => x
+"#,
+  );
+  let moniker = |line, character| an.moniker(path.wrap(analysis::Position { line, character }));
+  assert_eq!(moniker(1, 16), moniker(1, 11));
+  assert_eq!(moniker(2, 16), moniker(2, 11));
+  assert_ne!(moniker(1, 16), moniker(2, 16));
+}
@@ -0,0 +1,71 @@
+//! Reporting `handle`s that match only one exception constructor.
+
+use crate::check::{check, check_with_config};
+
+const CONFIG: &str = r#"
+version = 1
+[language]
+narrow-handle = true
+"#;
+
+#[test]
+fn one_exn() {
+  check_with_config(
+    CONFIG,
+    r#"
+fun get xs = List.hd xs handle Empty => 0
+(**                            ^^^^^ this `handle` only handles `Empty`; other exceptions are raised again *)
+"#,
+  );
+}
+
+#[test]
+fn one_exn_with_arg() {
+  check_with_config(
+    CONFIG,
+    r#"
+val _ = (raise Fail "no") handle Fail s => s
+(**                              ^^^^^^ this `handle` only handles `Fail`; other exceptions are raised again *)
+"#,
+  );
+}
+
+#[test]
+fn wildcard() {
+  check_with_config(
+    CONFIG,
+    r#"
+fun get xs = List.hd xs handle Empty => 0 | _ => ~1
+"#,
+  );
+}
+
+#[test]
+fn var() {
+  check_with_config(
+    CONFIG,
+    r#"
+fun get xs = List.hd xs handle e => raise e
+"#,
+  );
+}
+
+#[test]
+fn many_exns() {
+  check_with_config(
+    CONFIG,
+    r#"
+fun get xs = List.hd xs handle Empty => 0 | Subscript => 1
+"#,
+  );
+}
+
+#[test]
+fn off_by_default() {
+  check(
+    r#"
+exception E
+val _ = 1 handle E => 2
+"#,
+  );
+}
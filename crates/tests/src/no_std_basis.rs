@@ -0,0 +1,87 @@
+//! Analyzing with only the minimal basis, as with `--no-std-basis`.
+
+use crate::check::{analyze, check};
+
+#[test]
+fn list_map_undefined() {
+  check(
+    r#"
+val _ = List.map
+(**     ^^^^^^^^ undefined structure: List *)
+"#,
+  );
+}
+
+#[test]
+fn print_undefined() {
+  check(
+    r#"
+val _ = print "hi"
+(**     ^^^^^ undefined value: print *)
+"#,
+  );
+}
+
+#[test]
+fn primitives() {
+  check(
+    r#"
+val _ : int list = 1 :: 2 :: nil
+val _ : bool = true andalso 1 + 2 < 4
+val _ : string ref = ref "hi"
+val _ : unit = ()
+"#,
+  );
+}
+
+/// the exact set of names in scope in the minimal basis.
+#[test]
+fn names() {
+  let (an, path) = analyze("\n");
+  let pos = analysis::Position {
+    line: 1,
+    character: 0,
+  };
+  let mut got: Vec<_> = an
+    .completions(path.wrap(pos))
+    .into_iter()
+    .map(|x| (x.kind, x.label))
+    .collect();
+  got.sort_unstable_by(|(k1, l1), (k2, l2)| (format!("{k1:?}"), l1).cmp(&(format!("{k2:?}"), l2)));
+  let want: Vec<_> = [
+    (analysis::SymbolKind::Constructor, "::"),
+    (analysis::SymbolKind::Constructor, "false"),
+    (analysis::SymbolKind::Constructor, "nil"),
+    (analysis::SymbolKind::Constructor, "ref"),
+    (analysis::SymbolKind::Constructor, "true"),
+    (analysis::SymbolKind::Type, "bool"),
+    (analysis::SymbolKind::Type, "char"),
+    (analysis::SymbolKind::Type, "exn"),
+    (analysis::SymbolKind::Type, "int"),
+    (analysis::SymbolKind::Type, "list"),
+    (analysis::SymbolKind::Type, "real"),
+    (analysis::SymbolKind::Type, "ref"),
+    (analysis::SymbolKind::Type, "string"),
+    (analysis::SymbolKind::Type, "unit"),
+    (analysis::SymbolKind::Type, "vector"),
+    (analysis::SymbolKind::Type, "word"),
+    (analysis::SymbolKind::Value, "*"),
+    (analysis::SymbolKind::Value, "+"),
+    (analysis::SymbolKind::Value, "-"),
+    (analysis::SymbolKind::Value, "/"),
+    (analysis::SymbolKind::Value, "<"),
+    (analysis::SymbolKind::Value, "<="),
+    (analysis::SymbolKind::Value, "<>"),
+    (analysis::SymbolKind::Value, "="),
+    (analysis::SymbolKind::Value, ">"),
+    (analysis::SymbolKind::Value, ">="),
+    (analysis::SymbolKind::Value, "abs"),
+    (analysis::SymbolKind::Value, "div"),
+    (analysis::SymbolKind::Value, "mod"),
+    (analysis::SymbolKind::Value, "~"),
+  ]
+  .into_iter()
+  .map(|(k, l)| (k, l.to_owned()))
+  .collect();
+  assert_eq!(got, want);
+}
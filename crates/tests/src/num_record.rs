@@ -1,4 +1,4 @@
-use crate::check::check;
+use crate::check::{check, check_with_config};
 
 #[test]
 fn record_is_tuple() {
@@ -62,3 +62,108 @@ type t = { 0x3: int, 1: bool }
 "#,
   );
 }
+
+const GAP_CONFIG: &str = r#"
+version = 1
+[language]
+num-label-gap = true
+"#;
+
+#[test]
+fn gap_ty() {
+  check_with_config(
+    GAP_CONFIG,
+    r#"
+type t = { 1: int, 2: bool, 4: string }
+(**      ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ missing numeric label 3, so this is not a tuple *)
+"#,
+  );
+}
+
+#[test]
+fn gap_exp() {
+  check_with_config(
+    GAP_CONFIG,
+    r#"
+val _ = { 1 = 3, 2 = false, 4 = "hi" }
+(**     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ missing numeric label 3, so this is not a tuple *)
+"#,
+  );
+}
+
+#[test]
+fn gap_rest_pat() {
+  check_with_config(
+    GAP_CONFIG,
+    r#"
+val f = fn ({ 1 = a, 2 = b, 4 = c, ... } : int * int * int * int) => a + b + c
+"#,
+  );
+}
+
+#[test]
+fn gap_not_near_tuple() {
+  check_with_config(
+    GAP_CONFIG,
+    r#"
+type t = { 1: int, 3: bool, 5: string }
+type u = { 2: int, 3: bool }
+type v = { 1: int, 3: bool, foo: string }
+"#,
+  );
+}
+
+#[test]
+fn gap_disabled() {
+  check(
+    r#"
+type t = { 1: int, 2: bool, 4: string }
+"#,
+  );
+}
+
+#[test]
+fn hover_gap() {
+  check(
+    r#"
+val x = { 1 = 3, 3 = false }
+(** ^ hover: { 1 : int, 3 : bool } *)
+"#,
+  );
+}
+
+#[test]
+fn non_exhaustive_gap() {
+  check(
+    r#"
+datatype d = A | B
+fun f (x : {1 : d, 3 : bool}) =
+  let val {1 = A, 3 = b} = x in b end
+(**       ^^^^^^^^^^^^^^ non-exhaustive binding: missing {1 = B, 3 = _} *)
+"#,
+  );
+}
+
+#[test]
+fn non_exhaustive_one_label() {
+  check(
+    r#"
+datatype d = A | B
+fun f (x : {1 : d}) =
+  let val {1 = A} = x in () end
+(**       ^^^^^^^ non-exhaustive binding: missing {1 = B} *)
+"#,
+  );
+}
+
+#[test]
+fn non_exhaustive_tuple() {
+  check(
+    r#"
+datatype d = A | B
+fun f (x : {1 : d, 2 : bool}) =
+  let val {1 = A, 2 = b} = x in b end
+(**       ^^^^^^^^^^^^^^ non-exhaustive binding: missing (B, _) *)
+"#,
+  );
+}
@@ -186,3 +186,33 @@ fun hm a b = (abs (a - b), a + b)
 "#,
   );
 }
+
+#[test]
+fn comparison_rhs_mismatch() {
+  check(
+    r#"
+val _ = 1 < "two"
+(**         ^^^^^ expected int, found string *)
+"#,
+  );
+}
+
+#[test]
+fn comparison_lhs_mismatch() {
+  check(
+    r#"
+val _ = true < 1
+(**     ^^^^ expected <numtxt>, found bool *)
+"#,
+  );
+}
+
+#[test]
+fn comparison_eq_rhs_mismatch() {
+  check(
+    r#"
+val _ = 1 = "two"
+(**         ^^^^^ expected int, found string *)
+"#,
+  );
+}
@@ -1,13 +1,13 @@
 mod or;
 
-use crate::check::check;
+use crate::check::{check, check_with_std_basis};
 
 #[test]
 fn unexpected_arg_1() {
   check(
     r#"
 val _ = fn nil _ => 1 | _ => 2
-(**        ^^^^^ unexpected argument for constructor pattern *)
+(**        ^^^^^ unexpected argument for constructor pattern: nil takes no argument *)
 "#,
   );
 }
@@ -20,7 +20,7 @@ datatype d = A | B of int
 val _ =
   case A of
     A _ => 1
-(** ^^^ unexpected argument for constructor pattern *)
+(** ^^^ unexpected argument for constructor pattern: A takes no argument *)
   | B _ => 2
 "#,
   );
@@ -31,7 +31,7 @@ fn missing_arg_1() {
   check(
     r#"
 val _ = fn op:: => 3
-(**        ^^^^ missing argument for constructor pattern *)
+(**        ^^^^ missing argument for constructor pattern: :: expects an argument of type ?a * ?a list *)
 "#,
   );
 }
@@ -45,7 +45,33 @@ val _ =
   case A of
     A => 1
   | B => 2
-(** ^ missing argument for constructor pattern *)
+(** ^ missing argument for constructor pattern: B expects an argument of type int *)
+"#,
+  );
+}
+
+#[test]
+fn missing_arg_option() {
+  check_with_std_basis(
+    r#"
+fun f x =
+  case x of
+    SOME => 1
+(** ^^^^ missing argument for constructor pattern: SOME expects an argument of type ?a *)
+  | NONE => 2
+"#,
+  );
+}
+
+#[test]
+fn unexpected_arg_option() {
+  check_with_std_basis(
+    r#"
+fun f x =
+  case x of
+    SOME y => y
+  | NONE z => 0
+(** ^^^^^^ unexpected argument for constructor pattern: NONE takes no argument *)
 "#,
   );
 }
@@ -118,3 +144,72 @@ val _ = op+ - op* : int
 "#,
   );
 }
+
+#[test]
+fn as_pat_binds_all() {
+  check(
+    r#"
+val (p as (a, b)) = (1, 2)
+val _ : int * int = p
+val _ : int = a + b
+"#,
+  );
+}
+
+#[test]
+fn as_pat_alias_in_body() {
+  check(
+    r#"
+fun dupHead xs =
+  case xs of
+    ys as h :: _ => h :: ys
+  | [] => []
+val _ : int list = dupHead [1, 2]
+"#,
+  );
+}
+
+#[test]
+fn as_pat_dupe() {
+  check(
+    r#"
+val (x as (x, _)) = (1, 2)
+(**  ^^^^^^^^^^^ duplicate value: x *)
+"#,
+  );
+}
+
+#[test]
+fn var_binds() {
+  check(
+    r#"
+val f = fn x => x + 1
+val _ : int = f 2
+"#,
+  );
+}
+
+#[test]
+fn con_in_scope_not_var() {
+  check(
+    r#"
+datatype d = A | B
+fun f A = 1
+  | f B = 2
+val _ : int = f A
+"#,
+  );
+}
+
+#[test]
+fn con_in_scope_not_var_unreachable() {
+  check(
+    r#"
+datatype d = A | B
+fun f A = 1
+  | f B = 2
+  | f A = 3
+(**   ^ unreachable pattern *)
+"#,
+  );
+}
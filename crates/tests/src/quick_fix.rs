@@ -0,0 +1,104 @@
+//! Quick fixes for errors.
+
+use crate::check::analyze;
+
+/// asserts the quick fixes at `(line, character)` insert `want` at the given positions.
+#[track_caller]
+fn check(s: &str, (line, character): (u32, u32), want: &[((u32, u32), &str)]) {
+  let (an, path) = analyze(s);
+  let pos = analysis::Position { line, character };
+  let got = an.quick_fixes(path.wrap(pos));
+  let got: Vec<_> = got
+    .iter()
+    .map(|(range, new_text)| {
+      assert_eq!(range.start, range.end, "not an insertion");
+      ((range.start.line, range.start.character), new_text.as_str())
+    })
+    .collect();
+  assert_eq!(got, want);
+}
+
+#[test]
+fn op_exp() {
+  check(
+    r#"
+val _ = + (1, 2)
+"#,
+    (1, 8),
+    &[((1, 8), "op ")],
+  );
+}
+
+#[test]
+fn op_fun() {
+  check(
+    r#"
+fun + (a, b) = a
+"#,
+    (1, 4),
+    &[((1, 4), "op ")],
+  );
+}
+
+#[test]
+fn op_pat() {
+  check(
+    r#"
+fun map f [] = []
+  | map f x::xs = f x :: map f xs
+"#,
+    (2, 11),
+    &[((2, 11), "op ")],
+  );
+}
+
+#[test]
+fn not_infix_pat() {
+  check(
+    r#"
+datatype t = C of int * int
+fun f (_ C _) = 2
+"#,
+    (2, 9),
+    &[((2, 0), "infix C\n")],
+  );
+}
+
+#[test]
+fn not_infix_fun() {
+  check(
+    r#"
+fun (a f b) = a + b
+"#,
+    (1, 7),
+    &[((1, 0), "infix f\n")],
+  );
+}
+
+#[test]
+fn not_infix_indented() {
+  check(
+    r#"
+datatype t = C of int * int
+val _ =
+  let
+    fun f (_ C _) = 2
+  in
+    3
+  end
+"#,
+    (4, 13),
+    &[((4, 4), "infix C\n    ")],
+  );
+}
+
+#[test]
+fn none() {
+  check(
+    r#"
+val x = 3
+"#,
+    (1, 4),
+    &[],
+  );
+}
@@ -0,0 +1,155 @@
+//! Re-running statics on already lexed, parsed, and lowered source files, possibly after re-lowering
+//! a single changed file, in which case only it and the files after it are checked again.
+
+use crate::check::{get_input, ROOT};
+use fast_hash::FxHashMap;
+use paths::PathMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn same_as_full() {
+  let (_, input) = get_input(
+    &[
+      r#"
+infix 5 <+>
+fun a <+> b = a + b
+structure S = struct
+  val x = 1 <+> 2
+  val y : string = x
+end
+"#,
+      r#"
+val _ = S.x <+> 3
+val _ = S.z
+val _ = 4 <+> "hi"
+"#,
+    ],
+    None,
+  );
+  let mut an = analysis::Analysis::new(analysis::StdBasis::minimal(), config::ErrorLines::One);
+  let full = an.get_many(&input);
+  let recheck = an.recheck_many(&input);
+  assert_eq!(full.len(), recheck.len());
+  assert!(full.values().any(|errors| !errors.is_empty()));
+  for (path, errors) in full.iter() {
+    let got = recheck.get(path).expect("no errors for path");
+    assert_eq!(format!("{errors:?}"), format!("{got:?}"));
+  }
+}
+
+#[test]
+fn update_exported_ty() {
+  let up = check_update(&["val x = 1", "val _ : int = x"], 0, r#"val x = "hi""#);
+  assert!(up.before.values().all(Vec::is_empty));
+  assert!(up.after.values().any(|errors| !errors.is_empty()));
+  assert_eq!(up.reused, 0);
+}
+
+#[test]
+fn update_fixity() {
+  let up = check_update(
+    &["infix 5 <+>\nfun a <+> b = a + b", "val _ = 1 <+> 2"],
+    0,
+    "fun <+> (a, b) = a + b",
+  );
+  assert!(up.before.values().all(Vec::is_empty));
+  assert!(up.after.values().any(|errors| !errors.is_empty()));
+}
+
+#[test]
+fn update_dependent() {
+  let up = check_update(&["val x = 1", "val _ : string = x"], 1, "val _ : int = x");
+  assert!(up.before.values().any(|errors| !errors.is_empty()));
+  assert!(up.after.values().all(Vec::is_empty));
+}
+
+#[test]
+fn update_reuses_before() {
+  let up = check_update(
+    &["val x = 1", "val y = x", "val _ : int = y"],
+    2,
+    "val _ : string = y",
+  );
+  assert!(up.after.values().any(|errors| !errors.is_empty()));
+  assert_eq!(up.reused, 2);
+}
+
+#[test]
+fn update_rechecks_after() {
+  let up = check_update(
+    &["val x = 1", "val y = x", "val _ : int = y"],
+    1,
+    r#"val y = "hi""#,
+  );
+  assert!(up.after.values().any(|errors| !errors.is_empty()));
+  assert_eq!(up.reused, 1);
+}
+
+#[test]
+fn update_reused_ty() {
+  let up = check_update(
+    &["datatype t = T", "datatype u = U\nval _ : u = U"],
+    1,
+    "datatype v = V\nval _ : t = V",
+  );
+  assert!(up.after.values().any(|errors| !errors.is_empty()));
+  assert_eq!(up.reused, 1);
+}
+
+/// The errors from analyzing some files, then updating one of them.
+struct Update {
+  before: PathMap<Vec<analysis::Error>>,
+  after: PathMap<Vec<analysis::Error>>,
+  /// how many files the update did not check again.
+  reused: usize,
+}
+
+/// analyzes `before`, then replaces the file at `changed` with `after` and re-lowers only that file.
+/// checks the update agrees with analyzing from scratch.
+#[track_caller]
+fn check_update(before: &[&str], changed: usize, after: &str) -> Update {
+  let mut root = analysis::input::get_root_dir(ROOT.to_owned());
+  let input = input_with_root(&mut root, before.iter().copied());
+  let after_ss = before
+    .iter()
+    .enumerate()
+    .map(|(idx, &s)| if idx == changed { after } else { s });
+  let new_input = input_with_root(&mut root, after_ss);
+  let changed_path = new_input
+    .iter_sources()
+    .map(|s| s.path)
+    .find(|&path| root.as_paths().get_rel_path(path) == Path::new(&format!("f{changed}.sml")))
+    .expect("no changed path");
+  let mut an = analysis::Analysis::new(analysis::StdBasis::minimal(), config::ErrorLines::One);
+  let before_errors = an.get_many(&input);
+  let updated = an.update_file(&new_input, changed_path);
+  let mut fresh = analysis::Analysis::new(analysis::StdBasis::minimal(), config::ErrorLines::One);
+  let full = fresh.get_many(&new_input);
+  assert_eq!(full.len(), updated.len());
+  for (path, errors) in full.iter() {
+    let got = updated.get(path).expect("no errors for path");
+    assert_eq!(format!("{errors:?}"), format!("{got:?}"));
+  }
+  Update {
+    before: before_errors,
+    after: updated,
+    reused: an.stats().reused_statics,
+  }
+}
+
+fn input_with_root<'a, I>(root: &mut analysis::input::Root, ss: I) -> analysis::input::Input
+where
+  I: Iterator<Item = &'a str>,
+{
+  let mut m = FxHashMap::<PathBuf, String>::default();
+  let mut mlb_file = String::new();
+  for (idx, s) in ss.enumerate() {
+    let file_name = format!("f{idx}.sml");
+    writeln!(mlb_file, "{file_name}").unwrap();
+    m.insert(ROOT.as_path().join(file_name), s.to_owned());
+  }
+  m.insert(ROOT.as_path().join("sources.mlb"), mlb_file);
+  let fs = paths::MemoryFileSystem::new(m);
+  analysis::input::get(&fs, root).expect("in memory fs was not set up correctly")
+}
@@ -0,0 +1,61 @@
+//! Renaming an item and all of its uses.
+
+use crate::check::analyze;
+
+/// returns the sorted `(line, start, end, new_text)` of each edit to rename the item at `line` and
+/// `character` to `new_name`, or `None` if the rename was rejected.
+fn rename(
+  s: &str,
+  line: u32,
+  character: u32,
+  new_name: &str,
+) -> Option<Vec<(u32, u32, u32, String)>> {
+  let (an, path) = analyze(s);
+  let edits = an.rename(path.wrap(analysis::Position { line, character }), new_name)?;
+  let mut ret: Vec<_> = edits
+    .into_values()
+    .flatten()
+    .map(|(r, text)| (r.start.line, r.start.character, r.end.character, text))
+    .collect();
+  ret.sort_unstable();
+  Some(ret)
+}
+
+#[test]
+fn def_and_uses() {
+  let got = rename("val x = 1\nval y = x + x", 0, 4, "z").unwrap();
+  let want = vec![
+    (0, 4, 5, "z".to_owned()),
+    (1, 8, 9, "z".to_owned()),
+    (1, 12, 13, "z".to_owned()),
+  ];
+  assert_eq!(got, want);
+}
+
+#[test]
+fn collision_in_scope() {
+  assert!(rename("val x = 1\nval y = 2\nval z = x + y", 0, 4, "y").is_none());
+}
+
+#[test]
+fn no_collision_out_of_scope() {
+  let got = rename(
+    "val x = 1\nval _ = x\nstructure S = struct val y = 2 end",
+    1,
+    8,
+    "y",
+  )
+  .unwrap();
+  let want = vec![(0, 4, 5, "y".to_owned()), (1, 8, 9, "y".to_owned())];
+  assert_eq!(got, want);
+}
+
+#[test]
+fn std_basis() {
+  assert!(rename("val _ = true", 0, 8, "yes").is_none());
+}
+
+#[test]
+fn invalid_name() {
+  assert!(rename("val x = 1", 0, 4, "1x").is_none());
+}
@@ -93,3 +93,31 @@ fun f x =
 "#,
   );
 }
+
+#[test]
+fn applied_to_concrete() {
+  check(
+    r#"
+val _ : int = (fn {a, ...} => a) {a = 3, b = "hi"}
+"#,
+  );
+}
+
+#[test]
+fn with_other_field() {
+  check(
+    r#"
+val _ : string = (fn {a = _, b, ...} => b) {a = 3, b = "hi", c = false}
+"#,
+  );
+}
+
+#[test]
+fn unresolved_pat() {
+  check(
+    r#"
+    val f = fn {a, ...} => a
+(** ^^^^^^^^^^^^^^^^^^^^^^^^ cannot resolve record type containing `...` due to lack of context *)
+"#,
+  );
+}
@@ -0,0 +1,138 @@
+//! Record selectors, like `#foo`.
+
+use crate::check::{analyze, check};
+use sml_syntax::ast::{AstNode as _, SyntaxNodePtr};
+
+#[test]
+fn hover_name() {
+  check(
+    r#"
+val _ = #a {a = 1, b = "hi"}
+(**     ^ hover: { a : int, b : string } -> int *)
+"#,
+  );
+}
+
+#[test]
+fn hover_lab() {
+  check(
+    r#"
+val _ = #b {a = 1, b = "hi"}
+(**      ^ hover: { a : int, b : string } -> string *)
+"#,
+  );
+}
+
+#[test]
+fn hover_num() {
+  check(
+    r#"
+val _ = #1 (3, "hi")
+(**      ^ hover: int * string -> int *)
+"#,
+  );
+}
+
+#[test]
+fn ptrs_round_trip() {
+  let mut fix_env = mlb_statics::STD_BASIS_FIX_ENV.clone();
+  let (_, parsed, low) = mlb_statics::start_source_file(
+    sml_lower::Config::default(),
+    "val _ = #a {a = 1}",
+    &mut fix_env,
+  );
+  let selector = parsed
+    .root
+    .syntax()
+    .descendants()
+    .find(|node| node.kind() == sml_syntax::SyntaxKind::SelectorExp)
+    .expect("no selector");
+  let ptr = SyntaxNodePtr::new(&selector);
+  let idx = low
+    .ptrs
+    .ast_to_hir(ptr.clone())
+    .expect("no hir for selector");
+  assert_eq!(low.ptrs.hir_to_ast(idx), Some(ptr));
+}
+
+#[test]
+fn def_declared_field() {
+  let s = r#"
+type point = {x : int, y : int}
+fun getx (p : point) = #x p
+"#;
+  let (an, path) = analyze(s);
+  let pos = analysis::Position {
+    line: 2,
+    character: 24,
+  };
+  let def = an.get_def(path.wrap(pos)).expect("no def");
+  assert_eq!(def.path, path);
+  assert_eq!(
+    def.val.start,
+    analysis::Position {
+      line: 1,
+      character: 14
+    }
+  );
+  assert_eq!(
+    def.val.end,
+    analysis::Position {
+      line: 1,
+      character: 21
+    }
+  );
+}
+
+#[test]
+fn def_anonymous_field() {
+  let (an, path) = analyze("val _ = #a {a = 1}");
+  let pos = analysis::Position {
+    line: 0,
+    character: 9,
+  };
+  assert!(an.get_def(path.wrap(pos)).is_none());
+}
+
+#[test]
+fn def_same_labels_different_types() {
+  let s = r#"
+type a = {x : int, y : int}
+type b = {x : string, y : int}
+fun getx (p : b) = #x p
+"#;
+  let (an, path) = analyze(s);
+  let pos = analysis::Position {
+    line: 3,
+    character: 20,
+  };
+  let def = an.get_def(path.wrap(pos)).expect("no def");
+  assert_eq!(
+    def.val.start,
+    analysis::Position {
+      line: 2,
+      character: 10
+    }
+  );
+  assert_eq!(
+    def.val.end,
+    analysis::Position {
+      line: 2,
+      character: 20
+    }
+  );
+}
+
+#[test]
+fn def_not_in_scope() {
+  let s = r#"
+structure S = struct type p = {x : int} end
+val _ = #x {x = 1}
+"#;
+  let (an, path) = analyze(s);
+  let pos = analysis::Position {
+    line: 2,
+    character: 9,
+  };
+  assert!(an.get_def(path.wrap(pos)).is_none());
+}
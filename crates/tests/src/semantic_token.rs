@@ -0,0 +1,123 @@
+//! Classifying names for semantic highlighting.
+
+use crate::check::analyze;
+use analysis::SemanticTokenKind as Kind;
+
+/// returns the text and kind of each semantic token.
+fn get(s: &str) -> Vec<(String, Kind)> {
+  let (an, path) = analyze(s);
+  let lines: Vec<_> = s.lines().collect();
+  an.semantic_tokens(path)
+    .into_iter()
+    .map(|tok| {
+      assert_eq!(tok.range.start.line, tok.range.end.line);
+      let line = lines[tok.range.start.line as usize];
+      let text = &line[tok.range.start.character as usize..tok.range.end.character as usize];
+      (text.to_owned(), tok.kind)
+    })
+    .collect()
+}
+
+fn owned(xs: &[(&str, Kind)]) -> Vec<(String, Kind)> {
+  xs.iter().map(|&(s, k)| (s.to_owned(), k)).collect()
+}
+
+#[test]
+fn ctor_vs_value() {
+  let got = get(
+    r#"
+datatype 'a opt = None | Some of 'a
+exception Empty
+fun get (Some x) = x
+  | get None = raise Empty
+val y = Some (get None)
+"#,
+  );
+  let want = owned(&[
+    ("'a", Kind::TyVar),
+    ("opt", Kind::Type),
+    ("None", Kind::Constructor),
+    ("Some", Kind::Constructor),
+    ("'a", Kind::TyVar),
+    ("Empty", Kind::Constructor),
+    ("get", Kind::Value),
+    ("Some", Kind::Constructor),
+    ("x", Kind::Value),
+    ("x", Kind::Value),
+    ("get", Kind::Value),
+    ("None", Kind::Constructor),
+    ("Empty", Kind::Constructor),
+    ("y", Kind::Value),
+    ("Some", Kind::Constructor),
+    ("get", Kind::Value),
+    ("None", Kind::Constructor),
+  ]);
+  assert_eq!(got, want);
+}
+
+#[test]
+fn modules() {
+  let got = get(
+    r#"
+signature SIG = sig
+  type t
+  val x : t
+end
+structure S :> SIG = struct
+  type t = int
+  val x = 3
+end
+functor F (A : SIG) = struct
+  val y = A.x
+end
+structure B = F (S)
+"#,
+  );
+  let want = owned(&[
+    ("SIG", Kind::Signature),
+    ("t", Kind::Type),
+    ("x", Kind::Value),
+    ("t", Kind::Type),
+    ("S", Kind::Structure),
+    ("SIG", Kind::Signature),
+    ("t", Kind::Type),
+    ("int", Kind::Type),
+    ("x", Kind::Value),
+    ("F", Kind::Functor),
+    ("A", Kind::Structure),
+    ("SIG", Kind::Signature),
+    ("y", Kind::Value),
+    ("A", Kind::Structure),
+    ("x", Kind::Value),
+    ("B", Kind::Structure),
+    ("F", Kind::Functor),
+    ("S", Kind::Structure),
+  ]);
+  assert_eq!(got, want);
+}
+
+#[test]
+fn infix_ctor() {
+  let got = get(
+    r#"
+val xs = 1 :: nil
+"#,
+  );
+  let want = owned(&[
+    ("xs", Kind::Value),
+    ("::", Kind::Constructor),
+    ("nil", Kind::Constructor),
+  ]);
+  assert_eq!(got, want);
+}
+
+#[test]
+fn unresolved() {
+  let got = get(
+    r#"
+val _ = Nope nope
+"#,
+  );
+  let want = owned(&[("Nope", Kind::Constructor), ("nope", Kind::Value)]);
+  assert_eq!(got, want);
+}
@@ -0,0 +1,83 @@
+//! Configuring how errors with certain codes are reported.
+
+use crate::check::get_many_with_config;
+use config::Severity;
+
+const SRC: &str = r#"
+datatype d = A | B
+fun f x = case x of A => 1
+fun g x = case x of _ => 1 | A => 2
+"#;
+
+fn codes_and_severities(config: Option<&str>) -> Vec<(u16, Severity)> {
+  get_many_with_config(SRC, config)
+    .into_iter()
+    .map(|e| (e.code, e.severity))
+    .collect()
+}
+
+#[test]
+fn default() {
+  assert_eq!(
+    codes_and_severities(None),
+    vec![(5011, Severity::Warning), (5010, Severity::Warning)]
+  );
+}
+
+#[test]
+fn ignore_and_error() {
+  let config = r#"
+version = 1
+[workspace.diagnostics]
+5010 = "ignore"
+5011 = "error"
+"#;
+  assert_eq!(
+    codes_and_severities(Some(config)),
+    vec![(5011, Severity::Error)]
+  );
+}
+
+#[test]
+fn max_errors() {
+  let config = r#"
+version = 1
+[workspace]
+max-errors-per-file = 1
+[workspace.diagnostics]
+6004 = "error"
+"#;
+  assert_eq!(
+    codes_and_severities(Some(config)),
+    vec![(5011, Severity::Warning), (6004, Severity::Error)]
+  );
+}
+
+#[test]
+fn max_errors_ignore() {
+  let config = r#"
+version = 1
+[workspace]
+max-errors-per-file = 1
+[workspace.diagnostics]
+6004 = "ignore"
+"#;
+  assert_eq!(
+    codes_and_severities(Some(config)),
+    vec![(5011, Severity::Warning)]
+  );
+}
+
+#[test]
+fn num_label_gap_info() {
+  let config = r#"
+version = 1
+[language]
+num-label-gap = true
+"#;
+  let got: Vec<_> = get_many_with_config("type t = {1 : int, 2 : bool, 4 : string}", Some(config))
+    .into_iter()
+    .map(|e| (e.code, e.severity))
+    .collect();
+  assert_eq!(got, vec![(6003, Severity::Info)]);
+}
@@ -33,6 +33,30 @@ val _: t = Two
   );
 }
 
+#[test]
+fn datatype_old_con() {
+  check(
+    r#"
+datatype t = A
+datatype t = B
+val _: t = A
+(**        ^ expected t, found t *)
+"#,
+  );
+}
+
+#[test]
+fn datatype_old_con_pat() {
+  check(
+    r#"
+datatype t = A
+datatype t = B
+fun f (x : t) = case x of B => 1 | A => 2
+(**                                ^ expected t, found t *)
+"#,
+  );
+}
+
 #[test]
 fn structure() {
   check(
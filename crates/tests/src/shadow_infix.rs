@@ -0,0 +1,76 @@
+//! Reporting bindings that shadow infix operators from the standard basis.
+
+use crate::check::{check, check_with_config};
+
+const CONFIG: &str = r#"
+version = 1
+[language]
+shadow-infix = true
+"#;
+
+#[test]
+fn fun_op() {
+  check_with_config(
+    CONFIG,
+    r#"
+fun op + (a, b) = a - b
+(**    ^ `+` shadows an infix operator from the standard basis; consider declaring its fixity explicitly with `infix 6 +` or `nonfix +` *)
+"#,
+  );
+}
+
+#[test]
+fn val_op_right() {
+  check_with_config(
+    CONFIG,
+    r#"
+val op @ = fn (xs, _) => xs
+(**    ^ `@` shadows an infix operator from the standard basis; consider declaring its fixity explicitly with `infixr 5 @` or `nonfix @` *)
+"#,
+  );
+}
+
+#[test]
+fn declared_infix() {
+  check_with_config(
+    CONFIG,
+    r#"
+infix 6 +
+fun op + (a, b) = a - b
+val _ = 1 + 2
+"#,
+  );
+}
+
+#[test]
+fn declared_nonfix() {
+  check_with_config(
+    CONFIG,
+    r#"
+nonfix +
+fun + (a, b) = a - b
+val _ = + (1, 2)
+"#,
+  );
+}
+
+#[test]
+fn ordinary_names() {
+  check_with_config(
+    CONFIG,
+    r#"
+fun add (a, b) = a + b
+val plus = add
+val _ = plus (1, 2)
+"#,
+  );
+}
+
+#[test]
+fn disabled() {
+  check(
+    r#"
+fun op + (a, b) = a - b
+"#,
+  );
+}
@@ -1,4 +1,4 @@
-use crate::check::{check, check_multi};
+use crate::check::{check, check_multi, get_one};
 
 mod dec;
 mod exp;
@@ -28,6 +28,23 @@ fn no_top_items() {
   );
 }
 
+#[test]
+fn junk_top_items() {
+  check(
+    r#"
+    and ) 3 => end in
+(** ^^^ expected a top-level item *)
+val x = 1
+"#,
+  );
+}
+
+#[test]
+fn junk_top_items_one_error_per_run() {
+  let errors = get_one("and ) 3 => end in val x = 1 ) ] junk junk type t = int val y = x");
+  assert_eq!(errors.len(), 2);
+}
+
 #[test]
 fn expected() {
   check(
@@ -38,6 +55,16 @@ val _ 3
   );
 }
 
+#[test]
+fn expected_one_of() {
+  check(
+    r#"
+fun f x
+(**   ^ expected `:` or `=` *)
+"#,
+  );
+}
+
 #[test]
 fn invalid_char() {
   check(
@@ -53,8 +80,16 @@ fn unclosed_comment() {
   check(
     r#"
 (**       vv unclosed comment *)
-val x = 3 (*
-"#,
+val x = 3 (*"#,
+  );
+}
+
+#[test]
+fn unclosed_comment_nested() {
+  check(
+    r#"
+(**       vvvvvvvvvvvvvv unclosed comment *)
+val x = 3 (* a (* b *) c"#,
   );
 }
 
@@ -0,0 +1,61 @@
+//! Statistics about an analysis.
+
+use crate::check::{analyze, analyze_multi};
+
+#[test]
+fn no_errors() {
+  let (an, _) = analyze(
+    r#"
+val id = fn x => x
+val _ = id 3
+"#,
+  );
+  let stats = an.stats();
+  assert_eq!(stats.source_files, 1);
+  assert!(stats.meta_vars > 0);
+  assert!(stats.solved_meta_vars > 0);
+  assert!(stats.solved_meta_vars <= stats.meta_vars);
+  assert!(stats.errors_by_code.is_empty());
+}
+
+#[test]
+fn errors_by_code() {
+  let (an, _) = analyze(
+    r#"
+val _ = nope
+val _ = also_nope
+val _ : int = "hi"
+"#,
+  );
+  let stats = an.stats();
+  assert_eq!(stats.errors_by_code.get(&5001), Some(&2));
+  assert_eq!(stats.errors_by_code.get(&5006), Some(&1));
+}
+
+#[test]
+fn multiple_files() {
+  let (an, _) = analyze_multi(&["fun id a = a", r#"val y = id "hi""#]);
+  let stats = an.stats();
+  // the same declarations in one file generate and solve the same meta vars.
+  let (one, _) = analyze("fun id a = a\nval y = id \"hi\"");
+  let one = one.stats();
+  assert_eq!(stats.source_files, 2);
+  assert_eq!(one.source_files, 1);
+  assert!(stats.meta_vars > 0);
+  assert_eq!(stats.meta_vars, one.meta_vars);
+  assert_eq!(stats.solved_meta_vars, one.solved_meta_vars);
+  assert_eq!(
+    stats.unsolved_meta_vars(),
+    stats.meta_vars - stats.solved_meta_vars
+  );
+}
+
+#[test]
+fn unsolved_saturates() {
+  let stats = analysis::AnalysisStats {
+    meta_vars: 1,
+    solved_meta_vars: 2,
+    ..Default::default()
+  };
+  assert_eq!(stats.unsolved_meta_vars(), 0);
+}
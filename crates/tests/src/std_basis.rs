@@ -158,3 +158,112 @@ val _ = not
 "#,
   );
 }
+
+#[test]
+fn alias_list() {
+  check_with_std_basis(
+    r#"
+val _ = map
+(**     ^^^ hover: map f l applies f to each element of l from left to right *)
+"#,
+  );
+}
+
+#[test]
+fn alias_option() {
+  check_with_std_basis(
+    r#"
+val _ = valOf
+(**     ^^^^^ hover: Alias for `Option.valOf`. *)
+"#,
+  );
+}
+
+#[test]
+fn alias_last_in_file() {
+  check_with_std_basis(
+    r#"
+val _ = print
+(**     ^^^^^ hover: print s prints the string s to the standard output stream *)
+"#,
+  );
+}
+
+/// asserts going to the std basis definition from `(line, character)` in `s` goes to the std basis
+/// file `want_name`, to a definition whose text starts with `want_start`.
+#[track_caller]
+fn check_std_basis_def(s: &str, (line, character): (u32, u32), want_name: &str, want_start: &str) {
+  let (_, input) = crate::check::get_input(&[s], None);
+  let path = input.iter_sources().next().expect("no source").path;
+  let mut an = analysis::Analysis::new(analysis::StdBasis::full(), config::ErrorLines::One);
+  an.get_many(&input);
+  let pos = analysis::Position { line, character };
+  let (name, range) = an
+    .get_std_basis_def(path.wrap(pos))
+    .expect("no std basis def");
+  assert_eq!(name, want_name);
+  let doc = an.std_basis_doc(name).expect("no std basis doc");
+  let got = doc
+    .lines()
+    .nth(range.start.line as usize)
+    .expect("no such line");
+  let got = &got[range.start.character as usize..];
+  assert!(
+    got.starts_with(want_start),
+    "{got} does not start with {want_start}"
+  );
+}
+
+#[test]
+fn std_basis_def_list_map() {
+  check_std_basis_def(
+    r#"
+val _ = List.map
+"#,
+    (1, 13),
+    "list.sml",
+    "val map",
+  );
+}
+
+#[test]
+fn std_basis_def_top_level() {
+  check_std_basis_def(
+    r#"
+val _ = print
+"#,
+    (1, 8),
+    "text-io.sml",
+    "val print",
+  );
+}
+
+#[test]
+fn std_basis_def_regular() {
+  let (an, path) = crate::check::analyze(
+    r#"
+val x = 3
+val _ = x
+"#,
+  );
+  let pos = analysis::Position {
+    line: 2,
+    character: 8,
+  };
+  assert!(an.get_std_basis_def(path.wrap(pos)).is_none());
+}
+
+#[test]
+fn get_one_repeated() {
+  let an = analysis::Analysis::new(analysis::StdBasis::full(), config::ErrorLines::One);
+  let s = r#"
+datatype d = D of int list
+exception E of d
+val _ = D [1] : string
+"#;
+  for _ in 0..3 {
+    let errors = an.get_one(s);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "expected string, found d");
+  }
+}
@@ -0,0 +1,22 @@
+//! Inspecting the final substitution from type inference.
+
+#[test]
+fn apply_id() {
+  let mut fix_env = mlb_statics::STD_BASIS_FIX_ENV.clone();
+  let (_, _, low) = mlb_statics::start_source_file(
+    sml_lower::Config::default(),
+    r#"val _ = (fn x => x) "hi""#,
+    &mut fix_env,
+  );
+  let std_basis = mlb_statics::StdBasis::minimal();
+  let mut syms = std_basis.syms().clone();
+  let mode = sml_statics::Mode::Regular(None);
+  let checked = sml_statics::get(&mut syms, std_basis.basis(), mode, &low.arenas, low.root);
+  assert!(checked.errors.is_empty());
+  let subst = checked.info.subst(&syms);
+  assert!(!subst.is_empty());
+  for (mv, ty) in subst {
+    assert!(mv.starts_with('?'), "not a meta var: {mv}");
+    assert_eq!(ty, "string");
+  }
+}
@@ -0,0 +1,88 @@
+//! Classifying the symbol under the cursor.
+
+use crate::check::analyze;
+use analysis::SymbolKind;
+
+/// returns the kind of the symbol at `line` and `character` in `s`.
+fn symbol_kind(s: &str, line: u32, character: u32) -> Option<SymbolKind> {
+  let (an, path) = analyze(s);
+  an.symbol_kind(path.wrap(analysis::Position { line, character }))
+}
+
+#[test]
+fn value() {
+  assert_eq!(
+    symbol_kind("val x = 1\nval _ = x", 1, 8),
+    Some(SymbolKind::Value)
+  );
+}
+
+#[test]
+fn constructor() {
+  assert_eq!(
+    symbol_kind("datatype d = D\nval _ = D", 1, 8),
+    Some(SymbolKind::Constructor)
+  );
+}
+
+#[test]
+fn exception() {
+  assert_eq!(
+    symbol_kind("exception E\nval _ = E", 1, 8),
+    Some(SymbolKind::Exception)
+  );
+}
+
+#[test]
+fn ty() {
+  assert_eq!(
+    symbol_kind("type t = int\nval _ : t = 1", 1, 8),
+    Some(SymbolKind::Type)
+  );
+}
+
+#[test]
+fn ty_var() {
+  assert_eq!(
+    symbol_kind("fun 'a id (x : 'a) = x", 0, 15),
+    Some(SymbolKind::TyVar)
+  );
+}
+
+#[test]
+fn structure() {
+  assert_eq!(
+    symbol_kind("structure S = struct end\nstructure T = S", 1, 14),
+    Some(SymbolKind::Structure)
+  );
+}
+
+#[test]
+fn signature() {
+  assert_eq!(
+    symbol_kind(
+      "signature SIG = sig end\nstructure S : SIG = struct end",
+      1,
+      14
+    ),
+    Some(SymbolKind::Signature)
+  );
+}
+
+#[test]
+fn functor() {
+  assert_eq!(
+    symbol_kind("functor F () = struct end\nstructure S = F ()", 1, 14),
+    Some(SymbolKind::Functor)
+  );
+}
+
+#[test]
+fn binding_site() {
+  assert_eq!(symbol_kind("datatype d = D", 0, 9), Some(SymbolKind::Type));
+}
+
+#[test]
+fn literal() {
+  assert_eq!(symbol_kind("val _ = 1", 0, 8), None);
+}
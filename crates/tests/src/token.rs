@@ -0,0 +1,71 @@
+//! Getting the lexical tokens of a string.
+
+use sml_syntax::SyntaxKind as SK;
+
+/// checks the kinds and text of the non-whitespace tokens of the one-line `s`.
+#[track_caller]
+fn check(s: &str, want: &[(SK, &str)]) {
+  let got: Vec<_> = analysis::tokens(s)
+    .into_iter()
+    .filter(|&(kind, _)| kind != SK::Whitespace)
+    .map(|(kind, range)| {
+      assert_eq!(range.start.line, 0);
+      assert_eq!(range.end.line, 0);
+      let text = &s[range.start.character as usize..range.end.character as usize];
+      (kind, text)
+    })
+    .collect();
+  assert_eq!(want, got.as_slice());
+}
+
+#[test]
+fn each_category() {
+  check(
+    r#"val x = 1 (* hi *) "s" 'a"#,
+    &[
+      (SK::ValKw, "val"),
+      (SK::Name, "x"),
+      (SK::Eq, "="),
+      (SK::IntLit, "1"),
+      (SK::BlockComment, "(* hi *)"),
+      (SK::StringLit, r#""s""#),
+      (SK::TyVar, "'a"),
+    ],
+  );
+}
+
+#[test]
+fn unclosed_string() {
+  check(
+    r#"val s = "abc"#,
+    &[
+      (SK::ValKw, "val"),
+      (SK::Name, "s"),
+      (SK::Eq, "="),
+      (SK::Invalid, r#""abc"#),
+    ],
+  );
+}
+
+#[test]
+fn unclosed_comment() {
+  check(
+    "val x = 1 (* abc",
+    &[
+      (SK::ValKw, "val"),
+      (SK::Name, "x"),
+      (SK::Eq, "="),
+      (SK::IntLit, "1"),
+      (SK::Invalid, "(* abc"),
+    ],
+  );
+}
+
+#[test]
+fn multi_line() {
+  let got = analysis::tokens("val x =\n  2");
+  let (kind, range) = got.last().unwrap();
+  assert_eq!(*kind, SK::IntLit);
+  assert_eq!((range.start.line, range.start.character), (1, 2));
+  assert_eq!((range.end.line, range.end.character), (1, 3));
+}
@@ -0,0 +1,70 @@
+//! Getting the types of top-level bindings by name.
+
+use crate::check::{analyze, analyze_multi};
+
+#[track_caller]
+fn check(s: &str, name: &str, want: Option<&str>) {
+  let (an, path) = analyze(s);
+  assert_eq!(want, an.top_level_type(path, name).as_deref());
+}
+
+#[test]
+fn polymorphic_fun() {
+  let s = r#"
+fun id x = x
+val _ = id 3 + 1
+"#;
+  check(s, "id", Some("'a -> 'a"));
+}
+
+#[test]
+fn monomorphic_val() {
+  check("val x = 3 + 4", "x", Some("int"));
+}
+
+#[test]
+fn equality() {
+  check("fun eq (x, y) = x = y", "eq", Some("''a * ''a -> bool"));
+}
+
+#[test]
+fn shadowed() {
+  let s = r#"
+val x = 3
+val x = false
+"#;
+  check(s, "x", Some("bool"));
+}
+
+#[test]
+fn not_top_level() {
+  let s = r#"
+structure S = struct val y = 1 end
+val x = let val z = 2 in z end
+local val w = 3 in end
+"#;
+  check(s, "y", None);
+  check(s, "z", None);
+  check(s, "w", None);
+}
+
+#[test]
+fn not_val() {
+  check("datatype d = A | B of int", "B", None);
+}
+
+#[test]
+fn undefined() {
+  check("val x = 3", "nope", None);
+}
+
+#[test]
+fn other_file() {
+  let (an, paths) = analyze_multi(&["fun id x = x", "val y = id 3"]);
+  assert_eq!(
+    Some("'a -> 'a"),
+    an.top_level_type(paths[0], "id").as_deref()
+  );
+  assert_eq!(None, an.top_level_type(paths[1], "id"));
+  assert_eq!(Some("int"), an.top_level_type(paths[1], "y").as_deref());
+}
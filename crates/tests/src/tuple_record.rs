@@ -0,0 +1,191 @@
+//! Converting between tuples and records with numeric labels.
+
+use crate::check::analyze;
+
+/// asserts converting a tuple to a record at `(line, character)` gives `want`, if anything.
+#[track_caller]
+fn check_to_record(s: &str, (line, character): (u32, u32), want: Option<&str>) {
+  let (an, path) = analyze(s);
+  let pos = analysis::Position { line, character };
+  let got = an.tuple_to_record(path.wrap(pos));
+  assert_eq!(got.as_ref().map(|(_, s)| s.as_str()), want);
+}
+
+/// asserts converting a record to a tuple at `(line, character)` gives `want`, if anything.
+#[track_caller]
+fn check_to_tuple(s: &str, (line, character): (u32, u32), want: Option<&str>) {
+  let (an, path) = analyze(s);
+  let pos = analysis::Position { line, character };
+  let got = an.record_to_tuple(path.wrap(pos));
+  assert_eq!(got.as_ref().map(|(_, s)| s.as_str()), want);
+}
+
+#[test]
+fn exp_to_record() {
+  check_to_record(
+    r#"
+val _ = (1, true)
+"#,
+    (1, 9),
+    Some("{1 = 1, 2 = true}"),
+  );
+}
+
+#[test]
+fn exp_to_record_range() {
+  let (an, path) = analyze(
+    r#"
+val _ = (1, true)
+"#,
+  );
+  let pos = analysis::Position {
+    line: 1,
+    character: 9,
+  };
+  let (range, _) = an.tuple_to_record(path.wrap(pos)).unwrap();
+  assert_eq!((range.start.line, range.start.character), (1, 8));
+  assert_eq!((range.end.line, range.end.character), (1, 17));
+}
+
+#[test]
+fn exp_to_tuple() {
+  check_to_tuple(
+    r#"
+val _ = {1 = 1, 2 = true}
+"#,
+    (1, 13),
+    Some("(1, true)"),
+  );
+}
+
+#[test]
+fn exp_innermost() {
+  check_to_record(
+    r#"
+val _ = (1, (2, 3))
+"#,
+    (1, 13),
+    Some("{1 = 2, 2 = 3}"),
+  );
+}
+
+#[test]
+fn pat_to_record() {
+  check_to_record(
+    r#"
+fun f (a, b) = a + b
+"#,
+    (1, 7),
+    Some("{1 = a, 2 = b}"),
+  );
+}
+
+#[test]
+fn pat_to_tuple() {
+  check_to_tuple(
+    r#"
+fun f {1 = a, 2 = b} = a + b
+"#,
+    (1, 11),
+    Some("(a, b)"),
+  );
+}
+
+#[test]
+fn ty_to_record() {
+  check_to_record(
+    r#"
+type t = int * bool
+"#,
+    (1, 9),
+    Some("{1 : int, 2 : bool}"),
+  );
+}
+
+#[test]
+fn ty_to_tuple() {
+  check_to_tuple(
+    r#"
+type t = {1 : int -> int, 2 : bool}
+"#,
+    (1, 30),
+    Some("(int -> int) * bool"),
+  );
+}
+
+#[test]
+fn ty_to_tuple_con_arg() {
+  check_to_tuple(
+    r#"
+type t = {1 : int, 2 : bool} list
+"#,
+    (1, 10),
+    Some("(int * bool)"),
+  );
+}
+
+#[test]
+fn ty_to_tuple_in_tuple() {
+  check_to_tuple(
+    r#"
+type t = {1 : int, 2 : bool} * string
+"#,
+    (1, 10),
+    Some("(int * bool)"),
+  );
+}
+
+#[test]
+fn ty_to_tuple_fn_param() {
+  check_to_tuple(
+    r#"
+type t = {1 : int, 2 : bool} -> string
+"#,
+    (1, 10),
+    Some("(int * bool)"),
+  );
+}
+
+#[test]
+fn ty_to_tuple_fn_res() {
+  check_to_tuple(
+    r#"
+type t = string -> {1 : int, 2 : bool}
+"#,
+    (1, 20),
+    Some("int * bool"),
+  );
+}
+
+#[test]
+fn one_label() {
+  check_to_tuple(
+    r#"
+val _ = {1 = 1}
+"#,
+    (1, 13),
+    None,
+  );
+}
+
+#[test]
+fn label_gap() {
+  check_to_tuple(
+    r#"
+val _ = {1 = 1, 3 = true}
+"#,
+    (1, 13),
+    None,
+  );
+}
+
+#[test]
+fn labels_out_of_order() {
+  check_to_tuple(
+    r#"
+val _ = {2 = true, 1 = 1}
+"#,
+    (1, 13),
+    None,
+  );
+}
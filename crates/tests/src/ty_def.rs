@@ -0,0 +1,79 @@
+//! Going to the definition of the type of an expression.
+
+use crate::check::analyze;
+
+/// asserts going to the type definition from `(line, character)` goes to the definition starting
+/// on line `want`, if any.
+#[track_caller]
+fn check_ty_def(s: &str, (line, character): (u32, u32), want: Option<u32>) {
+  let (an, path) = analyze(s);
+  let pos = analysis::Position { line, character };
+  let got = an.get_type_definition(path.wrap(pos));
+  assert_eq!(got.map(|x| x.val.start.line), want);
+}
+
+#[test]
+fn smoke() {
+  check_ty_def(
+    r#"
+datatype foo = Foo
+val x = Foo
+val _ = x
+"#,
+    (3, 8),
+    Some(1),
+  );
+}
+
+#[test]
+fn std_basis_only() {
+  check_ty_def(
+    r#"
+val x = [1, 2]
+val _ = x
+"#,
+    (2, 8),
+    None,
+  );
+}
+
+#[test]
+fn skip_std_basis_head() {
+  check_ty_def(
+    r#"
+datatype foo = Foo
+val xs = [Foo]
+val _ = xs
+"#,
+    (3, 8),
+    Some(1),
+  );
+}
+
+#[test]
+fn outermost_wins() {
+  check_ty_def(
+    r#"
+datatype foo = Foo
+datatype 'a box = Box of 'a
+val x = Box [Foo]
+val _ = x
+"#,
+    (4, 8),
+    Some(2),
+  );
+}
+
+#[test]
+fn outermost_under_std_basis_wins() {
+  check_ty_def(
+    r#"
+datatype foo = Foo
+datatype 'a box = Box of 'a
+val xs = [Box Foo]
+val _ = xs
+"#,
+    (4, 8),
+    Some(2),
+  );
+}
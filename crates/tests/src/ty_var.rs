@@ -1,4 +1,4 @@
-use crate::check::check;
+use crate::check::{analyze, check, fail};
 
 #[test]
 fn across_var() {
@@ -114,6 +114,41 @@ end
   );
 }
 
+#[test]
+fn implicit_scope_spec_val_only() {
+  check(
+    r#"
+signature SIG = sig
+  type 'a t
+  val empty : 'a t
+  val cons : 'a * 'a t -> 'a t
+end where type 'a t = 'a list
+"#,
+  );
+}
+
+#[test]
+fn free_in_where_type() {
+  check(
+    r#"
+signature SIG = sig type t end where type t = 'a list
+(**                                           ^^ undefined type variable: 'a *)
+"#,
+  );
+}
+
+#[test]
+fn free_in_ty_spec() {
+  check(
+    r#"
+signature SIG = sig
+  type t = 'a list
+(**        ^^ undefined type variable: 'a *)
+end
+"#,
+  );
+}
+
 #[test]
 fn implicit_scope_1() {
   check(
@@ -155,3 +190,150 @@ val _ =
 "#,
   );
 }
+
+#[test]
+fn annotate_in_scope() {
+  check(
+    r#"
+fun 'a f (x : 'a) = (x : 'a)
+val _ = f 3 : int
+"#,
+  );
+}
+
+#[test]
+fn annotate_out_of_scope_type() {
+  check(
+    r#"
+type t = 'a list
+(**      ^^ undefined type variable: 'a *)
+"#,
+  );
+}
+
+#[test]
+fn annotate_out_of_scope_datatype() {
+  check(
+    r#"
+datatype d = D of 'a
+(**               ^^ undefined type variable: 'a *)
+"#,
+  );
+}
+
+#[test]
+fn annotate_recovered() {
+  fail(
+    r#"
+val _ = fn (x : 'a
+"#,
+  );
+}
+
+#[test]
+fn hover_implicit() {
+  check(
+    r#"
+val _ = fn x : 'a => x
+(**            ^^ hover: `'a` is a type variable. It is implicitly bound at the `val` on line 2. *)
+"#,
+  );
+}
+
+#[test]
+fn hover_explicit() {
+  check(
+    r#"
+fun 'a id (x : 'a) = x
+(**            ^^ hover: `'a` is a type variable. It is explicitly bound at the `fun` on line 2. *)
+"#,
+  );
+}
+
+#[test]
+fn hover_equality() {
+  check(
+    r#"
+val ''a eq = fn (x : ''a, y) => x = y
+(**                  ^^^ hover: `''a` is an equality type variable. It is explicitly bound *)
+"#,
+  );
+}
+
+#[test]
+fn hover_datatype() {
+  check(
+    r#"
+datatype 'a box = Box of 'a
+(**                      ^^ hover: `'a` is a type variable. It is bound at the `datatype` on line 2. *)
+"#,
+  );
+}
+
+/// asserts the type variables of the type at `(line, character)` are `want`.
+#[track_caller]
+fn check_ty_vars(s: &str, (line, character): (u32, u32), want: &[&str]) {
+  let (an, path) = analyze(s);
+  let pos = analysis::Position { line, character };
+  let got = an.free_ty_vars(path.wrap(pos)).expect("no type vars");
+  assert_eq!(got, want);
+}
+
+#[test]
+fn free_id_use() {
+  check_ty_vars(
+    r#"
+val id = fn x => x
+val _ = id 3
+"#,
+    (2, 8),
+    &["'a"],
+  );
+}
+
+#[test]
+fn free_id_def() {
+  check_ty_vars(
+    r#"
+val id = fn x => x
+"#,
+    (1, 4),
+    &["'a"],
+  );
+}
+
+#[test]
+fn free_two() {
+  check_ty_vars(
+    r#"
+fun pair x y = (x, y)
+val _ = pair 3 "hi"
+"#,
+    (2, 8),
+    &["'a", "'b"],
+  );
+}
+
+#[test]
+fn free_equality() {
+  check_ty_vars(
+    r#"
+fun eq x y = x = y
+val _ = eq 3 4
+"#,
+    (2, 8),
+    &["''a"],
+  );
+}
+
+#[test]
+fn free_monomorphic() {
+  check_ty_vars(
+    r#"
+val x = 3
+val _ = x
+"#,
+    (2, 8),
+    &[],
+  );
+}
@@ -0,0 +1,62 @@
+//! Tests for `val rec`.
+
+use crate::check::check;
+
+#[test]
+fn not_fn() {
+  check(
+    r#"
+val rec x = 3
+(**         ^ the expression for a `val rec` was not a `fn` *)
+"#,
+  );
+}
+
+#[test]
+fn not_fn_app() {
+  check(
+    r#"
+val mkAdd3 = fn () => fn x => x + 3
+val rec add3 = mkAdd3 ()
+(**            ^^^^^^^^^ the expression for a `val rec` was not a `fn` *)
+"#,
+  );
+}
+
+#[test]
+fn typed() {
+  check(
+    r#"
+val rec f = (fn x => x) : int -> int
+val _ = f 3 : int
+"#,
+  );
+}
+
+#[test]
+fn typed_paren() {
+  check(
+    r#"
+val rec f = (fn x => x : int -> int)
+"#,
+  );
+}
+
+#[test]
+fn typed_nested() {
+  check(
+    r#"
+val rec f = ((fn 0 => 0 | n => f (n - 1)) : int -> int) : int -> int
+"#,
+  );
+}
+
+#[test]
+fn mutual() {
+  check(
+    r#"
+val rec even = fn 0 => true | n => odd (n - 1)
+and odd = fn 0 => false | n => even (n - 1)
+"#,
+  );
+}
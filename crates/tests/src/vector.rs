@@ -0,0 +1,103 @@
+//! Vector expressions and patterns, which are only allowed when enabled in the config.
+
+use crate::check::{check, check_with_config};
+
+const CONFIG: &str = r#"
+version = 1
+[language.extensions]
+vectors = true
+"#;
+
+#[test]
+fn exp() {
+  check_with_config(
+    CONFIG,
+    r#"
+val xs : int vector = #[1, 2, 3]
+val _ : int = Vector.length xs
+val _ : int = Vector.sub (#[4, 5], 0)
+"#,
+  );
+}
+
+#[test]
+fn exp_empty() {
+  check_with_config(
+    CONFIG,
+    r#"
+val _ : string vector = #[]
+"#,
+  );
+}
+
+#[test]
+fn exp_elem_mismatch() {
+  check_with_config(
+    CONFIG,
+    r#"
+val _ = #[1, "hi"]
+(**          ^^^^ expected int, found string *)
+"#,
+  );
+}
+
+#[test]
+fn pat() {
+  check_with_config(
+    CONFIG,
+    r#"
+fun sum #[] = 0
+  | sum #[x] = x
+  | sum #[x, y] = x + y
+  | sum _ = 0
+val _ : int = sum #[1, 2]
+"#,
+  );
+}
+
+#[test]
+fn pat_non_exhaustive() {
+  check_with_config(
+    CONFIG,
+    r#"
+val #[x, y] = #[1, 2]
+(** ^^^^^^^ non-exhaustive binding: missing _ *)
+"#,
+  );
+}
+
+#[test]
+fn pat_unreachable() {
+  check_with_config(
+    CONFIG,
+    r#"
+val _ =
+  case #[1] of
+    #[x] => x
+  | #[y] => y
+(** ^^^^ unreachable pattern *)
+  | _ => 0
+"#,
+  );
+}
+
+#[test]
+fn disabled() {
+  check(
+    r#"
+val _ : int vector = #[1, 2]
+(**                  ^^^^^^^ unsupported language construct: vector expressions *)
+"#,
+  );
+}
+
+#[test]
+fn disabled_pat() {
+  check(
+    r#"
+fun f #[] = 0
+(**   ^^^ unsupported language construct: vector patterns *)
+  | f _ = 1
+"#,
+  );
+}